@@ -0,0 +1,100 @@
+use criterion::BatchSize;
+use criterion::Criterion;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use std::iter::repeat_with;
+
+use futuresdr::blocks::ComplexToInt;
+use futuresdr::blocks::Conversion;
+use futuresdr::blocks::Convert;
+use futuresdr::blocks::IntToComplex;
+use futuresdr::prelude::*;
+use futuresdr::runtime::mocker::Mocker;
+use futuresdr::runtime::mocker::Reader;
+use futuresdr::runtime::mocker::Writer;
+
+type MockConvert<C> = Convert<C, Reader<<C as Conversion>::In>, Writer<<C as Conversion>::Out>>;
+
+fn mocker<C: Conversion>(conversion: C, input: Vec<C::In>, n_out: usize) -> Mocker<MockConvert<C>> {
+    let block: MockConvert<C> = Convert::new(conversion);
+    let mut mocker = Mocker::new(block);
+    mocker.input().set(input);
+    mocker.output().reserve(n_out);
+    mocker
+}
+
+pub fn convert(c: &mut Criterion) {
+    let n_samp = 1024 * 1024;
+    let bytes: Vec<u8> = repeat_with(rand::random::<u8>).take(2 * n_samp).collect();
+    let shorts: Vec<i16> = repeat_with(rand::random::<i16>).take(2 * n_samp).collect();
+    let samples: Vec<Complex32> = repeat_with(|| {
+        Complex32::new(
+            rand::random::<f32>() * 2.0 - 1.0,
+            rand::random::<f32>() * 2.0 - 1.0,
+        )
+    })
+    .take(n_samp)
+    .collect();
+
+    let mut group = c.benchmark_group("convert");
+
+    group.throughput(criterion::Throughput::Elements(n_samp as u64));
+
+    group.bench_function(format!("scalar-u8-to-c32-{n_samp}"), |b| {
+        let mut out = vec![Complex32::default(); n_samp];
+        b.iter(|| {
+            for (i, o) in bytes.chunks_exact(2).zip(out.iter_mut()) {
+                *o = Complex32::new((i[0] as f32 - 127.5) / 127.5, (i[1] as f32 - 127.5) / 127.5);
+            }
+            std::hint::black_box(&mut out);
+        });
+    });
+
+    group.bench_function(format!("block-u8-to-c32-{n_samp}"), |b| {
+        b.iter_batched(
+            || mocker(IntToComplex::<u8>::new(), bytes.clone(), n_samp),
+            |mut m| m.run(),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function(format!("block-i16-to-c32-{n_samp}"), |b| {
+        b.iter_batched(
+            || mocker(IntToComplex::<i16>::new(), shorts.clone(), n_samp),
+            |mut m| m.run(),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function(format!("scalar-c32-to-i16-{n_samp}"), |b| {
+        let mut out = vec![0i16; 2 * n_samp];
+        b.iter(|| {
+            for (i, o) in samples.iter().zip(out.chunks_exact_mut(2)) {
+                o[0] = (i.re * 32768.0).round() as i16;
+                o[1] = (i.im * 32768.0).round() as i16;
+            }
+            std::hint::black_box(&mut out);
+        });
+    });
+
+    group.bench_function(format!("block-c32-to-i16-{n_samp}"), |b| {
+        b.iter_batched(
+            || mocker(ComplexToInt::<i16>::new(), samples.clone(), 2 * n_samp),
+            |mut m| m.run(),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function(format!("block-c32-to-u8-{n_samp}"), |b| {
+        b.iter_batched(
+            || mocker(ComplexToInt::<u8>::new(), samples.clone(), 2 * n_samp),
+            |mut m| m.run(),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, convert);
+criterion_main!(benches);
@@ -33,6 +33,13 @@ where
             d_resamp: FirInterpolator::<T>::new(),
         }
     }
+
+    /// Change the resampling ratio, keeping the current fractional delay.
+    ///
+    /// Allows to adapt the ratio slowly, e.g., to compensate clock drift.
+    pub fn set_ratio(&mut self, resamp_ratio: f32) {
+        self.d_mu_inc = 1.0 / resamp_ratio;
+    }
 }
 
 impl<T> StatefulFilter<T, T, f32> for Resampler<T>
@@ -73,3 +80,25 @@ where
         FirInterpolator::<T>::lookahead() + 1
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_ratio() {
+        let input = vec![1.0f32; 1007];
+        let mut output = vec![0.0f32; 4096];
+
+        let mut r = Resampler::<f32>::new(1.0);
+        let (consumed, produced, _) = r.filter(&input, &mut output);
+        assert_eq!(consumed, 1000);
+        assert_eq!(produced, 1000);
+
+        r.set_ratio(1.25);
+        let (consumed, produced, _) = r.filter(&input, &mut output);
+        assert_eq!(consumed, 1000);
+        assert_eq!(produced, 1250);
+        assert!(output[..produced].iter().all(|v| (v - 1.0).abs() < 1e-3));
+    }
+}
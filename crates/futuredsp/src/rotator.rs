@@ -19,6 +19,13 @@ impl Rotator {
         }
     }
 
+    /// Set the phase increment per sample, keeping the current phase
+    pub fn set_phase_incr(&mut self, phase_incr: f32) {
+        self.phase_incr = Complex32::from_polar(1.0, phase_incr);
+        // avoid drift of the magnitude
+        self.phase /= self.phase.norm();
+    }
+
     /// Rotate buffer inplace
     pub fn rotate_inplace(&mut self, buffer: &mut [Complex32]) {
         for v in buffer.iter_mut() {
@@ -1,5 +1,6 @@
 //! Macros to make working with FutureSDR a bit nicer.
 use proc_macro::TokenStream;
+use quote::format_ident;
 use quote::quote;
 use syn::Attribute;
 use syn::Data;
@@ -439,9 +440,11 @@ fn is_vec(type_path: &syn::TypePath) -> bool {
         input,
         output,
         message_inputs,
+        message_input_schema,
         message_outputs,
         blocking,
         type_name,
+        tag_propagation,
         null_kernel
     )
 )]
@@ -454,9 +457,11 @@ pub fn derive_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let mut message_inputs: Vec<Ident> = Vec::new();
     let mut message_input_names: Vec<String> = Vec::new();
     let mut message_output_names: Vec<String> = Vec::new();
+    let mut message_input_schemas: Vec<(Ident, proc_macro2::TokenStream)> = Vec::new();
     let mut kernel = quote! {};
     let mut blocking = quote! { false };
     let mut type_name = struct_name.to_string();
+    let mut tag_propagation = quote! { Custom };
 
     // remove defaults from generics
     let mut generics = generics.clone();
@@ -955,6 +960,8 @@ pub fn derive_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                     }
                 }
             }
+        } else if attr.path().is_ident("message_input_schema") {
+            message_input_schemas.push(parse_message_input_schema(attr));
         } else if attr.path().is_ident("message_outputs") {
             let nested = attr
                 .parse_args_with(
@@ -992,6 +999,25 @@ pub fn derive_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             } else {
                 panic!("type_name attribute should be in the form type_name(foo)");
             }
+        } else if attr.path().is_ident("tag_propagation") {
+            let nested = attr
+                .parse_args_with(
+                    syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated,
+                )
+                .unwrap();
+            let policy = match nested.get(0) {
+                Some(Meta::Path(p)) => p.get_ident().map(|i| i.to_string()),
+                _ => None,
+            };
+            tag_propagation = match policy.as_deref() {
+                Some("none") => quote! { None },
+                Some("one_to_one") => quote! { OneToOne },
+                Some("all_to_all") => quote! { AllToAll },
+                Some("custom") => quote! { Custom },
+                _ => panic!(
+                    "tag_propagation attribute should be one of tag_propagation(none), tag_propagation(one_to_one), tag_propagation(all_to_all), or tag_propagation(custom)"
+                ),
+            };
         }
     }
 
@@ -1007,17 +1033,49 @@ pub fn derive_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         }
     });
 
-    // Generate match arms for the handle method
+    // Generate match arms for the handle method, converting the argument and return value
     let handler_matches =
         message_inputs
             .iter()
             .zip(message_input_names.clone())
             .map(|(handler, handler_name)| {
                 quote! {
-                    #handler_name  => self.#handler(io, mio, meta, p).await,
+                    #handler_name => match ::futuresdr::runtime::handler_arg(
+                        <Self as ::futuresdr::runtime::KernelInterface>::type_name(),
+                        #handler_name,
+                        p,
+                    ) {
+                        Some(a) => self
+                            .#handler(io, mio, meta, a)
+                            .await
+                            .map(::futuresdr::runtime::HandlerReturn::into_pmt),
+                        None => Ok(Pmt::InvalidValue),
+                    },
                 }
             });
 
+    // Schemas of the handlers, defaulting to an empty schema
+    let handler_schemas = message_inputs.iter().map(|handler| {
+        match message_input_schemas.iter().find(|(h, _)| h == handler) {
+            Some((_, schema)) => schema.clone(),
+            None => quote! { ::futuresdr::runtime::MessageInputSchema::default() },
+        }
+    });
+    for (h, _) in &message_input_schemas {
+        if !message_inputs.contains(h) {
+            panic!("message_input_schema for unknown message input {h}");
+        }
+    }
+
+    // Infer handler signatures from closures that are never called
+    let handler_signatures = message_inputs.iter().map(|handler| {
+        quote! {
+            ::futuresdr::runtime::handler_signature::<Self, _, _, _>(|s, io, mio, meta, a| {
+                ::futuresdr::runtime::handler_return(&s.#handler(io, mio, meta, a))
+            })
+        }
+    });
+
     let expanded = quote! {
 
         impl #generics #struct_name #unconstraint_generics
@@ -1036,6 +1094,9 @@ pub fn derive_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 static TYPE_NAME: &str = #type_name;
                 TYPE_NAME
             }
+            fn tag_propagation() -> ::futuresdr::runtime::TagPropagation {
+                ::futuresdr::runtime::TagPropagation::#tag_propagation
+            }
             fn stream_inputs(&self) -> Vec<String> {
                 let mut names = vec![];
                 #(#stream_inputs_names)*
@@ -1084,6 +1145,12 @@ pub fn derive_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 static MESSAGE_INPUTS: &[&str] = &[#(#message_input_names),*];
                 MESSAGE_INPUTS
             }
+            fn message_input_signatures() -> Vec<::futuresdr::runtime::HandlerSignature> {
+                vec![#(#handler_signatures),*]
+            }
+            fn message_input_schemas(&self) -> Vec<::futuresdr::runtime::MessageInputSchema> {
+                vec![#(#handler_schemas),*]
+            }
             fn message_outputs() -> &'static[&'static str] {
                 static MESSAGE_OUTPUTS: &[&str] = &[#(#message_output_names),*];
                 MESSAGE_OUTPUTS
@@ -1119,6 +1186,64 @@ pub fn derive_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     proc_macro::TokenStream::from(expanded)
 }
 
+/// Parse `#[message_input_schema(handler, key = value, ...)]` into a `MessageInputSchema`
+/// expression.
+fn parse_message_input_schema(attr: &Attribute) -> (Ident, proc_macro2::TokenStream) {
+    let nested = attr
+        .parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)
+        .unwrap();
+    let mut nested = nested.into_iter();
+    let handler = match nested.next() {
+        Some(Meta::Path(p)) if p.get_ident().is_some() => p.get_ident().unwrap().clone(),
+        _ => panic!("message_input_schema has to start with the name of the message input"),
+    };
+
+    let mut fields = Vec::new();
+    for m in nested {
+        let Meta::NameValue(m) = m else {
+            panic!("message_input_schema properties have to be name-value pairs");
+        };
+        let key = m
+            .path
+            .get_ident()
+            .map(|i| i.to_string())
+            .unwrap_or_default();
+        let value = m.value;
+        let field = match key.as_str() {
+            "description" | "unit" => {
+                let key = format_ident!("{}", key);
+                quote! { #key: Some(::std::string::ToString::to_string(#value)) }
+            }
+            "min" | "max" | "step" => {
+                let key = format_ident!("{}", key);
+                quote! { #key: Some((#value) as f64) }
+            }
+            "kind" => quote! { kind: Some(::futuresdr::runtime::PmtKind::#value) },
+            "getter" => quote! {
+                value: Some(::futuresdr::runtime::HandlerReturn::into_pmt(self.#value()))
+            },
+            _ => panic!(
+                "unknown message_input_schema property {key}, expected description, kind, min, max, step, unit, or getter"
+            ),
+        };
+        fields.push(field);
+    }
+
+    // all seven fields are set, if each property is given
+    let rest = if fields.len() < 7 {
+        quote! { ..::futuresdr::runtime::MessageInputSchema::default() }
+    } else {
+        quote! {}
+    };
+    let schema = quote! {
+        ::futuresdr::runtime::MessageInputSchema {
+            #(#fields,)*
+            #rest
+        }
+    };
+    (handler, schema)
+}
+
 #[allow(dead_code)]
 fn pretty_print(ts: &proc_macro2::TokenStream) -> String {
     let syntax_tree = syn::parse2(ts.clone()).unwrap();
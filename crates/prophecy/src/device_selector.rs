@@ -0,0 +1,174 @@
+use futuresdr_types::ChannelDescription;
+use futuresdr_types::DeviceDescription;
+use futuresdr_types::Pmt;
+use leptos::prelude::*;
+
+use crate::RuntimeHandle;
+
+/// Device and configuration chosen in a [`DeviceSelector`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceSelection {
+    /// Seify arguments to open the device
+    pub args: String,
+    /// Channel
+    pub channel: usize,
+    /// Antenna, if one was chosen
+    pub antenna: Option<String>,
+}
+
+/// Format a range, encoded as `Pmt`, with a unit.
+fn format_range(range: &Pmt, unit: &str) -> String {
+    let Pmt::VecPmt(items) = range else {
+        return "n/a".to_string();
+    };
+    let f = |p: Option<&Pmt>| match p {
+        Some(Pmt::F64(v)) => format!("{v}"),
+        _ => "?".to_string(),
+    };
+    items
+        .iter()
+        .map(|i| match i {
+            Pmt::MapStrPmt(m) => match m.get("step") {
+                Some(step) => format!(
+                    "{} to {} in steps of {} {unit}",
+                    f(m.get("min")),
+                    f(m.get("max")),
+                    f(Some(step))
+                ),
+                None => format!("{} to {} {unit}", f(m.get("min")), f(m.get("max"))),
+            },
+            v => format!("{} {unit}", f(Some(v))),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn channels(d: &DeviceDescription, tx: bool) -> &Vec<ChannelDescription> {
+    if tx { &d.tx } else { &d.rx }
+}
+
+#[component]
+/// Device Selector
+///
+/// Lists the SDR devices of a runtime with their channels, antennas, and supported frequency,
+/// sample rate, and gain ranges. The chosen device and configuration are written to `selection`,
+/// allowing to build a flowgraph for it.
+pub fn DeviceSelector(
+    rt_handle: RuntimeHandle,
+    selection: RwSignal<Option<DeviceSelection>>,
+    /// Seify arguments to filter the devices, e.g., `driver=soapy`
+    #[prop(optional)]
+    args: Option<String>,
+    /// Show TX instead of RX channels
+    #[prop(optional)]
+    tx: bool,
+    #[prop(into, optional)] select_class: String,
+    #[prop(into, optional)] label_class: String,
+) -> impl IntoView {
+    let devices = LocalResource::new(move || {
+        let rt_handle = rt_handle.clone();
+        let args = args.clone();
+        async move { rt_handle.devices(args).await.unwrap_or_default() }
+    });
+    let device = RwSignal::new(0usize);
+    let channel = RwSignal::new(0usize);
+    let antenna = RwSignal::new(None::<String>);
+
+    Effect::new(move |_| {
+        let s = devices.get().and_then(|d| {
+            d.get(device.get()).map(|d| DeviceSelection {
+                args: d.args.clone(),
+                channel: channel.get(),
+                antenna: antenna.get(),
+            })
+        });
+        selection.set(s);
+    });
+
+    view! {
+        {move || match devices.get() {
+            None => view! { <p>"Searching devices..."</p> }.into_any(),
+            Some(devs) if devs.is_empty() => view! { <p>"No devices found"</p> }.into_any(),
+            Some(devs) => {
+                let chans = devs
+                    .get(device.get())
+                    .map(|d| channels(d, tx).clone())
+                    .unwrap_or_default();
+                let chan = chans.get(channel.get()).cloned();
+                view! {
+                    <label class=label_class.clone()>"Device"</label>
+                    <select
+                        class=select_class.clone()
+                        on:change=move |ev| {
+                            device.set(event_target_value(&ev).parse().unwrap_or(0));
+                            channel.set(0);
+                            antenna.set(None);
+                        }
+                    >
+                        {devs
+                            .iter()
+                            .enumerate()
+                            .map(|(i, d)| {
+                                view! {
+                                    <option value=i.to_string() selected=i == device.get_untracked()>
+                                        {format!("{} ({})", d.driver, d.args)}
+                                    </option>
+                                }
+                            })
+                            .collect::<Vec<_>>()}
+                    </select>
+                    <label class=label_class.clone()>"Channel"</label>
+                    <select
+                        class=select_class.clone()
+                        on:change=move |ev| {
+                            channel.set(event_target_value(&ev).parse().unwrap_or(0));
+                            antenna.set(None);
+                        }
+                    >
+                        {(0..chans.len())
+                            .map(|i| {
+                                view! {
+                                    <option value=i.to_string() selected=i == channel.get_untracked()>
+                                        {i.to_string()}
+                                    </option>
+                                }
+                            })
+                            .collect::<Vec<_>>()}
+                    </select>
+                    {chan
+                        .map(|c| {
+                            view! {
+                                <label class=label_class.clone()>"Antenna"</label>
+                                <select
+                                    class=select_class.clone()
+                                    on:change=move |ev| antenna.set(Some(event_target_value(&ev)))
+                                >
+                                    {c
+                                        .antennas
+                                        .iter()
+                                        .map(|a| {
+                                            view! {
+                                                <option
+                                                    value=a.clone()
+                                                    selected=Some(a) == antenna.get_untracked().as_ref()
+                                                >
+                                                    {a.clone()}
+                                                </option>
+                                            }
+                                        })
+                                        .collect::<Vec<_>>()}
+                                </select>
+                                <ul>
+                                    <li>"Frequency: " {format_range(&c.frequency_range, "Hz")}</li>
+                                    <li>"Sample rate: " {format_range(&c.sample_rate_range, "Hz")}</li>
+                                    <li>"Gain: " {format_range(&c.gain_range, "dB")}</li>
+                                    <li>"Gain elements: " {c.gain_elements.join(", ")}</li>
+                                </ul>
+                            }
+                        })}
+                }
+                    .into_any()
+            }
+        }}
+    }
+}
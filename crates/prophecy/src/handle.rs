@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use futuresdr::runtime;
+use futuresdr_types::DeviceDescription;
 use futuresdr_types::FlowgraphDescription;
 use futuresdr_types::FlowgraphId;
 use futuresdr_types::Pmt;
@@ -146,6 +147,20 @@ impl RuntimeHandle {
             )),
         }
     }
+    /// Enumerate SDR devices, optionally filtered by seify `args`
+    pub async fn devices(&self, args: Option<String>) -> Result<Vec<DeviceDescription>, Error> {
+        match self {
+            Self::Remote(u) => {
+                let mut request = Request::get(&format!("{u}api/devices/"));
+                if let Some(args) = args {
+                    request = request.query([("args", args)]);
+                }
+                Ok(request.send().await?.json().await?)
+            }
+            // hardware is not accessible from the browser
+            Self::Web(_) => Ok(Vec::new()),
+        }
+    }
 }
 
 /// Reference to a FutureSDR Flowgraph
@@ -0,0 +1,108 @@
+use futuresdr_types::MessageInputSchema;
+use futuresdr_types::Pmt;
+use futuresdr_types::PortId;
+use leptos::html::Input;
+use leptos::logging::*;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos::wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+
+use crate::FlowgraphHandle;
+
+const ENTER_KEY: u32 = 13;
+
+/// Parse user input according to the expected kind of the schema.
+fn parse(v: &str, schema: &MessageInputSchema) -> Option<Pmt> {
+    schema
+        .kind
+        .as_ref()
+        .and_then(|k| Pmt::from_string(v, k))
+        .or_else(|| v.parse::<Pmt>().ok())
+}
+
+#[component]
+/// Control for a message handler, generated from its [`MessageInputSchema`]
+///
+/// Handlers with a range are controlled by a slider, others by a text input. The PMT is
+/// constructed according to the `kind` of the schema.
+pub fn HandlerControl<P: Into<PortId>>(
+    fg_handle: FlowgraphHandle,
+    block_id: usize,
+    handler: P,
+    schema: MessageInputSchema,
+    #[prop(into, optional)] label_class: String,
+    #[prop(into, optional)] input_class: String,
+) -> impl IntoView {
+    let handler = handler.into();
+    let label = schema
+        .description
+        .clone()
+        .unwrap_or_else(|| handler.name().to_string());
+    let unit = schema.unit.clone().unwrap_or_default();
+
+    let send = {
+        let schema = schema.clone();
+        move |v: String| {
+            let Some(pmt) = parse(&v, &schema) else {
+                warn!("cannot parse {v} for handler {:?}", &handler);
+                return;
+            };
+            let mut fg_handle = fg_handle.clone();
+            let handler = handler.clone();
+            spawn_local(async move {
+                let _ = fg_handle.call(block_id, handler, pmt).await;
+            });
+        }
+    };
+
+    let control = match (schema.min, schema.max) {
+        (Some(min), Some(max)) => {
+            let init = schema
+                .value
+                .as_ref()
+                .and_then(|v| f64::try_from(v).ok())
+                .unwrap_or(min);
+            view! {
+                <input
+                    type="range"
+                    min=min
+                    max=max
+                    step=schema.step.unwrap_or(1.0)
+                    value=init
+                    class=input_class
+                    on:change=move |v| {
+                        let target = v.target().unwrap();
+                        let input: HtmlInputElement = target.dyn_into().unwrap();
+                        send(input.value());
+                    }
+                />
+            }
+            .into_any()
+        }
+        _ => {
+            let input_ref = NodeRef::<Input>::new();
+            let init = schema.value.as_ref().map(|v| v.to_string()).unwrap_or_default();
+            view! {
+                <input
+                    class=input_class
+                    node_ref=input_ref
+                    value=init
+                    on:keydown=move |ev: web_sys::KeyboardEvent| {
+                        ev.stop_propagation();
+                        if ev.key_code() == ENTER_KEY {
+                            send(input_ref.get().unwrap().value());
+                        }
+                    }
+                />
+            }
+            .into_any()
+        }
+    };
+
+    view! {
+        <label class=label_class>{label}</label>
+        {control}
+        {unit}
+    }
+}
@@ -15,6 +15,10 @@ pub use constellation_sink::ConstellationSink;
 mod constellation_sink_density;
 pub use constellation_sink_density::ConstellationSinkDensity;
 
+mod device_selector;
+pub use device_selector::DeviceSelection;
+pub use device_selector::DeviceSelector;
+
 mod handle;
 pub use handle::FlowgraphHandle;
 pub use handle::RuntimeHandle;
@@ -28,6 +32,9 @@ pub use flowgraph_canvas::FlowgraphCanvas;
 mod flowgraph_mermaid;
 pub use flowgraph_mermaid::FlowgraphMermaid;
 
+mod handler_control;
+pub use handler_control::HandlerControl;
+
 mod list_selector;
 pub use list_selector::ListSelector;
 
@@ -23,10 +23,17 @@ pub fn Pmt(
                 Pmt::U64(_) => "pmt-u64",
                 Pmt::F32(_) => "pmt-f32",
                 Pmt::F64(_) => "pmt-f64",
+                Pmt::CF32(_) => "pmt-cf32",
+                Pmt::VecCF32(_) => "pmt-veccf32",
+                Pmt::VecCF64(_) => "pmt-veccf64",
                 Pmt::VecF32(_) => "pmt-vecf32",
                 Pmt::VecU64(_) => "pmt-vecu64",
+                Pmt::VecU8(_) => "pmt-vecu8",
+                Pmt::VecI16(_) => "pmt-veci16",
+                Pmt::VecI32(_) => "pmt-veci32",
                 Pmt::Blob(_) => "pmt-blob",
                 Pmt::VecPmt(_) => "pmt-vecpmt",
+                Pmt::Tuple(_) => "pmt-tuple",
                 Pmt::Finished => "pmt-finished",
                 Pmt::MapStrPmt(_) => "pmt-mapstrpmt",
                 Pmt::Any(_) => "pmt-any",
@@ -117,10 +124,17 @@ pub fn PmtInputList(
             PmtKind::U64,
             PmtKind::F32,
             PmtKind::F64,
+            PmtKind::CF32,
+            PmtKind::VecCF32,
+            PmtKind::VecCF64,
             PmtKind::VecF32,
             PmtKind::VecU64,
+            PmtKind::VecU8,
+            PmtKind::VecI16,
+            PmtKind::VecI32,
             PmtKind::Blob,
             PmtKind::VecPmt,
+            PmtKind::Tuple,
             PmtKind::Finished,
             PmtKind::MapStrPmt,
     ])]
@@ -172,18 +186,39 @@ pub fn PmtInputList(
             PmtKind::U64 => v.parse::<u64>().map(Pmt::U64).ok(),
             PmtKind::F32 => v.parse::<f32>().map(Pmt::F32).ok(),
             PmtKind::F64 => v.parse::<f64>().map(Pmt::F64).ok(),
+            PmtKind::CF32 => serde_json::from_str::<Pmt>(&format!("{{\"CF32\": {v}}}"))
+                .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"CF32\": [{v}]}}")))
+                .ok(),
+            PmtKind::VecCF32 => serde_json::from_str::<Pmt>(&format!("{{\"VecCF32\": {v}}}"))
+                .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"VecCF32\": [{v}]}}")))
+                .ok(),
+            PmtKind::VecCF64 => serde_json::from_str::<Pmt>(&format!("{{\"VecCF64\": {v}}}"))
+                .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"VecCF64\": [{v}]}}")))
+                .ok(),
             PmtKind::VecF32 => serde_json::from_str::<Pmt>(&format!("{{\"VecF32\": {v}}}"))
                 .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"VecF32\": [{v}]}}")))
                 .ok(),
             PmtKind::VecU64 => serde_json::from_str::<Pmt>(&format!("{{\"VecU64\": {v}}}"))
                 .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"VecU64\": [{v}]}}")))
                 .ok(),
+            PmtKind::VecU8 => serde_json::from_str::<Pmt>(&format!("{{\"VecU8\": {v}}}"))
+                .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"VecU8\": [{v}]}}")))
+                .ok(),
+            PmtKind::VecI16 => serde_json::from_str::<Pmt>(&format!("{{\"VecI16\": {v}}}"))
+                .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"VecI16\": [{v}]}}")))
+                .ok(),
+            PmtKind::VecI32 => serde_json::from_str::<Pmt>(&format!("{{\"VecI32\": {v}}}"))
+                .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"VecI32\": [{v}]}}")))
+                .ok(),
             PmtKind::Blob => serde_json::from_str::<Pmt>(&format!("{{\"Blob\": {v}}}"))
                 .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"Blob\": [{v}]}}")))
                 .ok(),
             PmtKind::VecPmt => serde_json::from_str::<Pmt>(&format!("{{\"VecPmt\": {v}}}"))
                 .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"VecPmt\": [{v}]}}")))
                 .ok(),
+            PmtKind::Tuple => serde_json::from_str::<Pmt>(&format!("{{\"Tuple\": {v}}}"))
+                .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"Tuple\": [{v}]}}")))
+                .ok(),
             PmtKind::Finished => Some(Pmt::Finished),
             PmtKind::MapStrPmt => serde_json::from_str::<Pmt>(&format!("{{\"MapStrPmt\": {v}}}"))
                 .or_else(|_| serde_json::from_str::<Pmt>(&format!("{{\"MapStrPmt\": {{{v}}}}}")))
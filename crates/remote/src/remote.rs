@@ -1,6 +1,8 @@
 use futuresdr_types::BlockDescription;
 use futuresdr_types::BlockId;
+use futuresdr_types::DeviceDescription;
 use futuresdr_types::FlowgraphDescription;
+use futuresdr_types::MessageInputSchema;
 use futuresdr_types::Pmt;
 use futuresdr_types::PortId;
 use reqwest::Client;
@@ -61,6 +63,17 @@ impl Remote {
 
         Ok(v)
     }
+
+    /// Enumerate the SDR devices of the remote runtime, optionally filtered by seify `args`.
+    ///
+    /// Requires a runtime with `seify` support.
+    pub async fn devices(&self, args: Option<&str>) -> Result<Vec<DeviceDescription>, Error> {
+        let mut request = self.client.get(format!("{}/api/devices/", self.url));
+        if let Some(args) = args {
+            request = request.query(&[("args", args)]);
+        }
+        Ok(request.send().await?.json().await?)
+    }
 }
 
 /// A remote Flowgraph.
@@ -239,6 +252,21 @@ impl Block {
     pub fn description(&self) -> &BlockDescription {
         &self.description
     }
+
+    /// Schema of a message handler, e.g., to generate a control for it.
+    ///
+    /// Returns `None` if the handler does not exist.
+    pub fn schema(&self, handler: Handler) -> Option<&MessageInputSchema> {
+        let index = match handler {
+            Handler::Id(i) => i,
+            Handler::Name(n) => self
+                .description
+                .message_inputs
+                .iter()
+                .position(|h| *h == n)?,
+        };
+        self.description.message_input_schemas.get(index)
+    }
 }
 
 impl std::fmt::Display for Block {
@@ -319,9 +347,12 @@ impl std::fmt::Display for Connection {
 #[cfg(test)]
 mod tests {
     use crate::Flowgraph;
+    use crate::Handler;
     use futuresdr_types::BlockDescription;
     use futuresdr_types::BlockId;
     use futuresdr_types::FlowgraphDescription;
+    use futuresdr_types::HandlerSignature;
+    use futuresdr_types::MessageInputSchema;
     use futuresdr_types::PortId;
 
     fn block(id: usize, name: &str) -> BlockDescription {
@@ -332,6 +363,14 @@ mod tests {
             stream_inputs: vec!["in".to_string()],
             stream_outputs: vec!["out".to_string()],
             message_inputs: vec!["command".to_string()],
+            message_input_signatures: vec![HandlerSignature {
+                argument: "Pmt".to_string(),
+                returns: "Pmt".to_string(),
+            }],
+            message_input_schemas: vec![MessageInputSchema {
+                unit: Some("Hz".to_string()),
+                ..Default::default()
+            }],
             message_outputs: vec!["message".to_string()],
             blocking: false,
         }
@@ -369,6 +408,13 @@ mod tests {
             Some(BlockId(1))
         );
         assert!(fg.block_by(|d| d.type_name == "test_block").is_some());
+        let b = fg.block(BlockId(0)).unwrap();
+        assert_eq!(
+            b.schema(Handler::Name("command".to_string()))
+                .and_then(|s| s.unit.clone()),
+            Some("Hz".to_string())
+        );
+        assert!(b.schema(Handler::Id(1)).is_none());
         assert!(fg.block_by(|d| d.type_name == "foo").is_none());
     }
 }
@@ -2,6 +2,8 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::BlockId;
+use crate::Pmt;
+use crate::PmtKind;
 use crate::PortId;
 
 /// Description of a `Flowgraph`.
@@ -34,6 +36,12 @@ pub struct BlockDescription {
     pub stream_outputs: Vec<String>,
     /// Message inputs
     pub message_inputs: Vec<String>,
+    /// Signatures of the message handlers, in the order of `message_inputs`
+    #[serde(default)]
+    pub message_input_signatures: Vec<HandlerSignature>,
+    /// Schemas of the message handlers, in the order of `message_inputs`
+    #[serde(default)]
+    pub message_input_schemas: Vec<MessageInputSchema>,
     /// Message outputs
     pub message_outputs: Vec<String>,
     /// Blocking
@@ -42,3 +50,37 @@ pub struct BlockDescription {
     /// block inside the async function.
     pub blocking: bool,
 }
+
+/// Signature of a message handler.
+///
+/// Handlers that take and return a [`Pmt`](crate::Pmt) have the signature `Pmt -> Pmt`. Typed
+/// handlers list the Rust types of their argument and return value, e.g., `f64 -> ()`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandlerSignature {
+    /// Argument type
+    pub argument: String,
+    /// Return type
+    pub returns: String,
+}
+
+/// Schema of a message handler.
+///
+/// Optional metadata that allows clients to generate controls (e.g., sliders or input forms) for
+/// a handler. All fields are `None` for handlers without schema.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MessageInputSchema {
+    /// Description
+    pub description: Option<String>,
+    /// Expected [`PmtKind`]
+    pub kind: Option<PmtKind>,
+    /// Minimum value
+    pub min: Option<f64>,
+    /// Maximum value
+    pub max: Option<f64>,
+    /// Step size
+    pub step: Option<f64>,
+    /// Unit, e.g., `Hz` or `dB`
+    pub unit: Option<String>,
+    /// Current value, if the block provides a getter
+    pub value: Option<Pmt>,
+}
@@ -0,0 +1,43 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Pmt;
+
+/// Description of an SDR device and its capabilities.
+///
+/// This struct can be serialized to be used with the REST API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceDescription {
+    /// Arguments to open the device, e.g., `driver=rtlsdr,index=0`
+    pub args: String,
+    /// Driver
+    pub driver: String,
+    /// Identifier, e.g., the serial number
+    pub id: Option<String>,
+    /// RX channels
+    pub rx: Vec<ChannelDescription>,
+    /// TX channels
+    pub tx: Vec<ChannelDescription>,
+}
+
+/// Description of a channel of an SDR device.
+///
+/// Ranges are encoded as `Pmt::VecPmt` of values (`Pmt::F64`) and intervals (`Pmt::MapStrPmt`
+/// with `min`, `max`, and optional `step`). They are `Pmt::Null` if not supported by the device.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelDescription {
+    /// Full duplex
+    pub full_duplex: bool,
+    /// Antennas
+    pub antennas: Vec<String>,
+    /// Gain elements, ordered from RF to baseband
+    pub gain_elements: Vec<String>,
+    /// Range of the overall gain (dB)
+    pub gain_range: Pmt,
+    /// Range of the center frequency (Hz)
+    pub frequency_range: Pmt,
+    /// Range of the sample rate (Hz)
+    pub sample_rate_range: Pmt,
+    /// Range of the bandwidth filter (Hz)
+    pub bandwidth_range: Pmt,
+}
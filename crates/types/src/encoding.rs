@@ -0,0 +1,219 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Pmt;
+use crate::PmtConversionError;
+
+/// Version of the [`PmtEncoding::Cbor`] and [`PmtEncoding::MessagePack`] formats.
+pub const PMT_ENCODING_VERSION: u8 = 1;
+
+/// Wire format of [`Pmts`](Pmt).
+///
+/// [`Cbor`](PmtEncoding::Cbor) and [`MessagePack`](PmtEncoding::MessagePack) encode the serde
+/// representation of the [`Pmt`], prefixed with a version byte
+/// ([`PMT_ENCODING_VERSION`]). Decoders reject unknown versions. [`Json`](PmtEncoding::Json) is
+/// the unversioned serde representation, as used by the REST API.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PmtEncoding {
+    /// JSON
+    #[default]
+    Json,
+    /// Versioned CBOR
+    Cbor,
+    /// Versioned MessagePack
+    MessagePack,
+    /// GNU Radio's serialized PMT format (`pmt::serialize()`)
+    GnuRadio,
+}
+
+impl PmtEncoding {
+    /// Encode a [`Pmt`].
+    ///
+    /// Fails for `Pmt::Any` and for PMTs that have no representation in the format.
+    pub fn encode(&self, pmt: &Pmt) -> Result<Vec<u8>, PmtConversionError> {
+        match self {
+            PmtEncoding::Json => serde_json::to_vec(pmt).map_err(|_| PmtConversionError),
+            PmtEncoding::Cbor => {
+                let mut out = vec![PMT_ENCODING_VERSION];
+                ciborium::into_writer(pmt, &mut out).map_err(|_| PmtConversionError)?;
+                Ok(out)
+            }
+            PmtEncoding::MessagePack => {
+                let mut out = vec![PMT_ENCODING_VERSION];
+                rmp_serde::encode::write(&mut out, pmt).map_err(|_| PmtConversionError)?;
+                Ok(out)
+            }
+            PmtEncoding::GnuRadio => pmt.to_gr_bytes(),
+        }
+    }
+
+    /// Decode a [`Pmt`].
+    pub fn decode(&self, bytes: &[u8]) -> Result<Pmt, PmtConversionError> {
+        match self {
+            PmtEncoding::Json => serde_json::from_slice(bytes).map_err(|_| PmtConversionError),
+            PmtEncoding::Cbor => {
+                ciborium::from_reader(versioned(bytes)?).map_err(|_| PmtConversionError)
+            }
+            PmtEncoding::MessagePack => {
+                rmp_serde::from_slice(versioned(bytes)?).map_err(|_| PmtConversionError)
+            }
+            PmtEncoding::GnuRadio => Pmt::from_gr_bytes(bytes),
+        }
+    }
+
+    /// Media type, e.g., for HTTP `Content-Type` and `Accept` headers.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            PmtEncoding::Json => "application/json",
+            PmtEncoding::Cbor => "application/cbor",
+            PmtEncoding::MessagePack => "application/msgpack",
+            PmtEncoding::GnuRadio => "application/x-gnuradio-pmt",
+        }
+    }
+
+    /// Encoding for a media type, ignoring parameters like `; charset=utf-8`.
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let media_type = media_type.split(';').next()?.trim();
+        match media_type.to_ascii_lowercase().as_str() {
+            "application/json" => Some(PmtEncoding::Json),
+            "application/cbor" => Some(PmtEncoding::Cbor),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(PmtEncoding::MessagePack)
+            }
+            "application/x-gnuradio-pmt" => Some(PmtEncoding::GnuRadio),
+            _ => None,
+        }
+    }
+}
+
+/// Strip and check the version byte.
+fn versioned(bytes: &[u8]) -> Result<&[u8], PmtConversionError> {
+    match bytes.split_first() {
+        Some((&PMT_ENCODING_VERSION, payload)) => Ok(payload),
+        _ => Err(PmtConversionError),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use num_complex::Complex32;
+    use num_complex::Complex64;
+    use std::collections::HashMap;
+
+    #[test]
+    fn round_trip() {
+        let p = Pmt::MapStrPmt(HashMap::from([
+            ("freq".to_string(), Pmt::F64(2.4e9)),
+            (
+                "samples".to_string(),
+                Pmt::VecCF32(vec![Complex32::new(1.0, 2.0)]),
+            ),
+            ("name".to_string(), Pmt::String("foo".to_string())),
+            ("iq".to_string(), Pmt::VecI16(vec![-1, 1])),
+            (
+                "tuple".to_string(),
+                Pmt::Tuple(vec![Pmt::CF32(Complex32::new(0.5, 1.0)), Pmt::Bool(true)]),
+            ),
+        ]));
+        for e in [
+            PmtEncoding::Json,
+            PmtEncoding::Cbor,
+            PmtEncoding::MessagePack,
+            PmtEncoding::GnuRadio,
+        ] {
+            let b = e.encode(&p).unwrap();
+            assert_eq!(e.decode(&b), Ok(p.clone()));
+            assert_eq!(PmtEncoding::from_media_type(e.media_type()), Some(e));
+        }
+    }
+
+    #[test]
+    fn round_trip_all_kinds() {
+        let pmts = [
+            Pmt::Ok,
+            Pmt::InvalidValue,
+            Pmt::Null,
+            Pmt::String("foo".to_string()),
+            Pmt::Bool(true),
+            Pmt::Usize(usize::MAX),
+            Pmt::Isize(-5),
+            Pmt::U32(u32::MAX),
+            Pmt::U64(u64::MAX),
+            Pmt::F32(0.5),
+            Pmt::F64(2.4e9),
+            Pmt::CF32(Complex32::new(1.0, -2.0)),
+            Pmt::VecCF32(vec![Complex32::new(1.0, 2.0)]),
+            Pmt::VecCF64(vec![Complex64::new(1.0, 2.0)]),
+            Pmt::VecF32(vec![0.5, -1.5]),
+            Pmt::VecU64(vec![1, u64::MAX]),
+            Pmt::VecU8(vec![1, 2]),
+            Pmt::VecI16(vec![-1, 1]),
+            Pmt::VecI32(vec![-1, 1 << 20]),
+            Pmt::Blob(vec![3, 4]),
+            Pmt::VecPmt(vec![Pmt::U32(1), Pmt::Null]),
+            Pmt::Tuple(vec![Pmt::Bool(false), Pmt::F32(1.0)]),
+            Pmt::Finished,
+            Pmt::MapStrPmt(HashMap::new()),
+            Pmt::MapStrPmt(HashMap::from([("a".to_string(), Pmt::Usize(1))])),
+        ];
+        for p in pmts {
+            for e in [
+                PmtEncoding::Json,
+                PmtEncoding::Cbor,
+                PmtEncoding::MessagePack,
+            ] {
+                let b = e.encode(&p).unwrap();
+                assert_eq!(e.decode(&b), Ok(p.clone()), "{e:?}");
+            }
+
+            // see `Pmt::to_gr_bytes` for the type mapping
+            let gr = match &p {
+                Pmt::Ok | Pmt::InvalidValue | Pmt::Finished | Pmt::Any(_) => None,
+                Pmt::U32(v) => Some(Pmt::Isize(*v as isize)),
+                Pmt::Usize(v) => Some(Pmt::U64(*v as u64)),
+                Pmt::F32(v) => Some(Pmt::F64(*v as f64)),
+                Pmt::VecU8(v) => Some(Pmt::Blob(v.clone())),
+                Pmt::VecPmt(_) => Some(Pmt::VecPmt(vec![Pmt::Isize(1), Pmt::Null])),
+                Pmt::Tuple(_) => Some(Pmt::Tuple(vec![Pmt::Bool(false), Pmt::F64(1.0)])),
+                Pmt::MapStrPmt(m) => Some(Pmt::MapStrPmt(
+                    m.keys().map(|k| (k.clone(), Pmt::U64(1))).collect(),
+                )),
+                Pmt::Null
+                | Pmt::String(_)
+                | Pmt::Bool(_)
+                | Pmt::Isize(_)
+                | Pmt::U64(_)
+                | Pmt::F64(_)
+                | Pmt::CF32(_)
+                | Pmt::VecCF32(_)
+                | Pmt::VecCF64(_)
+                | Pmt::VecF32(_)
+                | Pmt::VecU64(_)
+                | Pmt::VecI16(_)
+                | Pmt::VecI32(_)
+                | Pmt::Blob(_) => Some(p.clone()),
+            };
+            match gr {
+                Some(gr) => {
+                    let b = PmtEncoding::GnuRadio.encode(&p).unwrap();
+                    assert_eq!(PmtEncoding::GnuRadio.decode(&b), Ok(gr));
+                }
+                None => assert!(PmtEncoding::GnuRadio.encode(&p).is_err()),
+            }
+        }
+    }
+
+    #[test]
+    fn version() {
+        let mut b = PmtEncoding::Cbor.encode(&Pmt::U32(1)).unwrap();
+        assert_eq!(b[0], PMT_ENCODING_VERSION);
+        b[0] = 0;
+        assert!(PmtEncoding::Cbor.decode(&b).is_err());
+        assert!(PmtEncoding::MessagePack.decode(&[]).is_err());
+        assert_eq!(
+            PmtEncoding::from_media_type("application/json; charset=utf-8"),
+            Some(PmtEncoding::Json)
+        );
+    }
+}
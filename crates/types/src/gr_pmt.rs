@@ -0,0 +1,455 @@
+//! [GNU Radio](https://www.gnuradio.org/) serialized PMT format.
+//!
+//! This is the format of `pmt::serialize()`/`pmt::deserialize()`, which GNU Radio uses, for
+//! example, for its ZeroMQ message blocks and tag headers. All values are big-endian.
+use num_complex::Complex32;
+use num_complex::Complex64;
+use std::collections::HashMap;
+
+use crate::Pmt;
+use crate::PmtConversionError;
+
+const TRUE: u8 = 0x00;
+const FALSE: u8 = 0x01;
+const SYMBOL: u8 = 0x02;
+const INT32: u8 = 0x03;
+const DOUBLE: u8 = 0x04;
+const COMPLEX: u8 = 0x05;
+const NULL: u8 = 0x06;
+const PAIR: u8 = 0x07;
+const VECTOR: u8 = 0x08;
+const DICT: u8 = 0x09;
+const UNIFORM_VECTOR: u8 = 0x0a;
+const UINT64: u8 = 0x0b;
+const TUPLE: u8 = 0x0c;
+const INT64: u8 = 0x0d;
+
+const UV_U8: u8 = 0x00;
+const UV_S8: u8 = 0x01;
+const UV_U16: u8 = 0x02;
+const UV_S16: u8 = 0x03;
+const UV_U32: u8 = 0x04;
+const UV_S32: u8 = 0x05;
+const UV_U64: u8 = 0x06;
+const UV_S64: u8 = 0x07;
+const UV_F32: u8 = 0x08;
+const UV_F64: u8 = 0x09;
+const UV_C32: u8 = 0x0a;
+const UV_C64: u8 = 0x0b;
+
+impl Pmt {
+    /// Serialize to the GNU Radio PMT format.
+    ///
+    /// GNU Radio has fewer types than [`Pmt`], so some values change their type in a round trip:
+    ///
+    /// | `Pmt`            | GNU Radio           | decoded as |
+    /// |------------------|---------------------|------------|
+    /// | `U32`, `Isize`   | `int32` or `int64`  | `Isize`    |
+    /// | `Usize`, `U64`   | `uint64`            | `U64`      |
+    /// | `F32`, `F64`     | `double`            | `F64`      |
+    /// | `VecU8`, `Blob`  | `u8vector`          | `Blob`     |
+    ///
+    /// All other types are decoded as they were encoded. Maps are serialized as dictionaries with
+    /// sorted keys. `Ok`, `InvalidValue`, `Finished`, and `Any` have no GNU Radio representation
+    /// and cannot be serialized.
+    pub fn to_gr_bytes(&self) -> Result<Vec<u8>, PmtConversionError> {
+        let mut out = Vec::new();
+        self.write_gr(&mut out)?;
+        Ok(out)
+    }
+
+    /// Deserialize from the GNU Radio PMT format.
+    ///
+    /// Fails if `bytes` is not exactly one serialized PMT.
+    pub fn from_gr_bytes(mut bytes: &[u8]) -> Result<Pmt, PmtConversionError> {
+        let p = Pmt::read_gr(&mut bytes)?;
+        if bytes.is_empty() {
+            Ok(p)
+        } else {
+            Err(PmtConversionError)
+        }
+    }
+
+    /// Append the GNU Radio serialization of the PMT to `out`.
+    pub fn write_gr(&self, out: &mut Vec<u8>) -> Result<(), PmtConversionError> {
+        match self {
+            Pmt::Null => out.push(NULL),
+            Pmt::Bool(true) => out.push(TRUE),
+            Pmt::Bool(false) => out.push(FALSE),
+            Pmt::String(s) => write_symbol(s, out)?,
+            Pmt::Usize(v) => write_u64(*v as u64, out),
+            Pmt::U64(v) => write_u64(*v, out),
+            Pmt::U32(v) => write_int(*v as i64, out),
+            Pmt::Isize(v) => write_int(*v as i64, out),
+            Pmt::F32(v) => write_double(*v as f64, out),
+            Pmt::F64(v) => write_double(*v, out),
+            Pmt::CF32(v) => {
+                out.push(COMPLEX);
+                out.extend_from_slice(&(v.re as f64).to_be_bytes());
+                out.extend_from_slice(&(v.im as f64).to_be_bytes());
+            }
+            Pmt::Blob(v) | Pmt::VecU8(v) => {
+                write_uvector_header(UV_U8, v.len(), out);
+                out.extend_from_slice(v);
+            }
+            Pmt::VecI16(v) => {
+                write_uvector_header(UV_S16, v.len(), out);
+                for x in v {
+                    out.extend_from_slice(&x.to_be_bytes());
+                }
+            }
+            Pmt::VecI32(v) => {
+                write_uvector_header(UV_S32, v.len(), out);
+                for x in v {
+                    out.extend_from_slice(&x.to_be_bytes());
+                }
+            }
+            Pmt::VecU64(v) => {
+                write_uvector_header(UV_U64, v.len(), out);
+                for x in v {
+                    out.extend_from_slice(&x.to_be_bytes());
+                }
+            }
+            // GNU Radio serializes single-precision elements as doubles
+            Pmt::VecF32(v) => {
+                write_uvector_header(UV_F32, v.len(), out);
+                for x in v {
+                    out.extend_from_slice(&(*x as f64).to_be_bytes());
+                }
+            }
+            Pmt::VecCF32(v) => {
+                write_uvector_header(UV_C32, v.len(), out);
+                for x in v {
+                    out.extend_from_slice(&(x.re as f64).to_be_bytes());
+                    out.extend_from_slice(&(x.im as f64).to_be_bytes());
+                }
+            }
+            Pmt::VecCF64(v) => {
+                write_uvector_header(UV_C64, v.len(), out);
+                for x in v {
+                    out.extend_from_slice(&x.re.to_be_bytes());
+                    out.extend_from_slice(&x.im.to_be_bytes());
+                }
+            }
+            Pmt::VecPmt(v) | Pmt::Tuple(v) => {
+                out.push(if matches!(self, Pmt::Tuple(_)) {
+                    TUPLE
+                } else {
+                    VECTOR
+                });
+                out.extend_from_slice(&len_u32(v.len())?.to_be_bytes());
+                for p in v {
+                    p.write_gr(out)?;
+                }
+            }
+            Pmt::MapStrPmt(m) => {
+                // a dictionary is a list of (key . value) pairs, GNU Radio's empty
+                // dictionary is NULL, which would not decode as a map
+                if m.is_empty() {
+                    out.push(DICT);
+                }
+                let mut keys: Vec<&String> = m.keys().collect();
+                keys.sort();
+                for k in keys {
+                    out.push(PAIR);
+                    out.push(PAIR);
+                    write_symbol(k, out)?;
+                    m[k].write_gr(out)?;
+                }
+                out.push(NULL);
+            }
+            Pmt::Ok | Pmt::InvalidValue | Pmt::Finished | Pmt::Any(_) => {
+                return Err(PmtConversionError);
+            }
+        }
+        Ok(())
+    }
+
+    /// Read one GNU Radio serialized PMT from the start of `bytes`, advancing the slice.
+    ///
+    /// Integers are decoded as `Isize` (signed) or `U64` (unsigned), doubles as `F64`, complex
+    /// numbers as `CF32`, and symbols as `String`. Dictionaries and association lists with symbol
+    /// keys are decoded as `MapStrPmt`, other pairs as `VecPmt`. `u8` vectors are decoded as
+    /// `Blob`. See [`Pmt::to_gr_bytes`] for the resulting type mapping.
+    pub fn read_gr(bytes: &mut &[u8]) -> Result<Pmt, PmtConversionError> {
+        let p = match take::<1>(bytes)?[0] {
+            TRUE => Pmt::Bool(true),
+            FALSE => Pmt::Bool(false),
+            NULL => Pmt::Null,
+            SYMBOL => Pmt::String(read_symbol(bytes)?),
+            INT32 => Pmt::Isize(i32::from_be_bytes(take(bytes)?) as isize),
+            INT64 => Pmt::Isize(i64::from_be_bytes(take(bytes)?) as isize),
+            UINT64 => Pmt::U64(u64::from_be_bytes(take(bytes)?)),
+            DOUBLE => Pmt::F64(f64::from_be_bytes(take(bytes)?)),
+            COMPLEX => {
+                let re = f64::from_be_bytes(take(bytes)?);
+                let im = f64::from_be_bytes(take(bytes)?);
+                Pmt::CF32(Complex32::new(re as f32, im as f32))
+            }
+            DICT if bytes.first() == Some(&NULL) => {
+                *bytes = &bytes[1..];
+                Pmt::MapStrPmt(HashMap::new())
+            }
+            PAIR | DICT => match read_dict(bytes) {
+                Some(m) => Pmt::MapStrPmt(m),
+                None => {
+                    let car = Pmt::read_gr(bytes)?;
+                    let cdr = Pmt::read_gr(bytes)?;
+                    Pmt::VecPmt(vec![car, cdr])
+                }
+            },
+            tag @ (VECTOR | TUPLE) => {
+                let n = u32::from_be_bytes(take(bytes)?) as usize;
+                let mut v = Vec::new();
+                for _ in 0..n {
+                    v.push(Pmt::read_gr(bytes)?);
+                }
+                if tag == TUPLE {
+                    Pmt::Tuple(v)
+                } else {
+                    Pmt::VecPmt(v)
+                }
+            }
+            UNIFORM_VECTOR => read_uvector(bytes)?,
+            _ => return Err(PmtConversionError),
+        };
+        Ok(p)
+    }
+}
+
+fn len_u32(len: usize) -> Result<u32, PmtConversionError> {
+    u32::try_from(len).map_err(|_| PmtConversionError)
+}
+
+fn write_symbol(s: &str, out: &mut Vec<u8>) -> Result<(), PmtConversionError> {
+    let len = u16::try_from(s.len()).map_err(|_| PmtConversionError)?;
+    out.push(SYMBOL);
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(s.as_bytes());
+    Ok(())
+}
+
+fn write_u64(v: u64, out: &mut Vec<u8>) {
+    out.push(UINT64);
+    out.extend_from_slice(&v.to_be_bytes());
+}
+
+fn write_int(v: i64, out: &mut Vec<u8>) {
+    match i32::try_from(v) {
+        Ok(v) => {
+            out.push(INT32);
+            out.extend_from_slice(&v.to_be_bytes());
+        }
+        Err(_) => {
+            out.push(INT64);
+            out.extend_from_slice(&v.to_be_bytes());
+        }
+    }
+}
+
+fn write_double(v: f64, out: &mut Vec<u8>) {
+    out.push(DOUBLE);
+    out.extend_from_slice(&v.to_be_bytes());
+}
+
+fn write_uvector_header(utype: u8, len: usize, out: &mut Vec<u8>) {
+    out.push(UNIFORM_VECTOR);
+    out.push(utype);
+    out.extend_from_slice(&(len as u32).to_be_bytes());
+    // one byte of padding, as written by GNU Radio
+    out.push(1);
+    out.push(0);
+}
+
+fn take<const N: usize>(bytes: &mut &[u8]) -> Result<[u8; N], PmtConversionError> {
+    if bytes.len() < N {
+        return Err(PmtConversionError);
+    }
+    let (head, tail) = bytes.split_at(N);
+    *bytes = tail;
+    Ok(head.try_into().unwrap())
+}
+
+fn take_slice<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8], PmtConversionError> {
+    if bytes.len() < n {
+        return Err(PmtConversionError);
+    }
+    let (head, tail) = bytes.split_at(n);
+    *bytes = tail;
+    Ok(head)
+}
+
+fn read_symbol(bytes: &mut &[u8]) -> Result<String, PmtConversionError> {
+    let len = u16::from_be_bytes(take(bytes)?) as usize;
+    let s = take_slice(bytes, len)?;
+    String::from_utf8(s.to_vec()).map_err(|_| PmtConversionError)
+}
+
+/// Read an association list of `(symbol . value)` pairs, i.e., a dictionary.
+///
+/// Called after the tag of the first pair was consumed. Returns `None` and leaves `bytes`
+/// untouched if the data is not a dictionary.
+fn read_dict(bytes: &mut &[u8]) -> Option<HashMap<String, Pmt>> {
+    let mut b = *bytes;
+    let mut map = HashMap::new();
+    loop {
+        if take::<1>(&mut b).ok()?[0] != PAIR || take::<1>(&mut b).ok()?[0] != SYMBOL {
+            return None;
+        }
+        let k = read_symbol(&mut b).ok()?;
+        let v = Pmt::read_gr(&mut b).ok()?;
+        map.insert(k, v);
+        match take::<1>(&mut b).ok()?[0] {
+            NULL => break,
+            PAIR | DICT => {}
+            _ => return None,
+        }
+    }
+    *bytes = b;
+    Some(map)
+}
+
+fn read_uvector(bytes: &mut &[u8]) -> Result<Pmt, PmtConversionError> {
+    let utype = take::<1>(bytes)?[0];
+    let n = u32::from_be_bytes(take(bytes)?) as usize;
+    let npad = take::<1>(bytes)?[0] as usize;
+    take_slice(bytes, npad)?;
+
+    fn elements<const N: usize, T>(
+        bytes: &mut &[u8],
+        n: usize,
+        f: impl Fn([u8; N]) -> T,
+    ) -> Result<Vec<T>, PmtConversionError> {
+        if bytes.len() < n * N {
+            return Err(PmtConversionError);
+        }
+        (0..n).map(|_| take::<N>(bytes).map(&f)).collect()
+    }
+
+    let p = match utype {
+        UV_U8 => Pmt::Blob(take_slice(bytes, n)?.to_vec()),
+        UV_U64 => Pmt::VecU64(elements(bytes, n, u64::from_be_bytes)?),
+        UV_F32 => Pmt::VecF32(elements(bytes, n, |b| f64::from_be_bytes(b) as f32)?),
+        UV_C32 => Pmt::VecCF32(elements(bytes, n, |b: [u8; 16]| {
+            Complex32::new(
+                f64::from_be_bytes(b[..8].try_into().unwrap()) as f32,
+                f64::from_be_bytes(b[8..].try_into().unwrap()) as f32,
+            )
+        })?),
+        UV_C64 => Pmt::VecCF64(elements(bytes, n, |b: [u8; 16]| {
+            Complex64::new(
+                f64::from_be_bytes(b[..8].try_into().unwrap()),
+                f64::from_be_bytes(b[8..].try_into().unwrap()),
+            )
+        })?),
+        UV_S16 => Pmt::VecI16(elements(bytes, n, i16::from_be_bytes)?),
+        UV_S32 => Pmt::VecI32(elements(bytes, n, i32::from_be_bytes)?),
+        UV_S8 => Pmt::VecPmt(elements(bytes, n, |b: [u8; 1]| {
+            Pmt::Isize(i8::from_be_bytes(b) as isize)
+        })?),
+        UV_U16 => Pmt::VecPmt(elements(bytes, n, |b| {
+            Pmt::U32(u16::from_be_bytes(b) as u32)
+        })?),
+        UV_U32 => Pmt::VecPmt(elements(bytes, n, |b| Pmt::U32(u32::from_be_bytes(b)))?),
+        UV_S64 => Pmt::VecPmt(elements(bytes, n, |b| {
+            Pmt::Isize(i64::from_be_bytes(b) as isize)
+        })?),
+        UV_F64 => Pmt::VecPmt(elements(bytes, n, |b| Pmt::F64(f64::from_be_bytes(b)))?),
+        _ => return Err(PmtConversionError),
+    };
+    Ok(p)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let pmts = [
+            Pmt::Null,
+            Pmt::Bool(true),
+            Pmt::Bool(false),
+            Pmt::String("foo".to_string()),
+            Pmt::U64(u64::MAX),
+            Pmt::Isize(-5),
+            Pmt::Isize(1 << 40),
+            Pmt::F64(0.25),
+            Pmt::Blob(vec![1, 2, 3]),
+            Pmt::VecU64(vec![1, 2, 3]),
+            Pmt::VecF32(vec![0.5, 1.5]),
+            Pmt::VecCF32(vec![Complex32::new(1.0, -1.0)]),
+            Pmt::CF32(Complex32::new(0.5, 2.0)),
+            Pmt::VecCF64(vec![Complex64::new(1.0, -1.0)]),
+            Pmt::VecI16(vec![-1, 2]),
+            Pmt::VecI32(vec![-1, 1 << 20]),
+            Pmt::Tuple(vec![Pmt::Isize(1), Pmt::String("a".to_string())]),
+            Pmt::VecPmt(vec![Pmt::U64(1), Pmt::String("a".to_string())]),
+            Pmt::MapStrPmt(HashMap::from([
+                ("a".to_string(), Pmt::U64(1)),
+                ("b".to_string(), Pmt::F64(2.0)),
+            ])),
+            Pmt::MapStrPmt(HashMap::new()),
+            Pmt::VecPmt(vec![Pmt::MapStrPmt(HashMap::new()), Pmt::Null]),
+        ];
+        for p in pmts {
+            assert_eq!(Pmt::from_gr_bytes(&p.to_gr_bytes().unwrap()), Ok(p));
+        }
+    }
+
+    #[test]
+    fn type_mapping() {
+        let pmts = [
+            (Pmt::U32(7), Pmt::Isize(7)),
+            (Pmt::Usize(7), Pmt::U64(7)),
+            (Pmt::F32(0.5), Pmt::F64(0.5)),
+            (Pmt::VecU8(vec![1, 2]), Pmt::Blob(vec![1, 2])),
+        ];
+        for (p, decoded) in pmts {
+            assert_eq!(Pmt::from_gr_bytes(&p.to_gr_bytes().unwrap()), Ok(decoded));
+        }
+    }
+
+    #[test]
+    fn gnuradio_bytes() {
+        // pmt::serialize_str(pmt::intern("foo"))
+        assert_eq!(
+            Pmt::String("foo".to_string()).to_gr_bytes().unwrap(),
+            [0x02, 0x00, 0x03, b'f', b'o', b'o']
+        );
+        // pmt::serialize_str(pmt::from_long(42))
+        assert_eq!(
+            Pmt::U32(42).to_gr_bytes().unwrap(),
+            [0x03, 0x00, 0x00, 0x00, 0x2a]
+        );
+        // pmt::serialize_str(pmt::init_u8vector(2, {1, 2}))
+        assert_eq!(
+            Pmt::Blob(vec![1, 2]).to_gr_bytes().unwrap(),
+            [0x0a, 0x00, 0x00, 0x00, 0x00, 0x02, 0x01, 0x00, 0x01, 0x02]
+        );
+        assert_eq!(
+            Pmt::VecU8(vec![1, 2]).to_gr_bytes(),
+            Pmt::Blob(vec![1, 2]).to_gr_bytes()
+        );
+        // pmt::serialize_str(pmt::from_complex(1.0, 2.0))
+        assert_eq!(
+            Pmt::CF32(Complex32::new(1.0, 2.0)).to_gr_bytes().unwrap(),
+            [
+                0x05, 0x3f, 0xf0, 0, 0, 0, 0, 0, 0, 0x40, 0x00, 0, 0, 0, 0, 0, 0
+            ]
+        );
+        // pmt::cons(pmt::intern("a"), pmt::from_double(1.0))
+        let pair = [
+            0x07, 0x02, 0x00, 0x01, b'a', 0x04, 0x3f, 0xf0, 0, 0, 0, 0, 0, 0,
+        ];
+        assert_eq!(
+            Pmt::from_gr_bytes(&pair),
+            Ok(Pmt::VecPmt(vec![
+                Pmt::String("a".to_string()),
+                Pmt::F64(1.0)
+            ]))
+        );
+        assert!(Pmt::Finished.to_gr_bytes().is_err());
+        assert!(Pmt::from_gr_bytes(&[0x02, 0x00, 0x03, b'f']).is_err());
+    }
+}
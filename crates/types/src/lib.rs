@@ -6,7 +6,18 @@
 mod description;
 pub use description::BlockDescription;
 pub use description::FlowgraphDescription;
+pub use description::HandlerSignature;
+pub use description::MessageInputSchema;
 
+mod device;
+pub use device::ChannelDescription;
+pub use device::DeviceDescription;
+
+mod encoding;
+pub use encoding::PMT_ENCODING_VERSION;
+pub use encoding::PmtEncoding;
+
+mod gr_pmt;
 mod pmt;
 pub use pmt::Pmt;
 pub use pmt::PmtConversionError;
@@ -18,5 +29,7 @@ mod flowgraph_id;
 pub use flowgraph_id::FlowgraphId;
 mod port_id;
 pub use port_id::PortId;
+mod snapshot;
+pub use snapshot::FlowgraphSnapshot;
 #[cfg(feature = "seify")]
 mod seify;
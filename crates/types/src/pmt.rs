@@ -1,5 +1,6 @@
 use dyn_clone::DynClone;
 use num_complex::Complex32;
+use num_complex::Complex64;
 use serde::Deserialize;
 use serde::Serialize;
 use std::any::Any;
@@ -17,6 +18,8 @@ pub trait PmtAny: Any + DynClone + Send + Sync + 'static {
     fn as_any_mut(&mut self) -> &mut dyn Any;
     /// Consume `self`, converting to a `Box<dyn Any>`
     fn to_any(self: Box<Self>) -> Box<dyn Any>;
+    /// Name of the wrapped type
+    fn any_type_name(&self) -> &'static str;
 }
 dyn_clone::clone_trait_object!(PmtAny);
 
@@ -30,6 +33,9 @@ impl<T: Any + DynClone + Send + Sync + 'static> PmtAny for T {
     fn to_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
+    fn any_type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
 }
 
 impl fmt::Debug for Box<dyn PmtAny> {
@@ -94,16 +100,32 @@ pub enum Pmt {
     F32(f32),
     /// F64, 64-bit float
     F64(f64),
+    /// CF32, 32-bit complex float
+    CF32(Complex32),
     /// Vector of 32-bit complex floats.
     VecCF32(Vec<Complex32>),
+    /// Vector of 64-bit complex floats.
+    VecCF64(Vec<Complex64>),
     /// Vector of 32-bit floats.
     VecF32(Vec<f32>),
     /// Vector of 64-bit floats.
     VecU64(Vec<u64>),
+    /// Vector of 8-bit unsigned integers.
+    ///
+    /// Numeric data, in contrast to [`Blob`](Pmt::Blob), which holds opaque bytes.
+    VecU8(Vec<u8>),
+    /// Vector of 16-bit signed integers.
+    VecI16(Vec<i16>),
+    /// Vector of 32-bit signed integers.
+    VecI32(Vec<i32>),
     /// Binary data blob
     Blob(Vec<u8>),
     /// Vector of [`Pmts`](Pmt)
     VecPmt(Vec<Pmt>),
+    /// Tuple of [`Pmts`](Pmt)
+    ///
+    /// Fixed-size, heterogeneous group of values, e.g., converted from a Rust tuple.
+    Tuple(Vec<Pmt>),
     /// Finished
     ///
     /// Runtime message, used to signal the handler that a connected block finished.
@@ -133,11 +155,17 @@ impl Pmt {
             Pmt::U64(_) => PmtKind::U64,
             Pmt::F32(_) => PmtKind::F32,
             Pmt::F64(_) => PmtKind::F64,
+            Pmt::CF32(_) => PmtKind::CF32,
             Pmt::VecCF32(_) => PmtKind::VecCF32,
+            Pmt::VecCF64(_) => PmtKind::VecCF64,
             Pmt::VecF32(_) => PmtKind::VecF32,
             Pmt::VecU64(_) => PmtKind::VecU64,
+            Pmt::VecU8(_) => PmtKind::VecU8,
+            Pmt::VecI16(_) => PmtKind::VecI16,
+            Pmt::VecI32(_) => PmtKind::VecI32,
             Pmt::Blob(_) => PmtKind::Blob,
             Pmt::VecPmt(_) => PmtKind::VecPmt,
+            Pmt::Tuple(_) => PmtKind::Tuple,
             Pmt::Finished => PmtKind::Finished,
             Pmt::MapStrPmt(_) => PmtKind::MapStrPmt,
             Pmt::Any(_) => PmtKind::Any,
@@ -145,32 +173,142 @@ impl Pmt {
     }
 }
 
+/// Python-style display.
+///
+/// Values are formatted like Python's `str()`, e.g., `True`, `(1+2j)`, `[1.0, 2.5]`, `(1, 'a')`,
+/// `b'\x01ab'`, or `{'freq': 2400000000.0}`. Strings are quoted inside of containers. Map keys
+/// are sorted.
 impl fmt::Display for Pmt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_python(f, false)
+    }
+}
+
+impl Pmt {
+    fn fmt_python(&self, f: &mut fmt::Formatter<'_>, nested: bool) -> fmt::Result {
         match self {
             Pmt::Ok => write!(f, "Ok"),
             Pmt::InvalidValue => write!(f, "InvalidValue"),
-            Pmt::Null => write!(f, "Null"),
+            Pmt::Null => write!(f, "None"),
+            Pmt::String(v) if nested => fmt_str(f, v),
             Pmt::String(v) => write!(f, "{v}"),
-            Pmt::Bool(v) => write!(f, "{v}"),
+            Pmt::Bool(true) => write!(f, "True"),
+            Pmt::Bool(false) => write!(f, "False"),
             Pmt::Usize(v) => write!(f, "{v}"),
             Pmt::Isize(v) => write!(f, "{v}"),
             Pmt::U32(v) => write!(f, "{v}"),
             Pmt::U64(v) => write!(f, "{v}"),
-            Pmt::F32(v) => write!(f, "{v}"),
-            Pmt::F64(v) => write!(f, "{v}"),
-            Pmt::VecCF32(v) => write!(f, "{v:?}"),
-            Pmt::VecF32(v) => write!(f, "{v:?}"),
-            Pmt::VecU64(v) => write!(f, "{v:?}"),
-            Pmt::Blob(v) => write!(f, "{v:?}"),
-            Pmt::VecPmt(v) => write!(f, "{v:?}"),
+            Pmt::F32(v) => fmt_float(f, *v as f64),
+            Pmt::F64(v) => fmt_float(f, *v),
+            Pmt::CF32(v) => fmt_complex(f, v.re as f64, v.im as f64),
+            Pmt::VecCF32(v) => fmt_list(f, v, |f, x| fmt_complex(f, x.re as f64, x.im as f64)),
+            Pmt::VecCF64(v) => fmt_list(f, v, |f, x| fmt_complex(f, x.re, x.im)),
+            Pmt::VecF32(v) => fmt_list(f, v, |f, x| fmt_float(f, *x as f64)),
+            Pmt::VecU64(v) => fmt_list(f, v, |f, x| write!(f, "{x}")),
+            Pmt::VecU8(v) => fmt_list(f, v, |f, x| write!(f, "{x}")),
+            Pmt::VecI16(v) => fmt_list(f, v, |f, x| write!(f, "{x}")),
+            Pmt::VecI32(v) => fmt_list(f, v, |f, x| write!(f, "{x}")),
+            Pmt::Blob(v) => fmt_bytes(f, v),
+            Pmt::VecPmt(v) => fmt_list(f, v, |f, x| x.fmt_python(f, true)),
+            Pmt::Tuple(v) => {
+                write!(f, "(")?;
+                for (i, x) in v.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    x.fmt_python(f, true)?;
+                }
+                if v.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
             Pmt::Finished => write!(f, "Finished"),
-            Pmt::MapStrPmt(v) => write!(f, "{v:?}"),
-            Pmt::Any(v) => write!(f, "{v:?}"),
+            Pmt::MapStrPmt(v) => {
+                let mut keys: Vec<&String> = v.keys().collect();
+                keys.sort();
+                write!(f, "{{")?;
+                for (i, k) in keys.into_iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    fmt_str(f, k)?;
+                    write!(f, ": ")?;
+                    v[k].fmt_python(f, true)?;
+                }
+                write!(f, "}}")
+            }
+            Pmt::Any(v) => write!(f, "<Any {}>", (**v).any_type_name()),
         }
     }
 }
 
+fn fmt_list<T>(
+    f: &mut fmt::Formatter<'_>,
+    v: &[T],
+    g: impl Fn(&mut fmt::Formatter<'_>, &T) -> fmt::Result,
+) -> fmt::Result {
+    write!(f, "[")?;
+    for (i, x) in v.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        g(f, x)?;
+    }
+    write!(f, "]")
+}
+
+fn fmt_float(f: &mut fmt::Formatter<'_>, v: f64) -> fmt::Result {
+    if v.is_nan() {
+        write!(f, "nan")
+    } else if v.is_infinite() {
+        write!(f, "{}", if v > 0.0 { "inf" } else { "-inf" })
+    } else {
+        // debug format keeps the `.0` of integral values
+        write!(f, "{v:?}")
+    }
+}
+
+fn fmt_complex(f: &mut fmt::Formatter<'_>, re: f64, im: f64) -> fmt::Result {
+    // like Python, integral parts are printed without `.0`
+    if re == 0.0 && re.is_sign_positive() {
+        write!(f, "{im}j")
+    } else if im.is_sign_negative() {
+        write!(f, "({re}-{}j)", -im)
+    } else {
+        write!(f, "({re}+{im}j)")
+    }
+}
+
+fn fmt_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "'")?;
+    for c in s.chars() {
+        match c {
+            '\'' => write!(f, "\\'")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\t' => write!(f, "\\t")?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "'")
+}
+
+fn fmt_bytes(f: &mut fmt::Formatter<'_>, v: &[u8]) -> fmt::Result {
+    write!(f, "b'")?;
+    for b in v {
+        match b {
+            b'\'' => write!(f, "\\'")?,
+            b'\\' => write!(f, "\\\\")?,
+            b'\n' => write!(f, "\\n")?,
+            b'\t' => write!(f, "\\t")?,
+            0x20..=0x7e => write!(f, "{}", *b as char)?,
+            b => write!(f, "\\x{b:02x}")?,
+        }
+    }
+    write!(f, "'")
+}
+
 impl PartialEq for Pmt {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -185,11 +323,17 @@ impl PartialEq for Pmt {
             (Pmt::U64(x), Pmt::U64(y)) => x == y,
             (Pmt::F32(x), Pmt::F32(y)) => x == y,
             (Pmt::F64(x), Pmt::F64(y)) => x == y,
+            (Pmt::CF32(x), Pmt::CF32(y)) => x == y,
             (Pmt::VecF32(x), Pmt::VecF32(y)) => x == y,
             (Pmt::VecU64(x), Pmt::VecU64(y)) => x == y,
+            (Pmt::VecU8(x), Pmt::VecU8(y)) => x == y,
+            (Pmt::VecI16(x), Pmt::VecI16(y)) => x == y,
+            (Pmt::VecI32(x), Pmt::VecI32(y)) => x == y,
             (Pmt::VecCF32(x), Pmt::VecCF32(y)) => x == y,
+            (Pmt::VecCF64(x), Pmt::VecCF64(y)) => x == y,
             (Pmt::Blob(x), Pmt::Blob(y)) => x == y,
             (Pmt::VecPmt(x), Pmt::VecPmt(y)) => x == y,
+            (Pmt::Tuple(x), Pmt::Tuple(y)) => x == y,
             (Pmt::Finished, Pmt::Finished) => true,
             (Pmt::MapStrPmt(x), Pmt::MapStrPmt(y)) => x == y,
             _ => false,
@@ -203,9 +347,9 @@ impl std::str::FromStr for Pmt {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "Ok" | "ok" => return Ok(Pmt::Ok),
-            "Null" | "null" => return Ok(Pmt::Null),
-            "true" => return Ok(Pmt::Bool(true)),
-            "false" => return Ok(Pmt::Bool(false)),
+            "Null" | "null" | "None" => return Ok(Pmt::Null),
+            "true" | "True" => return Ok(Pmt::Bool(true)),
+            "false" | "False" => return Ok(Pmt::Bool(false)),
             "InvalidValue" | "invalidvalue" => return Ok(Pmt::InvalidValue),
             "Finished" | "finished" => return Ok(Pmt::Finished),
             _ => (),
@@ -403,6 +547,125 @@ impl TryFrom<Pmt> for Vec<u64> {
     }
 }
 
+impl TryFrom<Pmt> for Vec<Complex64> {
+    type Error = PmtConversionError;
+
+    fn try_from(value: Pmt) -> Result<Vec<Complex64>, Self::Error> {
+        match value {
+            Pmt::VecCF64(v) => Ok(v),
+            _ => Err(PmtConversionError),
+        }
+    }
+}
+
+impl TryFrom<Pmt> for Vec<u8> {
+    type Error = PmtConversionError;
+
+    fn try_from(value: Pmt) -> Result<Vec<u8>, Self::Error> {
+        match value {
+            Pmt::VecU8(v) => Ok(v),
+            Pmt::Blob(v) => Ok(v),
+            _ => Err(PmtConversionError),
+        }
+    }
+}
+
+impl TryFrom<Pmt> for Vec<i16> {
+    type Error = PmtConversionError;
+
+    fn try_from(value: Pmt) -> Result<Vec<i16>, Self::Error> {
+        match value {
+            Pmt::VecI16(v) => Ok(v),
+            _ => Err(PmtConversionError),
+        }
+    }
+}
+
+impl TryFrom<Pmt> for Vec<i32> {
+    type Error = PmtConversionError;
+
+    fn try_from(value: Pmt) -> Result<Vec<i32>, Self::Error> {
+        match value {
+            Pmt::VecI32(v) => Ok(v),
+            _ => Err(PmtConversionError),
+        }
+    }
+}
+
+impl TryFrom<&Pmt> for Complex32 {
+    type Error = PmtConversionError;
+
+    fn try_from(value: &Pmt) -> Result<Complex32, Self::Error> {
+        match value {
+            Pmt::CF32(v) => Ok(*v),
+            Pmt::F32(v) => Ok(Complex32::new(*v, 0.0)),
+            Pmt::F64(v) => Ok(Complex32::new(*v as f32, 0.0)),
+            _ => Err(PmtConversionError),
+        }
+    }
+}
+
+impl TryFrom<Pmt> for Complex32 {
+    type Error = PmtConversionError;
+
+    fn try_from(value: Pmt) -> Result<Complex32, Self::Error> {
+        (&value).try_into()
+    }
+}
+
+impl<T> TryFrom<Pmt> for HashMap<String, T>
+where
+    T: TryFrom<Pmt, Error = PmtConversionError>,
+{
+    type Error = PmtConversionError;
+
+    fn try_from(value: Pmt) -> Result<HashMap<String, T>, Self::Error> {
+        match value {
+            Pmt::MapStrPmt(m) => m
+                .into_iter()
+                .map(|(k, v)| Ok((k, T::try_from(v)?)))
+                .collect(),
+            _ => Err(PmtConversionError),
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($n:literal; $($t:ident),+) => {
+        impl<$($t),+> From<($($t,)+)> for Pmt
+        where
+            $($t: Into<Pmt>),+
+        {
+            #[allow(non_snake_case)]
+            fn from(($($t,)+): ($($t,)+)) -> Self {
+                Pmt::Tuple(vec![$($t.into()),+])
+            }
+        }
+
+        impl<$($t),+> TryFrom<Pmt> for ($($t,)+)
+        where
+            $($t: TryFrom<Pmt, Error = PmtConversionError>),+
+        {
+            type Error = PmtConversionError;
+
+            fn try_from(value: Pmt) -> Result<Self, Self::Error> {
+                match value {
+                    Pmt::Tuple(v) if v.len() == $n => {
+                        let mut v = v.into_iter();
+                        Ok(($($t::try_from(v.next().unwrap())?,)+))
+                    }
+                    _ => Err(PmtConversionError),
+                }
+            }
+        }
+    };
+}
+
+impl_tuple!(1; A);
+impl_tuple!(2; A, B);
+impl_tuple!(3; A, B, C);
+impl_tuple!(4; A, B, C, D);
+
 impl From<()> for Pmt {
     fn from(_: ()) -> Self {
         Pmt::Null
@@ -469,11 +732,47 @@ impl From<Vec<Complex32>> for Pmt {
     }
 }
 
+impl From<Complex32> for Pmt {
+    fn from(v: Complex32) -> Self {
+        Pmt::CF32(v)
+    }
+}
+
+impl From<Vec<Complex64>> for Pmt {
+    fn from(v: Vec<Complex64>) -> Self {
+        Pmt::VecCF64(v)
+    }
+}
+
+impl From<Vec<u8>> for Pmt {
+    fn from(v: Vec<u8>) -> Self {
+        Pmt::VecU8(v)
+    }
+}
+
+impl From<Vec<i16>> for Pmt {
+    fn from(v: Vec<i16>) -> Self {
+        Pmt::VecI16(v)
+    }
+}
+
+impl From<Vec<i32>> for Pmt {
+    fn from(v: Vec<i32>) -> Self {
+        Pmt::VecI32(v)
+    }
+}
+
+impl<T: Into<Pmt>> From<HashMap<String, T>> for Pmt {
+    fn from(v: HashMap<String, T>) -> Self {
+        Pmt::MapStrPmt(v.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
 /// PMT types that do not wrap values.
 ///
 /// Useful for bindings to other languages that do not support Rust's broad enum features.
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PmtKind {
     /// Ok
     Ok,
@@ -497,16 +796,28 @@ pub enum PmtKind {
     F32,
     /// F64
     F64,
+    /// CF32
+    CF32,
     /// VecCF32
     VecCF32,
+    /// VecCF64
+    VecCF64,
     /// VecF32
     VecF32,
     /// VecU64
     VecU64,
+    /// VecU8
+    VecU8,
+    /// VecI16
+    VecI16,
+    /// VecI32
+    VecI32,
     /// Blob
     Blob,
     /// Vec Pmt
     VecPmt,
+    /// Tuple
+    Tuple,
     /// Finished
     Finished,
     /// Map String -> Pmt
@@ -541,11 +852,17 @@ impl fmt::Display for PmtKind {
             PmtKind::U64 => write!(f, "U64"),
             PmtKind::F32 => write!(f, "F32"),
             PmtKind::F64 => write!(f, "F64"),
+            PmtKind::CF32 => write!(f, "CF32"),
             PmtKind::VecCF32 => write!(f, "VecCF32"),
+            PmtKind::VecCF64 => write!(f, "VecCF64"),
             PmtKind::VecF32 => write!(f, "VecF32"),
             PmtKind::VecU64 => write!(f, "VecU64"),
+            PmtKind::VecU8 => write!(f, "VecU8"),
+            PmtKind::VecI16 => write!(f, "VecI16"),
+            PmtKind::VecI32 => write!(f, "VecI32"),
             PmtKind::Blob => write!(f, "Blob"),
             PmtKind::VecPmt => write!(f, "VecPmt"),
+            PmtKind::Tuple => write!(f, "Tuple"),
             PmtKind::Finished => write!(f, "Finished"),
             PmtKind::MapStrPmt => write!(f, "MapStrPmt"),
             PmtKind::Any => write!(f, "Any"),
@@ -569,10 +886,17 @@ impl std::str::FromStr for PmtKind {
             "U64" => return Ok(PmtKind::U64),
             "F32" => return Ok(PmtKind::F32),
             "F64" => return Ok(PmtKind::F64),
+            "CF32" => return Ok(PmtKind::CF32),
+            "VecCF32" => return Ok(PmtKind::VecCF32),
+            "VecCF64" => return Ok(PmtKind::VecCF64),
             "VecF32" => return Ok(PmtKind::VecF32),
             "VecU64" => return Ok(PmtKind::VecU64),
+            "VecU8" => return Ok(PmtKind::VecU8),
+            "VecI16" => return Ok(PmtKind::VecI16),
+            "VecI32" => return Ok(PmtKind::VecI32),
             "Blob" => return Ok(PmtKind::Blob),
             "VecPmt" => return Ok(PmtKind::VecPmt),
+            "Tuple" => return Ok(PmtKind::Tuple),
             "Finished" => return Ok(PmtKind::Finished),
             "MapStrPmt" => return Ok(PmtKind::MapStrPmt),
             "Any" => return Ok(PmtKind::Any),
@@ -589,7 +913,7 @@ mod test {
     #[test]
     fn pmt() {
         let p = Pmt::Null;
-        assert_eq!(p.to_string(), "Null");
+        assert_eq!(p.to_string(), "None");
         let p = Pmt::String("foo".to_string());
         assert_eq!(p.to_string(), "foo");
     }
@@ -746,6 +1070,78 @@ mod test {
         assert_eq!(p.try_into(), Ok(e));
     }
 
+    #[test]
+    fn from_into_extended() {
+        let e = Complex32::new(1.0, -2.0);
+        let p = Pmt::from(e);
+        assert_eq!(p, Pmt::CF32(e));
+        assert_eq!((&p).try_into(), Ok(e));
+        assert_eq!(
+            Complex32::try_from(Pmt::F32(3.0)),
+            Ok(Complex32::new(3.0, 0.0))
+        );
+
+        let e = vec![Complex64::new(1.0, 2.0)];
+        let p = Pmt::from(e.clone());
+        assert_eq!(p, Pmt::VecCF64(e.clone()));
+        assert_eq!(p.try_into(), Ok(e));
+
+        let e: Vec<u8> = vec![1, 2, 3];
+        let p = Pmt::from(e.clone());
+        assert_eq!(p, Pmt::VecU8(e.clone()));
+        assert_eq!(p.try_into(), Ok(e.clone()));
+        assert_eq!(Pmt::Blob(e.clone()).try_into(), Ok(e));
+
+        let e: Vec<i16> = vec![-1, 2];
+        let p = Pmt::from(e.clone());
+        assert_eq!(p, Pmt::VecI16(e.clone()));
+        assert_eq!(p.try_into(), Ok(e));
+
+        let e: Vec<i32> = vec![-1, 2];
+        let p = Pmt::from(e.clone());
+        assert_eq!(p, Pmt::VecI32(e.clone()));
+        assert_eq!(p.try_into(), Ok(e));
+
+        let p = Pmt::from((1.0f64, 2usize));
+        assert_eq!(p, Pmt::Tuple(vec![Pmt::F64(1.0), Pmt::Usize(2)]));
+        assert_eq!(p.clone().try_into(), Ok((1.0f64, 2usize)));
+        assert_eq!(<(f64, usize, bool)>::try_from(p), Err(PmtConversionError));
+
+        let e = HashMap::from([("a".to_string(), 1.0f64), ("b".to_string(), 2.0)]);
+        let p = Pmt::from(e.clone());
+        assert_eq!(p.kind(), PmtKind::MapStrPmt);
+        assert_eq!(p.clone().try_into(), Ok(e));
+        assert_eq!(
+            HashMap::<String, bool>::try_from(p),
+            Err(PmtConversionError)
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Pmt::Bool(true).to_string(), "True");
+        assert_eq!(Pmt::F64(1.0).to_string(), "1.0");
+        assert_eq!(Pmt::F32(f32::NEG_INFINITY).to_string(), "-inf");
+        assert_eq!(Pmt::CF32(Complex32::new(1.0, -2.5)).to_string(), "(1-2.5j)");
+        assert_eq!(Pmt::CF32(Complex32::new(0.0, 2.0)).to_string(), "2j");
+        assert_eq!(Pmt::VecF32(vec![1.0, 2.5]).to_string(), "[1.0, 2.5]");
+        assert_eq!(Pmt::VecI16(vec![-1, 2]).to_string(), "[-1, 2]");
+        assert_eq!(Pmt::Blob(vec![1, b'a', b'\'']).to_string(), "b'\\x01a\\''");
+        assert_eq!(
+            Pmt::Tuple(vec![Pmt::U32(1), Pmt::String("a".to_string())]).to_string(),
+            "(1, 'a')"
+        );
+        assert_eq!(Pmt::Tuple(vec![Pmt::Null]).to_string(), "(None,)");
+        let p = Pmt::MapStrPmt(HashMap::from([
+            ("b".to_string(), Pmt::VecPmt(vec![Pmt::Bool(false)])),
+            ("a".to_string(), Pmt::F64(2.4e9)),
+        ]));
+        assert_eq!(p.to_string(), "{'a': 2400000000.0, 'b': [False]}");
+        assert_eq!(Pmt::Any(Box::new(1u8)).to_string(), "<Any u8>");
+        assert_eq!("True".parse::<Pmt>(), Ok(Pmt::Bool(true)));
+        assert_eq!("None".parse::<Pmt>(), Ok(Pmt::Null));
+    }
+
     #[test]
     fn pmt_kind() {
         let p = Pmt::U32(42);
@@ -767,6 +1163,18 @@ mod test {
         let p = Pmt::VecCF32(vec![]);
         assert_eq!(PmtKind::VecCF32, p.kind());
         assert_eq!(PmtKind::VecCF32, (&p).into());
+
+        for k in [
+            PmtKind::CF32,
+            PmtKind::VecCF32,
+            PmtKind::VecCF64,
+            PmtKind::VecU8,
+            PmtKind::VecI16,
+            PmtKind::VecI32,
+            PmtKind::Tuple,
+        ] {
+            assert_eq!(k.to_string().parse(), Ok(k));
+        }
     }
 
     #[test]
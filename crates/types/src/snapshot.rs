@@ -0,0 +1,58 @@
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::Pmt;
+
+/// Snapshot of the settings of a `Flowgraph`.
+///
+/// Maps the instance name of each block to the current values of its message handlers, as
+/// reported by the getters of their
+/// [`MessageInputSchema`](crate::MessageInputSchema). Snapshots are stored as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FlowgraphSnapshot {
+    /// Settings (handler name to value) per block instance name
+    pub blocks: BTreeMap<String, BTreeMap<String, Pmt>>,
+}
+
+impl FlowgraphSnapshot {
+    /// Create an empty snapshot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a snapshot from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let s = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&s)?)
+    }
+
+    /// Store the snapshot in a JSON file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let s = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn save_load() {
+        let mut s = FlowgraphSnapshot::new();
+        s.blocks.insert(
+            "SeifySource-0".to_string(),
+            BTreeMap::from([
+                ("freq".to_string(), Pmt::F64(100e6)),
+                ("gain".to_string(), Pmt::F64(20.0)),
+            ]),
+        );
+        let path = std::env::temp_dir().join(format!("snapshot-{}.json", std::process::id()));
+        s.save(&path).unwrap();
+        let l = FlowgraphSnapshot::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(s, l);
+    }
+}
@@ -1,13 +1,12 @@
 #![allow(clippy::excessive_precision)]
 use anyhow::Result;
-use codec2::Codec2;
-use codec2::Codec2Mode;
 use futuresdr::blocks::Apply;
-use futuresdr::blocks::ApplyNM;
 use futuresdr::blocks::Combine;
 use futuresdr::blocks::FileSource;
 use futuresdr::blocks::FirBuilder;
 use futuresdr::blocks::audio::AudioSink;
+use futuresdr::blocks::audio::Codec2Decoder;
+use futuresdr::blocks::audio::Codec2Mode;
 use futuresdr::prelude::*;
 
 use m17::DecoderBlock;
@@ -117,20 +116,13 @@ fn main() -> Result<()> {
     let symbol_sync: SymbolSync =
         SymbolSync::new(10.0, 2.0 * std::f32::consts::PI * 0.0015, 1.0, 1.0, 0.05, 1);
     let decoder = DecoderBlock::new();
-    let mut c2 = Codec2::new(Codec2Mode::MODE_3200);
-    assert_eq!(c2.samples_per_frame(), 160);
-    assert_eq!(c2.bits_per_frame(), 64);
-    let codec =
-        ApplyNM::<_, _, _, { 64_usize.div_ceil(8) }, 160>::new(move |i: &[u8], o: &mut [i16]| {
-            c2.decode(o, i);
-        });
-    let conv = Apply::<_, _, _>::new(|i: &i16| (*i as f32) / i16::MAX as f32);
+    let codec: Codec2Decoder = Codec2Decoder::new(Codec2Mode::MODE_3200);
     let upsample = FirBuilder::resampling::<f32, f32>(6, 1);
     let snk = AudioSink::new(48000, 1)?;
 
     connect!(fg, src > demod > in0.subtract;
                  demod > moving_average > in1.subtract;
-                 subtract > rrc > symbol_sync > decoder > codec > conv > upsample > snk);
+                 subtract > rrc > symbol_sync > decoder > codec > upsample > snk);
 
     Runtime::new().run(fg)?;
 
@@ -1,12 +1,14 @@
 #![allow(clippy::excessive_precision)]
 use anyhow::Result;
-use codec2::Codec2;
-use codec2::Codec2Mode;
+use clap::Parser;
 use futuresdr::blocks::Apply;
 use futuresdr::blocks::ApplyNM;
 use futuresdr::blocks::FileSink;
 use futuresdr::blocks::FiniteSource;
 use futuresdr::blocks::FirBuilder;
+use futuresdr::blocks::audio::AudioSource;
+use futuresdr::blocks::audio::Codec2Encoder;
+use futuresdr::blocks::audio::Codec2Mode;
 use futuresdr::hound;
 use futuresdr::prelude::*;
 
@@ -15,35 +17,19 @@ use m17::EncoderBlock;
 use m17::LinkSetupFrame;
 use m17::RRC_TAPS;
 
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// Record voice from the microphone instead of rick.wav
+    #[clap(long)]
+    mic: bool,
+}
+
 fn main() -> Result<()> {
+    let args = Args::parse();
     let mut fg = Flowgraph::new();
 
-    let reader = hound::WavReader::open("rick.wav").expect("failed to open rick.wav");
-    let spec = reader.spec();
-    assert_eq!(spec.channels, 1);
-    assert_eq!(spec.sample_rate, 8000);
-    assert_eq!(spec.sample_format, hound::SampleFormat::Int);
-    assert_eq!(spec.bits_per_sample, 16);
-    let data: Vec<i16> = reader.into_samples::<i16>().map(|v| v.unwrap()).collect();
-
-    let mut i = 0;
-    let src = FiniteSource::<_, _>::new(move || {
-        if i >= data.len() {
-            None
-        } else {
-            i += 1;
-            Some(data[i - 1])
-        }
-    });
-
-    let mut c2 = Codec2::new(Codec2Mode::MODE_3200);
-    assert_eq!(c2.samples_per_frame(), 160);
-    assert_eq!(c2.bits_per_frame(), 64);
-
-    let codec2 =
-        ApplyNM::<_, _, _, 160, { 64_usize.div_ceil(8) }>::new(move |i: &[i16], o: &mut [u8]| {
-            c2.encode(o, i);
-        });
+    let codec2: Codec2Encoder = Codec2Encoder::new(Codec2Mode::MODE_3200);
 
     let lsf = LinkSetupFrame::new(CallSign::new_id("DF1BBL"), CallSign::new_broadcast());
     let encoder: EncoderBlock = EncoderBlock::new(lsf);
@@ -62,7 +48,33 @@ fn main() -> Result<()> {
         curr
     });
     let snk = FileSink::<Complex32>::new("input.cf32");
-    connect!(fg, src > codec2 > encoder > pulse > rrc > fm > snk);
+    connect!(fg, codec2 > encoder > pulse > rrc > fm > snk);
+    if args.mic {
+        let mic: AudioSource = AudioSource::new(8000, 1)?;
+        connect!(fg, mic > codec2);
+    } else {
+        let reader = hound::WavReader::open("rick.wav").expect("failed to open rick.wav");
+        let spec = reader.spec();
+        assert_eq!(spec.channels, 1);
+        assert_eq!(spec.sample_rate, 8000);
+        assert_eq!(spec.sample_format, hound::SampleFormat::Int);
+        assert_eq!(spec.bits_per_sample, 16);
+        let data: Vec<f32> = reader
+            .into_samples::<i16>()
+            .map(|v| v.unwrap() as f32 / i16::MAX as f32)
+            .collect();
+
+        let mut i = 0;
+        let src = FiniteSource::<_, _>::new(move || {
+            if i >= data.len() {
+                None
+            } else {
+                i += 1;
+                Some(data[i - 1])
+            }
+        });
+        connect!(fg, src > codec2);
+    }
 
     // let upsample = FirBuilder::resampling::<Complex32, Complex32>(16, 1);
     //
@@ -38,6 +38,7 @@ use futuresdr::prelude::*;
 /// });
 /// ```
 #[derive(Block)]
+#[tag_propagation(one_to_one)]
 pub struct Apply<F, A, B, IN = DefaultCpuReader<A>, OUT = DefaultCpuWriter<B>>
 where
     F: FnMut(&A) -> B + Send + 'static,
@@ -99,11 +100,7 @@ where
                 *r = (self.f)(v);
             }
 
-            i_tags.iter().for_each(|t| {
-                if t.index < m {
-                    o_tags.add_tag(t.index, t.tag.clone())
-                }
-            });
+            Self::tag_propagation().forward(0, 0, i_tags, m, &mut o_tags, m);
 
             self.input.consume(m);
             self.output.produce(m);
@@ -33,6 +33,7 @@ use futuresdr::prelude::*;
 /// ```
 #[allow(clippy::type_complexity)]
 #[derive(Block)]
+#[tag_propagation(one_to_one)]
 pub struct ApplyNM<
     F,
     A,
@@ -88,8 +89,8 @@ where
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        let i = self.input.slice();
-        let o = self.output.slice();
+        let (i, i_tags) = self.input.slice_with_tags();
+        let (o, mut o_tags) = self.output.slice_with_tags();
         let i_len = i.len();
 
         // See https://www.nickwilcox.com/blog/autovec/ for a discussion
@@ -100,6 +101,8 @@ where
                 (self.f)(v, r);
             }
 
+            Self::tag_propagation().forward(0, 0, i_tags, N * m, &mut o_tags, M * m);
+
             self.input.consume(N * m);
             self.output.produce(M * m);
         }
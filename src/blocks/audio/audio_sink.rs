@@ -3,15 +3,48 @@ use cpal::SampleRate;
 use cpal::Stream;
 use cpal::StreamConfig;
 use cpal::traits::DeviceTrait;
-use cpal::traits::HostTrait;
 use cpal::traits::StreamTrait;
 use futures::SinkExt;
 use futures::channel::mpsc;
 use futures::channel::oneshot;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
+use super::device::device;
+use super::device::device_names;
+use super::device::sample_rates;
+use super::device::select_config;
+use super::resampler::AudioResampler;
+use super::resampler::DriftController;
+use super::resampler::remap;
 use crate::prelude::*;
 
 /// Audio Sink.
+///
+/// Plays interleaved samples with the given number of channels. If the device does not support
+/// the sample rate, the samples are resampled to a supported rate. If it does not support the
+/// number of channels, mono is duplicated to all channels of the device, surplus channels are
+/// dropped, and missing channels are filled with zeros.
+///
+/// Use the [`AudioSinkBuilder`] to select a device by name or to compensate clock drift.
+///
+/// # Inputs
+///
+/// `input`: Interleaved samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::audio::AudioSink;
+/// use futuresdr::blocks::audio::AudioSinkBuilder;
+///
+/// let snk: AudioSink = AudioSink::new(48_000, 1)?;
+/// let snk: AudioSink = AudioSinkBuilder::new(48_000, 2)
+///     .device("USB")
+///     .drift_compensation(true)
+///     .build()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
 #[derive(Block)]
 pub struct AudioSink<I = DefaultCpuReader<f32>>
 where
@@ -22,9 +55,14 @@ where
     input_channels: u16,
     sample_rate: u32,
     channels: u16,
+    device: Option<String>,
     stream: Option<Stream>,
     min_buffer_size: usize,
     vec: Vec<f32>,
+    resampled: Vec<f32>,
+    resampler: Option<AudioResampler>,
+    drift: Option<DriftController>,
+    queued: Arc<AtomicUsize>,
     terminated: Option<oneshot::Receiver<()>>,
     tx: Option<mpsc::Sender<Vec<f32>>>,
 }
@@ -34,53 +72,15 @@ where
 unsafe impl<I> Send for AudioSink<I> where I: CpuBufferReader<Item = f32> {}
 
 const QUEUE_SIZE: usize = 5;
-const STANDARD_RATES: [u32; 4] = [24000, 44100, 48000, 96000];
+const MIN_BUFFER_SIZE: usize = 2048;
 
 impl<I> AudioSink<I>
 where
     I: CpuBufferReader<Item = f32>,
 {
-    fn supports_config(sample_rate: u32, channels: u16) -> bool {
-        let Some(device) = cpal::default_host().default_output_device() else {
-            return false;
-        };
-
-        let Ok(configs) = device.supported_output_configs() else {
-            return false;
-        };
-
-        configs.into_iter().any(|config| {
-            config.channels() == channels
-                && sample_rate >= config.min_sample_rate().0
-                && sample_rate <= config.max_sample_rate().0
-        })
-    }
-
-    /// Create AudioSink block
+    /// Create AudioSink block for the default device
     pub fn new(sample_rate: u32, channels: u16) -> Result<Self> {
-        let output_channels = if Self::supports_config(sample_rate, channels) {
-            channels
-        } else if channels == 1 && Self::supports_config(sample_rate, 2) {
-            warn!(
-                "audio sink requested mono output at {} Hz, but only stereo is supported; duplicating samples to both channels",
-                sample_rate
-            );
-            2
-        } else {
-            return Err(Error::InvalidParameter.into());
-        };
-
-        Ok(AudioSink {
-            input: I::default(),
-            input_channels: channels,
-            sample_rate,
-            channels: output_channels,
-            stream: None,
-            min_buffer_size: 2048,
-            vec: Vec::new(),
-            terminated: None,
-            tx: None,
-        })
+        AudioSinkBuilder::new(sample_rate, channels).build()
     }
 }
 
@@ -88,8 +88,8 @@ impl AudioSink<DefaultCpuReader<f32>> {
     /// Get default sample rate
     pub fn default_sample_rate() -> Option<u32> {
         Some(
-            cpal::default_host()
-                .default_output_device()?
+            device(None, false)
+                .ok()?
                 .default_output_config()
                 .ok()?
                 .sample_rate()
@@ -98,27 +98,113 @@ impl AudioSink<DefaultCpuReader<f32>> {
     }
     /// Get supported sample rates
     pub fn supported_sample_rates() -> Vec<u32> {
-        if let Some(d) = cpal::default_host().default_output_device() {
-            if let Ok(configs) = d.supported_output_configs() {
-                let mut v = Vec::new();
-                for c in configs {
-                    let min = c.min_sample_rate().0;
-                    let max = c.max_sample_rate().0;
-                    if min >= 10000 {
-                        v.push(min);
-                    }
-                    if max >= 10000 {
-                        v.push(max);
-                    }
+        device(None, false)
+            .ok()
+            .and_then(|d| d.supported_output_configs().ok())
+            .map(sample_rates)
+            .unwrap_or_default()
+    }
+    /// Get the names of the output devices
+    pub fn devices() -> Vec<String> {
+        device_names(false)
+    }
+}
 
-                    v.extend(STANDARD_RATES.iter().filter(|x| *x >= &min && *x <= &max));
-                }
-                v.sort();
-                v.dedup();
-                return v;
-            }
+/// Build an [AudioSink].
+pub struct AudioSinkBuilder<I = DefaultCpuReader<f32>>
+where
+    I: CpuBufferReader<Item = f32>,
+{
+    sample_rate: u32,
+    channels: u16,
+    device: Option<String>,
+    drift_compensation: bool,
+    _type: std::marker::PhantomData<I>,
+}
+
+impl<I> AudioSinkBuilder<I>
+where
+    I: CpuBufferReader<Item = f32>,
+{
+    /// Create builder for the sample rate and number of channels of the input
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        Self {
+            sample_rate,
+            channels,
+            device: None,
+            drift_compensation: false,
+            _type: std::marker::PhantomData,
         }
-        Vec::new()
+    }
+
+    /// Use the first output device whose name contains `name`
+    #[must_use]
+    pub fn device(mut self, name: impl Into<String>) -> Self {
+        self.device = Some(name.into());
+        self
+    }
+
+    /// Compensate drift between the clock of the flowgraph and the sound card
+    ///
+    /// Adapts the resampling ratio slightly to keep the amount of buffered samples constant.
+    /// This avoids glitches in long-running receivers, where the sample rate of the flowgraph is
+    /// determined by an SDR.
+    #[must_use]
+    pub fn drift_compensation(mut self, enable: bool) -> Self {
+        self.drift_compensation = enable;
+        self
+    }
+
+    /// Build AudioSink
+    pub fn build(self) -> Result<AudioSink<I>> {
+        let dev = device(self.device.as_deref(), false)?;
+        let default_rate = dev.default_output_config().ok().map(|c| c.sample_rate().0);
+        let configs = dev.supported_output_configs()?.collect();
+        let Some((sample_rate, channels)) =
+            select_config(configs, default_rate, self.sample_rate, self.channels)
+        else {
+            return Err(Error::InvalidParameter.into());
+        };
+
+        if channels != self.channels {
+            warn!(
+                "audio sink requested {} channels, but the device uses {}",
+                self.channels, channels
+            );
+        }
+        if sample_rate != self.sample_rate {
+            info!(
+                "audio sink requested {} Hz, resampling to {} Hz",
+                self.sample_rate, sample_rate
+            );
+        }
+
+        let resampler = (sample_rate != self.sample_rate || self.drift_compensation).then(|| {
+            AudioResampler::new(
+                sample_rate as f32 / self.sample_rate as f32,
+                self.channels as usize,
+            )
+        });
+        let drift = self
+            .drift_compensation
+            .then(|| DriftController::new(QUEUE_SIZE * MIN_BUFFER_SIZE / 2));
+
+        Ok(AudioSink {
+            input: I::default(),
+            input_channels: self.channels,
+            sample_rate,
+            channels,
+            device: self.device,
+            stream: None,
+            min_buffer_size: MIN_BUFFER_SIZE,
+            vec: Vec::new(),
+            resampled: Vec::new(),
+            resampler,
+            drift,
+            queued: Arc::new(AtomicUsize::new(0)),
+            terminated: None,
+            tx: None,
+        })
     }
 }
 
@@ -128,10 +214,7 @@ where
     I: CpuBufferReader<Item = f32>,
 {
     async fn init(&mut self, _m: &mut MessageOutputs, _b: &mut BlockMeta) -> Result<()> {
-        let device = cpal::default_host()
-            .default_output_device()
-            .expect("no output device available");
-        let duplicate_mono = self.input_channels == 1 && self.channels == 2;
+        let device = device(self.device.as_deref(), false)?;
 
         let config = StreamConfig {
             channels: self.channels,
@@ -144,6 +227,7 @@ where
         self.terminated = Some(terminated);
         let (tx, mut rx) = mpsc::channel(QUEUE_SIZE);
         let mut iter: Option<Vec<f32>> = None;
+        let queued = self.queued.clone();
 
         let stream = device
             .build_output_stream(
@@ -156,37 +240,23 @@ where
                             if let Some(t) = terminate.take() {
                                 t.send(()).unwrap();
                             }
-                            return;
+                            break;
                         }
-                        if duplicate_mono {
-                            let n = std::cmp::min(v.len(), (data.len() - i) / 2);
-                            for j in 0..(n) {
-                                data[i + 2 * j] = v[j];
-                                data[i + 2 * j + 1] = v[j];
-                            }
-                            i += 2 * n;
-                            if n < v.len() {
-                                iter = Some(v.split_off(n));
-                                debug_assert!(!iter.as_ref().unwrap().is_empty());
-                                debug_assert_eq!(i, data.len());
-                                return;
-                            } else if i == data.len() {
-                                return;
-                            }
-                        } else {
-                            let n = std::cmp::min(v.len(), data.len() - i);
-                            data[i..i + n].copy_from_slice(&v[..n]);
-                            i += n;
-                            if n < v.len() {
-                                iter = Some(v.split_off(n));
-                                debug_assert!(!iter.as_ref().unwrap().is_empty());
-                                debug_assert_eq!(i, data.len());
-                                return;
-                            } else if i == data.len() {
-                                return;
-                            }
+                        let n = std::cmp::min(v.len(), data.len() - i);
+                        data[i..i + n].copy_from_slice(&v[..n]);
+                        queued.fetch_sub(n, Ordering::Relaxed);
+                        i += n;
+                        if n < v.len() {
+                            iter = Some(v.split_off(n));
+                            debug_assert!(!iter.as_ref().unwrap().is_empty());
+                            debug_assert_eq!(i, data.len());
+                            return;
+                        } else if i == data.len() {
+                            return;
                         }
                     }
+                    // underrun
+                    data[i..].fill(0.0);
                 },
                 move |err| {
                     panic!("cpal stream error {err:?}");
@@ -222,19 +292,36 @@ where
         _meta: &mut BlockMeta,
     ) -> Result<()> {
         let i = self.input.slice();
-        let i_len = i.len();
+        let input_channels = self.input_channels as usize;
+        // only complete frames
+        let n = i.len() - i.len() % input_channels;
+
+        let samples = match self.resampler.as_mut() {
+            Some(r) => {
+                if let Some(d) = self.drift.as_mut() {
+                    r.set_correction(d.update(self.queued.load(Ordering::Relaxed)));
+                }
+                self.resampled.clear();
+                r.process(&i[..n], &mut self.resampled);
+                &self.resampled[..]
+            }
+            None => &i[..n],
+        };
+        remap(
+            samples,
+            input_channels,
+            self.channels as usize,
+            &mut self.vec,
+        );
+
+        self.input.consume(n);
 
-        self.vec.extend_from_slice(i);
         if self.vec.len() >= self.min_buffer_size || self.input.finished() {
-            self.tx
-                .as_mut()
-                .unwrap()
-                .send(std::mem::take(&mut self.vec))
-                .await?;
+            let v = std::mem::take(&mut self.vec);
+            self.queued.fetch_add(v.len(), Ordering::Relaxed);
+            self.tx.as_mut().unwrap().send(v).await?;
         }
 
-        self.input.consume(i_len);
-
         if self.input.finished() {
             io.finished = true;
         }
@@ -3,14 +3,45 @@ use cpal::SampleRate;
 use cpal::Stream;
 use cpal::StreamConfig;
 use cpal::traits::DeviceTrait;
-use cpal::traits::HostTrait;
 use cpal::traits::StreamTrait;
 use futures::StreamExt;
 use futures::channel::mpsc;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
+use super::device::device;
+use super::device::device_names;
+use super::device::select_config;
+use super::resampler::AudioResampler;
+use super::resampler::DriftController;
+use super::resampler::remap;
 use crate::prelude::*;
 
 /// Audio Source.
+///
+/// Records interleaved samples with the given number of channels. If the device does not support
+/// the sample rate, the samples are resampled from a supported rate. If it does not support the
+/// number of channels, surplus channels are dropped and missing channels are filled with zeros.
+///
+/// Use the [`AudioSourceBuilder`] to select a device by name or to compensate clock drift.
+///
+/// # Outputs
+///
+/// `output`: Interleaved samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::audio::AudioSource;
+/// use futuresdr::blocks::audio::AudioSourceBuilder;
+///
+/// let src: AudioSource = AudioSource::new(48_000, 1)?;
+/// let src: AudioSource = AudioSourceBuilder::new(8_000, 1)
+///     .device("USB")
+///     .drift_compensation(true)
+///     .build()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
 #[derive(Block)]
 pub struct AudioSource<O = DefaultCpuWriter<f32>>
 where
@@ -21,9 +52,14 @@ where
     output_channels: u16,
     sample_rate: u32,
     channels: u16,
+    device: Option<String>,
     stream: Option<Stream>,
     rx: Option<mpsc::UnboundedReceiver<Vec<f32>>>,
     buff: Option<(Vec<f32>, usize)>,
+    remapped: Vec<f32>,
+    resampler: Option<AudioResampler>,
+    drift: Option<DriftController>,
+    queued: Arc<AtomicUsize>,
 }
 
 // cpal::Stream is !Send
@@ -34,44 +70,111 @@ impl<O> AudioSource<O>
 where
     O: CpuBufferWriter<Item = f32>,
 {
-    fn supports_config(sample_rate: u32, channels: u16) -> bool {
-        let Some(device) = cpal::default_host().default_input_device() else {
-            return false;
-        };
+    /// Create AudioSource block for the default device
+    pub fn new(sample_rate: u32, channels: u16) -> Result<Self> {
+        AudioSourceBuilder::new(sample_rate, channels).build()
+    }
+}
 
-        let Ok(configs) = device.supported_input_configs() else {
-            return false;
-        };
+impl AudioSource<DefaultCpuWriter<f32>> {
+    /// Get the names of the input devices
+    pub fn devices() -> Vec<String> {
+        device_names(true)
+    }
+}
 
-        configs.into_iter().any(|config| {
-            config.channels() == channels
-                && sample_rate >= config.min_sample_rate().0
-                && sample_rate <= config.max_sample_rate().0
-        })
+/// Build an [AudioSource].
+pub struct AudioSourceBuilder<O = DefaultCpuWriter<f32>>
+where
+    O: CpuBufferWriter<Item = f32>,
+{
+    sample_rate: u32,
+    channels: u16,
+    device: Option<String>,
+    drift_compensation: bool,
+    _type: std::marker::PhantomData<O>,
+}
+
+impl<O> AudioSourceBuilder<O>
+where
+    O: CpuBufferWriter<Item = f32>,
+{
+    /// Create builder for the sample rate and number of channels of the output
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        Self {
+            sample_rate,
+            channels,
+            device: None,
+            drift_compensation: false,
+            _type: std::marker::PhantomData,
+        }
     }
 
-    /// Create AudioSource block
-    pub fn new(sample_rate: u32, channels: u16) -> Result<Self> {
-        let input_channels = if Self::supports_config(sample_rate, channels) {
-            channels
-        } else if channels == 1 && Self::supports_config(sample_rate, 2) {
-            warn!(
-                "audio source requested mono input at {} Hz, but only stereo is supported; using channel 0",
-                sample_rate
-            );
-            2
-        } else {
+    /// Use the first input device whose name contains `name`
+    #[must_use]
+    pub fn device(mut self, name: impl Into<String>) -> Self {
+        self.device = Some(name.into());
+        self
+    }
+
+    /// Compensate drift between the clock of the sound card and the flowgraph
+    ///
+    /// Adapts the resampling ratio slightly to keep the amount of buffered samples constant.
+    /// This is useful if the flowgraph is paced by another clock, e.g., an SDR transmitter.
+    #[must_use]
+    pub fn drift_compensation(mut self, enable: bool) -> Self {
+        self.drift_compensation = enable;
+        self
+    }
+
+    /// Build AudioSource
+    pub fn build(self) -> Result<AudioSource<O>> {
+        let dev = device(self.device.as_deref(), true)?;
+        let default_rate = dev.default_input_config().ok().map(|c| c.sample_rate().0);
+        let configs = dev.supported_input_configs()?.collect();
+        let Some((sample_rate, channels)) =
+            select_config(configs, default_rate, self.sample_rate, self.channels)
+        else {
             return Err(Error::InvalidParameter.into());
         };
 
+        if channels != self.channels {
+            warn!(
+                "audio source requested {} channels, but the device uses {}",
+                self.channels, channels
+            );
+        }
+        if sample_rate != self.sample_rate {
+            info!(
+                "audio source requested {} Hz, resampling from {} Hz",
+                self.sample_rate, sample_rate
+            );
+        }
+
+        let resampler = (sample_rate != self.sample_rate || self.drift_compensation).then(|| {
+            AudioResampler::new(
+                self.sample_rate as f32 / sample_rate as f32,
+                self.channels as usize,
+            )
+        });
+        // keep about 100ms buffered
+        let drift = self
+            .drift_compensation
+            .then(|| DriftController::new(sample_rate as usize * channels as usize / 10));
+
         Ok(AudioSource {
             output: O::default(),
-            output_channels: channels,
+            output_channels: self.channels,
             sample_rate,
-            channels: input_channels,
+            channels,
+            device: self.device,
             stream: None,
             rx: None,
             buff: None,
+            remapped: Vec::new(),
+            resampler,
+            drift,
+            queued: Arc::new(AtomicUsize::new(0)),
         })
     }
 }
@@ -82,9 +185,7 @@ where
     O: CpuBufferWriter<Item = f32>,
 {
     async fn init(&mut self, _m: &mut MessageOutputs, _b: &mut BlockMeta) -> Result<()> {
-        let device = cpal::default_host()
-            .default_input_device()
-            .expect("no input device available");
+        let device = device(self.device.as_deref(), true)?;
 
         let config = StreamConfig {
             channels: self.channels,
@@ -93,12 +194,13 @@ where
         };
 
         let (tx, rx) = mpsc::unbounded();
+        let queued = self.queued.clone();
 
         let stream = device.build_input_stream(
             &config,
-            move |data, _| {
-                let data = data.to_owned();
-                tx.unbounded_send(data).unwrap();
+            move |data: &[f32], _| {
+                queued.fetch_add(data.len(), Ordering::Relaxed);
+                tx.unbounded_send(data.to_owned()).unwrap();
             },
             move |err| {
                 panic!("cpal stream error {err:?}");
@@ -122,25 +224,10 @@ where
     ) -> Result<()> {
         if let Some((buff, mut full)) = self.buff.take() {
             let o = self.output.slice();
-            if self.output_channels == 1 && self.channels == 2 {
-                let n = std::cmp::min(o.len(), (buff.len() - full) / 2);
-
-                for j in 0..n {
-                    o[j] = buff[full + 2 * j];
-                }
-
-                full += 2 * n;
-                self.output.produce(n);
-            } else {
-                let n = std::cmp::min(o.len(), buff.len() - full);
-
-                for (i, v) in o.iter_mut().take(n).enumerate() {
-                    *v = buff[full + i]
-                }
-
-                full += n;
-                self.output.produce(n);
-            }
+            let n = std::cmp::min(o.len(), buff.len() - full);
+            o[..n].copy_from_slice(&buff[full..full + n]);
+            full += n;
+            self.output.produce(n);
 
             if buff.len() == full {
                 io.call_again = true;
@@ -149,8 +236,31 @@ where
                 self.buff = Some((buff, full));
             }
         } else if let Some(v) = self.rx.as_mut().unwrap().next().await {
+            self.queued.fetch_sub(v.len(), Ordering::Relaxed);
+            let mut out = Vec::new();
+            match self.resampler.as_mut() {
+                Some(r) => {
+                    if let Some(d) = self.drift.as_mut() {
+                        r.set_correction(d.update(self.queued.load(Ordering::Relaxed)));
+                    }
+                    self.remapped.clear();
+                    remap(
+                        &v,
+                        self.channels as usize,
+                        self.output_channels as usize,
+                        &mut self.remapped,
+                    );
+                    r.process(&self.remapped, &mut out);
+                }
+                None => remap(
+                    &v,
+                    self.channels as usize,
+                    self.output_channels as usize,
+                    &mut out,
+                ),
+            }
             io.call_again = true;
-            self.buff = Some((v, 0));
+            self.buff = Some((out, 0));
         } else {
             io.finished = true;
         }
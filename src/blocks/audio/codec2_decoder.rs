@@ -0,0 +1,96 @@
+use codec2::Codec2;
+use codec2::Codec2Mode;
+
+use crate::prelude::*;
+
+/// Decode Codec2 frames to audio samples.
+///
+/// # Inputs
+///
+/// `input`: Encoded frames, each frame padded to full bytes
+///
+/// # Outputs
+///
+/// `output`: Audio samples (8 kHz, mono, `[-1, 1]`)
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::audio::Codec2Decoder;
+/// use futuresdr::blocks::audio::Codec2Mode;
+///
+/// let dec: Codec2Decoder = Codec2Decoder::new(Codec2Mode::MODE_3200);
+/// ```
+#[derive(Block)]
+pub struct Codec2Decoder<I = DefaultCpuReader<u8>, O = DefaultCpuWriter<f32>>
+where
+    I: CpuBufferReader<Item = u8>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+    codec: Codec2,
+    samples: Vec<i16>,
+    bytes: usize,
+}
+
+impl<I, O> Codec2Decoder<I, O>
+where
+    I: CpuBufferReader<Item = u8>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    /// Create Codec2 Decoder block
+    ///
+    /// The mode has to match the mode of the encoder.
+    pub fn new(mode: Codec2Mode) -> Self {
+        let codec = Codec2::new(mode);
+        Self {
+            input: I::default(),
+            output: O::default(),
+            samples: vec![0; codec.samples_per_frame()],
+            bytes: codec.bits_per_frame().div_ceil(8),
+            codec,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for Codec2Decoder<I, O>
+where
+    I: CpuBufferReader<Item = u8>,
+    O: CpuBufferWriter<Item = f32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = self.input.slice();
+        let o = self.output.slice();
+        let n = self.samples.len();
+        let frames = std::cmp::min(i.len() / self.bytes, o.len() / n);
+
+        for (frame, audio) in i
+            .chunks_exact(self.bytes)
+            .zip(o.chunks_exact_mut(n))
+            .take(frames)
+        {
+            self.codec.decode(&mut self.samples, frame);
+            for (v, s) in audio.iter_mut().zip(&self.samples) {
+                *v = *s as f32 / i16::MAX as f32;
+            }
+        }
+
+        let i_len = i.len();
+        self.input.consume(frames * self.bytes);
+        self.output.produce(frames * n);
+
+        if self.input.finished() && i_len - frames * self.bytes < self.bytes {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use codec2::Codec2;
+use codec2::Codec2Mode;
+
+use crate::prelude::*;
+
+/// Encode audio samples to Codec2 frames.
+///
+/// Codec2 is a speech codec for low bitrates, e.g., for digital voice modes like M17. Incomplete
+/// frames at the end of the input are dropped.
+///
+/// # Inputs
+///
+/// `input`: Audio samples (8 kHz, mono, `[-1, 1]`)
+///
+/// # Outputs
+///
+/// `output`: Encoded frames, each frame padded to full bytes
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::audio::Codec2Encoder;
+/// use futuresdr::blocks::audio::Codec2Mode;
+///
+/// let enc: Codec2Encoder = Codec2Encoder::new(Codec2Mode::MODE_3200);
+/// ```
+#[derive(Block)]
+pub struct Codec2Encoder<I = DefaultCpuReader<f32>, O = DefaultCpuWriter<u8>>
+where
+    I: CpuBufferReader<Item = f32>,
+    O: CpuBufferWriter<Item = u8>,
+{
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+    codec: Codec2,
+    samples: Vec<i16>,
+    bytes: usize,
+}
+
+impl<I, O> Codec2Encoder<I, O>
+where
+    I: CpuBufferReader<Item = f32>,
+    O: CpuBufferWriter<Item = u8>,
+{
+    /// Create Codec2 Encoder block
+    ///
+    /// The frame size depends on the mode, e.g., 160 samples to 8 bytes for 3200 bit/s.
+    pub fn new(mode: Codec2Mode) -> Self {
+        let codec = Codec2::new(mode);
+        Self {
+            input: I::default(),
+            output: O::default(),
+            samples: vec![0; codec.samples_per_frame()],
+            bytes: codec.bits_per_frame().div_ceil(8),
+            codec,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for Codec2Encoder<I, O>
+where
+    I: CpuBufferReader<Item = f32>,
+    O: CpuBufferWriter<Item = u8>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = self.input.slice();
+        let o = self.output.slice();
+        let n = self.samples.len();
+        let frames = std::cmp::min(i.len() / n, o.len() / self.bytes);
+
+        for (audio, frame) in i
+            .chunks_exact(n)
+            .zip(o.chunks_exact_mut(self.bytes))
+            .take(frames)
+        {
+            for (s, v) in self.samples.iter_mut().zip(audio) {
+                *s = (v.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            }
+            self.codec.encode(frame, &self.samples);
+        }
+
+        let i_len = i.len();
+        self.input.consume(frames * n);
+        self.output.produce(frames * self.bytes);
+
+        if self.input.finished() && i_len - frames * n < n {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use cpal::Device;
+use cpal::SupportedStreamConfigRange;
+use cpal::traits::DeviceTrait;
+use cpal::traits::HostTrait;
+
+use crate::runtime::Error;
+
+const STANDARD_RATES: [u32; 4] = [24000, 44100, 48000, 96000];
+
+/// Get input or output device, matching `name` or the default device
+pub(super) fn device(name: Option<&str>, input: bool) -> Result<Device, Error> {
+    let host = cpal::default_host();
+    let device = match name {
+        Some(name) => {
+            let devices = if input {
+                host.input_devices()
+            } else {
+                host.output_devices()
+            };
+            devices
+                .map_err(|e| Error::RuntimeError(e.to_string()))?
+                .find(|d| d.name().is_ok_and(|n| n.contains(name)))
+        }
+        None if input => host.default_input_device(),
+        None => host.default_output_device(),
+    };
+    device.ok_or_else(|| {
+        Error::RuntimeError(format!(
+            "no audio {} device {}",
+            if input { "input" } else { "output" },
+            name.unwrap_or("available")
+        ))
+    })
+}
+
+/// Names of input or output devices
+pub(super) fn device_names(input: bool) -> Vec<String> {
+    let host = cpal::default_host();
+    let devices = if input {
+        host.input_devices()
+    } else {
+        host.output_devices()
+    };
+    devices
+        .map(|d| d.filter_map(|d| d.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Supported sample rates of a device
+pub(super) fn sample_rates(configs: impl Iterator<Item = SupportedStreamConfigRange>) -> Vec<u32> {
+    let mut v = Vec::new();
+    for c in configs {
+        let min = c.min_sample_rate().0;
+        let max = c.max_sample_rate().0;
+        if min >= 10000 {
+            v.push(min);
+        }
+        if max >= 10000 {
+            v.push(max);
+        }
+
+        v.extend(STANDARD_RATES.iter().filter(|x| *x >= &min && *x <= &max));
+    }
+    v.sort();
+    v.dedup();
+    v
+}
+
+/// Select the stream configuration of a device for the requested sample rate and channels
+///
+/// Prefers the requested number of channels, stereo for mono, or the smallest number of
+/// channels that is sufficient. If the sample rate is not supported, the default rate of the
+/// device or a standard rate is used, requiring resampling.
+pub(super) fn select_config(
+    configs: Vec<SupportedStreamConfigRange>,
+    default_rate: Option<u32>,
+    sample_rate: u32,
+    channels: u16,
+) -> Option<(u32, u16)> {
+    let rank = |c: u16| {
+        if c == channels {
+            (0, 0)
+        } else if channels == 1 && c == 2 {
+            (1, 0)
+        } else if c > channels {
+            (2, c)
+        } else {
+            (3, u16::MAX - c)
+        }
+    };
+    let device_channels = configs
+        .iter()
+        .map(|c| c.channels())
+        .min_by_key(|c| rank(*c))?;
+    let ranges: Vec<_> = configs
+        .iter()
+        .filter(|c| c.channels() == device_channels)
+        .map(|c| c.min_sample_rate().0..=c.max_sample_rate().0)
+        .collect();
+
+    let rate = std::iter::once(sample_rate)
+        .chain(default_rate)
+        .chain(STANDARD_RATES.iter().copied().filter(|r| *r >= sample_rate))
+        .chain(STANDARD_RATES.iter().rev().copied())
+        .find(|r| ranges.iter().any(|range| range.contains(r)))
+        .unwrap_or(*ranges[0].end());
+
+    Some((rate, device_channels))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cpal::SampleFormat;
+    use cpal::SampleRate;
+    use cpal::SupportedBufferSize;
+
+    fn config(channels: u16, min: u32, max: u32) -> SupportedStreamConfigRange {
+        SupportedStreamConfigRange::new(
+            channels,
+            SampleRate(min),
+            SampleRate(max),
+            SupportedBufferSize::Unknown,
+            SampleFormat::F32,
+        )
+    }
+
+    #[test]
+    fn select_channels() {
+        let configs = || vec![config(2, 8000, 96000), config(4, 8000, 96000)];
+        assert_eq!(select_config(configs(), None, 48000, 4), Some((48000, 4)));
+        assert_eq!(select_config(configs(), None, 48000, 1), Some((48000, 2)));
+        assert_eq!(select_config(configs(), None, 48000, 3), Some((48000, 4)));
+        assert_eq!(select_config(configs(), None, 48000, 6), Some((48000, 4)));
+
+        let configs = vec![config(1, 8000, 96000), config(8, 8000, 96000)];
+        assert_eq!(select_config(configs, None, 48000, 2), Some((48000, 8)));
+
+        assert_eq!(select_config(vec![], None, 48000, 2), None);
+    }
+
+    #[test]
+    fn select_rate() {
+        let configs = || vec![config(2, 44100, 48000)];
+        // requested rate
+        assert_eq!(
+            select_config(configs(), Some(44100), 48000, 2),
+            Some((48000, 2))
+        );
+        // default rate of the device
+        assert_eq!(
+            select_config(configs(), Some(44100), 8000, 2),
+            Some((44100, 2))
+        );
+        // next higher standard rate
+        assert_eq!(select_config(configs(), None, 30000, 2), Some((44100, 2)));
+        // highest standard rate below
+        assert_eq!(select_config(configs(), None, 50000, 2), Some((48000, 2)));
+        // maximum rate of the device
+        let configs = vec![config(2, 10000, 12000)];
+        assert_eq!(select_config(configs, None, 8000, 2), Some((12000, 2)));
+    }
+}
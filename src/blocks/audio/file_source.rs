@@ -7,6 +7,8 @@ use std::io::BufReader;
 use crate::prelude::*;
 
 /// Read an audio file and output its samples.
+///
+/// Decodes WAV, FLAC, MP3, and Ogg Vorbis files to interleaved samples in `[-1, 1]`.
 #[derive(Block)]
 pub struct FileSource<O = DefaultCpuWriter<f32>>
 where
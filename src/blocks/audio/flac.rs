@@ -0,0 +1,271 @@
+//! Minimal FLAC encoder
+//!
+//! Encodes independent channels with fixed predictors and Rice-coded residuals.
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+
+/// Samples per channel in each frame
+pub(super) const BLOCK_SIZE: usize = 4096;
+const MAX_RICE_PARAM: u32 = 14;
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u64,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            acc: 0,
+            bits: 0,
+        }
+    }
+
+    fn write(&mut self, value: u64, bits: u32) {
+        debug_assert!(bits <= 32);
+        if bits == 0 {
+            return;
+        }
+        self.acc = (self.acc << bits) | (value & ((1 << bits) - 1));
+        self.bits += bits;
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.bytes.push((self.acc >> self.bits) as u8);
+        }
+    }
+
+    fn write_signed(&mut self, value: i64, bits: u32) {
+        self.write(value as u64, bits);
+    }
+
+    fn write_unary(&mut self, mut zeros: u64) {
+        while zeros >= 32 {
+            self.write(0, 32);
+            zeros -= 32;
+        }
+        self.write(1, zeros as u32 + 1);
+    }
+
+    fn align(&mut self) {
+        if self.bits > 0 {
+            self.write(0, 8 - self.bits);
+        }
+    }
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |mut crc, b| {
+        crc ^= b;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |mut crc, b| {
+        crc ^= (*b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// Frame number in the UTF-8-like variable length coding of FLAC
+fn write_utf8(w: &mut BitWriter, v: u64) {
+    if v < 0x80 {
+        w.write(v, 8);
+        return;
+    }
+    let mut n = 2;
+    while v >= 1 << (5 * n + 1) {
+        n += 1;
+    }
+    w.write(((0xff00 >> n) as u64 & 0xff) | (v >> (6 * (n - 1))), 8);
+    for i in (0..n - 1).rev() {
+        w.write(0x80 | ((v >> (6 * i)) & 0x3f), 8);
+    }
+}
+
+fn fixed_residual(samples: &[i64], order: usize) -> impl Iterator<Item = i64> + '_ {
+    (order..samples.len()).map(move |i| {
+        let s = |d: usize| samples[i - d];
+        let prediction = match order {
+            0 => 0,
+            1 => s(1),
+            2 => 2 * s(1) - s(2),
+            3 => 3 * s(1) - 3 * s(2) + s(3),
+            _ => 4 * s(1) - 6 * s(2) + 4 * s(3) - s(4),
+        };
+        samples[i] - prediction
+    })
+}
+
+fn zigzag(v: i64) -> u64 {
+    ((v << 1) ^ (v >> 63)) as u64
+}
+
+/// Rice parameter and number of bits for the residual
+fn rice_param(residual: &[u64]) -> (u32, u64) {
+    (0..=MAX_RICE_PARAM)
+        .map(|k| {
+            let bits = residual.iter().map(|u| (u >> k) + 1 + k as u64).sum();
+            (k, bits)
+        })
+        .min_by_key(|(_, bits)| *bits)
+        .unwrap()
+}
+
+fn write_subframe(w: &mut BitWriter, samples: &[i64], bps: u32) {
+    if samples.iter().all(|s| *s == samples[0]) {
+        w.write(0, 8);
+        w.write_signed(samples[0], bps);
+        return;
+    }
+
+    let verbatim = samples.len() as u64 * bps as u64;
+    let best = (0..=4usize.min(samples.len() - 1))
+        .map(|order| {
+            let residual: Vec<u64> = fixed_residual(samples, order).map(zigzag).collect();
+            let (k, bits) = rice_param(&residual);
+            let total = order as u64 * bps as u64 + 6 + bits;
+            (order, k, residual, total)
+        })
+        .min_by_key(|(.., total)| *total)
+        .unwrap();
+
+    let (order, k, residual, total) = best;
+    if total >= verbatim {
+        w.write(0b0000_0010, 8);
+        for s in samples {
+            w.write_signed(*s, bps);
+        }
+        return;
+    }
+
+    w.write(0b0001_0000 | (order as u64) << 1, 8);
+    for s in &samples[..order] {
+        w.write_signed(*s, bps);
+    }
+    // Rice coding with 4-bit parameters, a single partition
+    w.write(0, 2);
+    w.write(0, 4);
+    w.write(k as u64, 4);
+    for u in residual {
+        w.write_unary(u >> k);
+        w.write(u, k);
+    }
+}
+
+/// FLAC encoder writing to a seekable stream
+pub(super) struct FlacEncoder<W: Write + Seek> {
+    writer: W,
+    sample_rate: u32,
+    channels: u16,
+    bits_per_sample: u8,
+    frame: u64,
+    total: u64,
+}
+
+impl<W: Write + Seek> FlacEncoder<W> {
+    pub fn new(
+        mut writer: W,
+        sample_rate: u32,
+        channels: u16,
+        bits_per_sample: u8,
+    ) -> std::io::Result<Self> {
+        writer.write_all(b"fLaC")?;
+        let mut s = Self {
+            writer,
+            sample_rate,
+            channels,
+            bits_per_sample,
+            frame: 0,
+            total: 0,
+        };
+        s.write_stream_info()?;
+        Ok(s)
+    }
+
+    fn write_stream_info(&mut self) -> std::io::Result<()> {
+        let mut w = BitWriter::new();
+        // last metadata block, STREAMINFO, 34 bytes
+        w.write(1, 1);
+        w.write(0, 7);
+        w.write(34, 24);
+        w.write(BLOCK_SIZE as u64, 16);
+        w.write(BLOCK_SIZE as u64, 16);
+        // unknown frame sizes
+        w.write(0, 24);
+        w.write(0, 24);
+        w.write(self.sample_rate as u64, 20);
+        w.write(self.channels as u64 - 1, 3);
+        w.write(self.bits_per_sample as u64 - 1, 5);
+        w.write(self.total >> 32, 4);
+        w.write(self.total & 0xffff_ffff, 32);
+        // MD5 not computed
+        w.bytes.extend_from_slice(&[0; 16]);
+        self.writer.write_all(&w.bytes)
+    }
+
+    /// Encode a frame of interleaved samples (at most [`BLOCK_SIZE`] per channel)
+    pub fn write_frame(&mut self, samples: &[i32]) -> std::io::Result<()> {
+        let channels = self.channels as usize;
+        let n = samples.len() / channels;
+        debug_assert!(n > 0 && n <= BLOCK_SIZE);
+        debug_assert_eq!(samples.len() % channels, 0);
+
+        let mut w = BitWriter::new();
+        // sync code, fixed block size
+        w.write(0xfff8, 16);
+        // block size in 16 bits at the end of the header, sample rate from STREAMINFO
+        w.write(0b0111, 4);
+        w.write(0b0000, 4);
+        // independent channels, sample size from STREAMINFO
+        w.write(channels as u64 - 1, 4);
+        w.write(0, 3);
+        w.write(0, 1);
+        write_utf8(&mut w, self.frame);
+        w.write(n as u64 - 1, 16);
+        let crc = crc8(&w.bytes);
+        w.write(crc as u64, 8);
+
+        let mut channel = vec![0i64; n];
+        for c in 0..channels {
+            for (i, s) in channel.iter_mut().enumerate() {
+                *s = samples[i * channels + c] as i64;
+            }
+            write_subframe(&mut w, &channel, self.bits_per_sample as u32);
+        }
+        w.align();
+        let crc = crc16(&w.bytes);
+        w.write(crc as u64, 16);
+
+        self.writer.write_all(&w.bytes)?;
+        self.frame += 1;
+        self.total += n as u64;
+        Ok(())
+    }
+
+    /// Update the number of samples in the header and flush the stream
+    pub fn finish(&mut self) -> std::io::Result<()> {
+        let pos = self.writer.stream_position()?;
+        self.writer.seek(SeekFrom::Start(4))?;
+        self.write_stream_info()?;
+        self.writer.seek(SeekFrom::Start(pos))?;
+        self.writer.flush()
+    }
+}
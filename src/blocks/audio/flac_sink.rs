@@ -0,0 +1,117 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use super::flac::BLOCK_SIZE;
+use super::flac::FlacEncoder;
+use crate::prelude::*;
+
+/// Convert a sample in `[-1, 1]` to a signed integer with `bits` bits.
+///
+/// Samples outside of the range are clipped.
+pub(super) fn f32_to_int(v: f32, bits: u8) -> i32 {
+    let max = ((1i64 << (bits - 1)) - 1) as f32;
+    (v * max).round().clamp(-max - 1.0, max) as i32
+}
+
+/// Write samples to a FLAC file.
+///
+/// Lossless compression of interleaved samples, typically about half the size of a WAV file.
+/// Samples are expected in `[-1, 1]`, samples outside of the range are clipped.
+///
+/// # Inputs
+///
+/// `input`: Interleaved samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::audio::FlacSink;
+///
+/// let snk: FlacSink = FlacSink::new("/tmp/output.flac", 48_000, 1, 16)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Block)]
+pub struct FlacSink<I = DefaultCpuReader<f32>>
+where
+    I: CpuBufferReader<Item = f32>,
+{
+    #[input]
+    input: I,
+    encoder: FlacEncoder<BufWriter<File>>,
+    channels: usize,
+    bits_per_sample: u8,
+    buffer: Vec<i32>,
+}
+
+impl<I> FlacSink<I>
+where
+    I: CpuBufferReader<Item = f32>,
+{
+    /// Create FLAC Sink block
+    ///
+    /// Supports 1 to 8 channels with 16 or 24 bits per sample.
+    pub fn new<P: AsRef<Path>>(
+        file_name: P,
+        sample_rate: u32,
+        channels: u16,
+        bits_per_sample: u8,
+    ) -> Result<Self> {
+        if !(1..=8).contains(&channels)
+            || !matches!(bits_per_sample, 16 | 24)
+            || sample_rate == 0
+            || sample_rate >= 1 << 20
+        {
+            return Err(Error::InvalidParameter.into());
+        }
+        let file = BufWriter::new(File::create(file_name)?);
+        Ok(Self {
+            input: I::default(),
+            encoder: FlacEncoder::new(file, sample_rate, channels, bits_per_sample)?,
+            channels: channels as usize,
+            bits_per_sample,
+            buffer: Vec::with_capacity(BLOCK_SIZE * channels as usize),
+        })
+    }
+}
+
+#[doc(hidden)]
+impl<I> Kernel for FlacSink<I>
+where
+    I: CpuBufferReader<Item = f32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = self.input.slice();
+        let items = i.len();
+        let frame = BLOCK_SIZE * self.channels;
+
+        for v in i.iter() {
+            self.buffer.push(f32_to_int(*v, self.bits_per_sample));
+            if self.buffer.len() == frame {
+                self.encoder.write_frame(&self.buffer)?;
+                self.buffer.clear();
+            }
+        }
+
+        if self.input.finished() {
+            io.finished = true;
+        }
+
+        self.input.consume(items);
+        Ok(())
+    }
+
+    async fn deinit(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        // write the remaining samples as a shorter, final frame
+        let n = self.buffer.len() - self.buffer.len() % self.channels;
+        if n > 0 {
+            self.encoder.write_frame(&self.buffer[..n])?;
+        }
+        self.encoder.finish()?;
+        Ok(())
+    }
+}
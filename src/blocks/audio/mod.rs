@@ -4,15 +4,51 @@ mod audio_sink;
 #[cfg(feature = "audio")]
 pub use audio_sink::AudioSink;
 #[cfg(feature = "audio")]
+pub use audio_sink::AudioSinkBuilder;
+#[cfg(feature = "audio")]
 mod audio_source;
 #[cfg(feature = "audio")]
 pub use audio_source::AudioSource;
+#[cfg(feature = "audio")]
+pub use audio_source::AudioSourceBuilder;
+#[cfg(feature = "codec2")]
+mod codec2_decoder;
+#[cfg(feature = "codec2")]
+pub use codec2_decoder::Codec2Decoder;
+#[cfg(feature = "codec2")]
+mod codec2_encoder;
+#[cfg(feature = "codec2")]
+pub use codec2_encoder::Codec2Encoder;
+#[cfg(feature = "codec2")]
+pub use codec2::Codec2Mode;
+#[cfg(feature = "audio")]
+mod device;
+#[cfg(feature = "audio")]
+mod resampler;
 
 #[cfg(all(not(target_arch = "wasm32"), feature = "audio"))]
 mod file_source;
 #[cfg(all(not(target_arch = "wasm32"), feature = "audio"))]
 pub use file_source::FileSource;
 
+#[cfg(all(not(target_arch = "wasm32"), feature = "audio"))]
+mod flac;
+#[cfg(all(not(target_arch = "wasm32"), feature = "audio"))]
+mod flac_sink;
+#[cfg(all(not(target_arch = "wasm32"), feature = "audio"))]
+pub use flac_sink::FlacSink;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "opus"))]
+mod opus;
+#[cfg(all(not(target_arch = "wasm32"), feature = "opus"))]
+mod opus_sink;
+#[cfg(all(not(target_arch = "wasm32"), feature = "opus"))]
+pub use opus_sink::OpusSink;
+#[cfg(all(not(target_arch = "wasm32"), feature = "opus"))]
+mod opus_source;
+#[cfg(all(not(target_arch = "wasm32"), feature = "opus"))]
+pub use opus_source::OpusSource;
+
 #[cfg(all(not(target_arch = "wasm32"), feature = "audio"))]
 mod wav_sink;
 #[cfg(all(not(target_arch = "wasm32"), feature = "audio"))]
@@ -0,0 +1,290 @@
+//! Ogg Opus encapsulation (RFC 7845)
+//!
+//! Mono and stereo streams with one Opus packet per 20 ms frame.
+use anyhow::bail;
+use audiopus::Application;
+use audiopus::Bitrate;
+use audiopus::Channels;
+use audiopus::SampleRate;
+use audiopus::coder::Decoder;
+use audiopus::coder::Encoder;
+use ogg::PacketReader;
+use ogg::PacketWriteEndInfo;
+use ogg::PacketWriter;
+use std::io::Read;
+use std::io::Seek;
+use std::io::Write;
+
+use crate::prelude::*;
+
+/// Granule positions count samples at 48 kHz, independent of the coded rate.
+const GRANULE_RATE: u32 = 48_000;
+/// Maximum duration of an Opus packet, 120 ms at 48 kHz.
+const MAX_FRAME_SIZE: usize = 5760;
+/// Maximum size of an encoded packet, as recommended by the Opus documentation.
+const MAX_PACKET_BYTES: usize = 4000;
+/// Logical stream of the Ogg file
+const SERIAL: u32 = 0x4655_5452;
+
+const HEAD_MAGIC: &[u8; 8] = b"OpusHead";
+const TAGS_MAGIC: &[u8; 8] = b"OpusTags";
+const VENDOR: &[u8] = b"FutureSDR";
+
+fn sample_rate(rate: u32) -> Option<SampleRate> {
+    <SampleRate as audiopus::TryFrom<i32>>::try_from(rate as i32).ok()
+}
+
+fn channels(channels: u16) -> Option<Channels> {
+    match channels {
+        1 => Some(Channels::Mono),
+        2 => Some(Channels::Stereo),
+        _ => None,
+    }
+}
+
+/// Identification header
+struct Head {
+    channels: u8,
+    /// Samples at 48 kHz to discard at the beginning of the stream
+    pre_skip: u16,
+    /// Sample rate of the original input
+    sample_rate: u32,
+}
+
+impl Head {
+    fn encode(&self) -> Vec<u8> {
+        let mut v = HEAD_MAGIC.to_vec();
+        v.push(1);
+        v.push(self.channels);
+        v.extend_from_slice(&self.pre_skip.to_le_bytes());
+        v.extend_from_slice(&self.sample_rate.to_le_bytes());
+        // output gain
+        v.extend_from_slice(&0i16.to_le_bytes());
+        // channel mapping family for mono and stereo
+        v.push(0);
+        v
+    }
+
+    fn decode(data: &[u8]) -> Result<Self> {
+        if data.len() < 19 || &data[0..8] != HEAD_MAGIC {
+            bail!("no Opus identification header");
+        }
+        if data[8] >> 4 != 0 {
+            bail!("unsupported Ogg Opus version {}", data[8]);
+        }
+        let channels = data[9];
+        if data[18] != 0 || !(1..=2).contains(&channels) {
+            bail!("unsupported Ogg Opus channel mapping");
+        }
+        Ok(Self {
+            channels,
+            pre_skip: u16::from_le_bytes([data[10], data[11]]),
+            sample_rate: u32::from_le_bytes(data[12..16].try_into().unwrap()),
+        })
+    }
+}
+
+/// Comment header without user comments
+fn tags() -> Vec<u8> {
+    let mut v = TAGS_MAGIC.to_vec();
+    v.extend_from_slice(&(VENDOR.len() as u32).to_le_bytes());
+    v.extend_from_slice(VENDOR);
+    v.extend_from_slice(&0u32.to_le_bytes());
+    v
+}
+
+/// Encode interleaved samples to an Ogg Opus stream
+pub(super) struct OpusWriter<W: Write> {
+    writer: PacketWriter<W>,
+    encoder: Encoder,
+    channels: usize,
+    frame_size: usize,
+    granule_factor: u64,
+    lookahead: u64,
+    buffer: Vec<f32>,
+    received: u64,
+    encoded: u64,
+    pending: Option<(Vec<u8>, u64)>,
+}
+
+impl<W: Write> OpusWriter<W> {
+    pub fn new(writer: W, rate: u32, n_channels: u16, bitrate: u32) -> Result<Self> {
+        let (Some(opus_rate), Some(opus_channels)) = (sample_rate(rate), channels(n_channels))
+        else {
+            return Err(Error::InvalidParameter.into());
+        };
+        if !(6_000..=510_000).contains(&bitrate) {
+            return Err(Error::InvalidParameter.into());
+        }
+
+        let mut encoder = Encoder::new(opus_rate, opus_channels, Application::Audio)?;
+        encoder.set_bitrate(Bitrate::BitsPerSecond(bitrate as i32))?;
+        let granule_factor = (GRANULE_RATE / rate) as u64;
+        let lookahead = encoder.lookahead()? as u64;
+
+        let mut writer = PacketWriter::new(writer);
+        let head = Head {
+            channels: n_channels as u8,
+            pre_skip: (lookahead * granule_factor) as u16,
+            sample_rate: rate,
+        };
+        writer.write_packet(
+            head.encode().into_boxed_slice(),
+            SERIAL,
+            PacketWriteEndInfo::EndPage,
+            0,
+        )?;
+        writer.write_packet(
+            tags().into_boxed_slice(),
+            SERIAL,
+            PacketWriteEndInfo::EndPage,
+            0,
+        )?;
+
+        let frame_size = (rate / 50) as usize;
+        Ok(Self {
+            writer,
+            encoder,
+            channels: n_channels as usize,
+            frame_size,
+            granule_factor,
+            lookahead,
+            buffer: Vec::with_capacity(frame_size * n_channels as usize),
+            received: 0,
+            encoded: 0,
+            pending: None,
+        })
+    }
+
+    /// Encode the buffered frame, writing the previous packet.
+    ///
+    /// The last packet is kept back, since it has to end the stream.
+    fn encode_frame(&mut self) -> Result<()> {
+        let mut packet = vec![0; MAX_PACKET_BYTES];
+        let n = self.encoder.encode_float(&self.buffer, &mut packet)?;
+        packet.truncate(n);
+        self.buffer.clear();
+        self.encoded += self.frame_size as u64;
+
+        let granule = self.encoded * self.granule_factor;
+        if let Some((p, g)) = self.pending.replace((packet, granule)) {
+            self.writer.write_packet(
+                p.into_boxed_slice(),
+                SERIAL,
+                PacketWriteEndInfo::NormalPacket,
+                g,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Add interleaved samples
+    pub fn write(&mut self, samples: &[f32]) -> Result<()> {
+        let frame = self.frame_size * self.channels;
+        for v in samples {
+            self.buffer.push(*v);
+            if self.buffer.len() == frame {
+                self.encode_frame()?;
+            }
+        }
+        self.received += samples.len() as u64;
+        Ok(())
+    }
+
+    /// Flush the encoder and end the stream
+    pub fn finish(&mut self) -> Result<()> {
+        let received = self.received / self.channels as u64;
+        let frame = self.frame_size * self.channels;
+        // pad with silence, until the samples in the lookahead of the encoder are out
+        while self.pending.is_none() || self.encoded < received + self.lookahead {
+            self.buffer.resize(frame, 0.0);
+            self.encode_frame()?;
+        }
+        // the granule position of the last page marks the end of the input
+        let (p, _) = self.pending.take().unwrap();
+        let granule = (self.lookahead + received) * self.granule_factor;
+        self.writer.write_packet(
+            p.into_boxed_slice(),
+            SERIAL,
+            PacketWriteEndInfo::EndStream,
+            granule,
+        )?;
+        self.writer.inner_mut().flush()?;
+        Ok(())
+    }
+}
+
+/// Decode an Ogg Opus stream to interleaved samples
+pub(super) struct OpusReader<R: Read + Seek> {
+    reader: PacketReader<R>,
+    decoder: Decoder,
+    channels: u16,
+    sample_rate: u32,
+    granule_factor: u64,
+    pre_skip: u64,
+    decoded: u64,
+    pcm: Vec<f32>,
+}
+
+impl<R: Read + Seek> OpusReader<R> {
+    pub fn new(reader: R) -> Result<Self> {
+        let mut reader = PacketReader::new(reader);
+        let head = Head::decode(&reader.read_packet_expected()?.data)?;
+        let tags = reader.read_packet_expected()?;
+        if tags.data.len() < 8 || &tags.data[0..8] != TAGS_MAGIC {
+            bail!("no Opus comment header");
+        }
+
+        // decode at the rate of the input, if supported
+        let rate = sample_rate(head.sample_rate).unwrap_or(SampleRate::Hz48000);
+        let granule_factor = (GRANULE_RATE / rate as u32) as u64;
+        let decoder = Decoder::new(rate, channels(head.channels as u16).unwrap())?;
+
+        Ok(Self {
+            reader,
+            decoder,
+            channels: head.channels as u16,
+            sample_rate: rate as u32,
+            granule_factor,
+            pre_skip: head.pre_skip as u64 / granule_factor,
+            decoded: 0,
+            pcm: vec![0.0; MAX_FRAME_SIZE * head.channels as usize],
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Decode the next packet, appending samples to `out`
+    ///
+    /// Returns `false` at the end of the stream.
+    pub fn read(&mut self, out: &mut Vec<f32>) -> Result<bool> {
+        let Some(packet) = self.reader.read_packet()? else {
+            return Ok(false);
+        };
+        let n = self.decoder.decode_float(
+            Some(packet.data.as_slice()),
+            self.pcm.as_mut_slice(),
+            false,
+        )? as u64;
+
+        // skip the encoder delay and the padding of the last frame
+        let start = self.decoded.max(self.pre_skip);
+        let mut end = self.decoded + n;
+        if packet.last_in_stream() {
+            end = end.min(packet.absgp_page() / self.granule_factor);
+        }
+        if end > start {
+            let c = self.channels as usize;
+            let offset = (start - self.decoded) as usize;
+            out.extend_from_slice(&self.pcm[offset * c..(offset + (end - start) as usize) * c]);
+        }
+        self.decoded += n;
+        Ok(true)
+    }
+}
@@ -0,0 +1,84 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use super::opus::OpusWriter;
+use crate::prelude::*;
+
+/// Write samples to an Ogg Opus file.
+///
+/// Lossy compression for compact recordings, e.g., about 7 MB per hour of speech at 16 kbit/s.
+/// Samples are encoded in frames of 20 ms. The file is completed when the block terminates.
+///
+/// Requires the `opus` feature, which links the native libopus library.
+///
+/// # Inputs
+///
+/// `input`: Interleaved samples in `[-1, 1]`
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::audio::OpusSink;
+///
+/// let snk: OpusSink = OpusSink::new("/tmp/output.opus", 16_000, 1, 16_000)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Block)]
+pub struct OpusSink<I = DefaultCpuReader<f32>>
+where
+    I: CpuBufferReader<Item = f32>,
+{
+    #[input]
+    input: I,
+    writer: OpusWriter<BufWriter<File>>,
+}
+
+impl<I> OpusSink<I>
+where
+    I: CpuBufferReader<Item = f32>,
+{
+    /// Create Opus Sink block
+    ///
+    /// Supports one or two channels, the sample rates of Opus (8, 12, 16, 24, or 48 kHz), and
+    /// bitrates from 6 to 510 kbit/s.
+    pub fn new<P: AsRef<Path>>(
+        file_name: P,
+        sample_rate: u32,
+        channels: u16,
+        bitrate: u32,
+    ) -> Result<Self> {
+        let file = BufWriter::new(File::create(file_name)?);
+        Ok(Self {
+            input: I::default(),
+            writer: OpusWriter::new(file, sample_rate, channels, bitrate)?,
+        })
+    }
+}
+
+#[doc(hidden)]
+impl<I> Kernel for OpusSink<I>
+where
+    I: CpuBufferReader<Item = f32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = self.input.slice();
+        let items = i.len();
+        self.writer.write(i)?;
+
+        if self.input.finished() {
+            io.finished = true;
+        }
+
+        self.input.consume(items);
+        Ok(())
+    }
+
+    async fn deinit(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        self.writer.finish()
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use super::opus::OpusReader;
+use crate::prelude::*;
+
+/// Read an Ogg Opus file and output its samples.
+///
+/// Decodes mono and stereo files to interleaved samples in `[-1, 1]`. Samples are output at the
+/// original sample rate of the recording, if Opus supports it, and 48 kHz otherwise.
+///
+/// Requires the `opus` feature, which links the native libopus library.
+///
+/// # Outputs
+///
+/// `output`: Interleaved samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::audio::OpusSource;
+///
+/// let src: OpusSource = OpusSource::new("/tmp/input.opus")?;
+/// println!("{} Hz, {} channels", src.sample_rate(), src.channels());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Block)]
+pub struct OpusSource<O = DefaultCpuWriter<f32>>
+where
+    O: CpuBufferWriter<Item = f32>,
+{
+    #[output]
+    output: O,
+    reader: OpusReader<BufReader<File>>,
+    buffer: Vec<f32>,
+    offset: usize,
+}
+
+impl<O> OpusSource<O>
+where
+    O: CpuBufferWriter<Item = f32>,
+{
+    /// Create Opus Source block
+    pub fn new<P: AsRef<Path>>(file_name: P) -> Result<Self> {
+        let file = BufReader::new(File::open(file_name)?);
+        Ok(Self {
+            output: O::default(),
+            reader: OpusReader::new(file)?,
+            buffer: Vec::new(),
+            offset: 0,
+        })
+    }
+    /// Get sample rate
+    pub fn sample_rate(&self) -> u32 {
+        self.reader.sample_rate()
+    }
+    /// Get number of channels
+    pub fn channels(&self) -> u16 {
+        self.reader.channels()
+    }
+}
+
+#[doc(hidden)]
+impl<O> Kernel for OpusSource<O>
+where
+    O: CpuBufferWriter<Item = f32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        if self.offset == self.buffer.len() {
+            self.buffer.clear();
+            self.offset = 0;
+            if !self.reader.read(&mut self.buffer)? {
+                io.finished = true;
+                return Ok(());
+            }
+        }
+
+        let out = self.output.slice();
+        let n = std::cmp::min(out.len(), self.buffer.len() - self.offset);
+        out[..n].copy_from_slice(&self.buffer[self.offset..self.offset + n]);
+        self.offset += n;
+        self.output.produce(n);
+
+        // continue with the next packet, if this one is done
+        io.call_again = n > 0 || self.offset == self.buffer.len();
+        Ok(())
+    }
+}
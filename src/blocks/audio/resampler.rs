@@ -0,0 +1,172 @@
+use futuredsp::MmseResampler;
+use futuredsp::prelude::*;
+
+/// Maximum relative rate correction of the drift compensation
+const MAX_CORRECTION: f32 = 1e-3;
+/// Gain of the drift compensation, i.e., correction per relative fill level error
+const DRIFT_GAIN: f32 = 1e-3;
+/// Averaging rate of the fill level
+const DRIFT_ALPHA: f32 = 0.05;
+
+/// Map interleaved frames from `from` to `to` channels.
+///
+/// Mono is duplicated to all channels. Otherwise, surplus channels are dropped and missing
+/// channels are filled with zeros.
+pub(super) fn remap(input: &[f32], from: usize, to: usize, output: &mut Vec<f32>) {
+    if from == to {
+        output.extend_from_slice(input);
+        return;
+    }
+    for frame in input.chunks_exact(from) {
+        if from == 1 {
+            output.extend(std::iter::repeat_n(frame[0], to));
+        } else {
+            output.extend((0..to).map(|c| frame.get(c).copied().unwrap_or(0.0)));
+        }
+    }
+}
+
+/// Resampler for interleaved multi-channel audio.
+///
+/// Uses one [`MmseResampler`] per channel. Input that cannot be processed yet, since the
+/// interpolator needs future samples, is kept for the next call.
+pub(super) struct AudioResampler {
+    ratio: f32,
+    resamplers: Vec<MmseResampler<f32>>,
+    pending: Vec<Vec<f32>>,
+    out: Vec<f32>,
+}
+
+impl AudioResampler {
+    /// Create resampler with output to input rate `ratio`
+    pub fn new(ratio: f32, channels: usize) -> Self {
+        Self {
+            ratio,
+            resamplers: (0..channels).map(|_| MmseResampler::new(ratio)).collect(),
+            pending: vec![Vec::new(); channels],
+            out: Vec::new(),
+        }
+    }
+
+    /// Apply a relative correction to the nominal ratio
+    pub fn set_correction(&mut self, correction: f32) {
+        let ratio = self.ratio * (1.0 + correction);
+        self.resamplers.iter_mut().for_each(|r| r.set_ratio(ratio));
+    }
+
+    /// Resample interleaved `input`, appending interleaved samples to `output`
+    pub fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        let channels = self.pending.len();
+        for frame in input.chunks_exact(channels) {
+            for (p, v) in self.pending.iter_mut().zip(frame) {
+                p.push(*v);
+            }
+        }
+
+        let max = (self.pending[0].len() as f32 * self.ratio * (1.0 + MAX_CORRECTION)) as usize + 2;
+        self.out.resize(max * channels, 0.0);
+        let mut produced = 0;
+        for (c, (r, p)) in self
+            .resamplers
+            .iter_mut()
+            .zip(&mut self.pending)
+            .enumerate()
+        {
+            let (consumed, n, _) = r.filter(p, &mut self.out[c * max..(c + 1) * max]);
+            p.drain(..consumed);
+            produced = n;
+        }
+
+        output.reserve(produced * channels);
+        for i in 0..produced {
+            output.extend((0..channels).map(|c| self.out[c * max + i]));
+        }
+    }
+}
+
+/// Keep the fill level of the queue between the flowgraph and the sound card at a target.
+///
+/// The sound card and the flowgraph, e.g., an SDR, run on independent clocks. The controller
+/// derives a small correction of the resampling ratio from the averaged fill level, so that the
+/// queue neither runs empty nor grows without bounds.
+pub(super) struct DriftController {
+    target: f32,
+    level: f32,
+}
+
+impl DriftController {
+    /// Create controller for a target fill level in samples
+    pub fn new(target: usize) -> Self {
+        Self {
+            target: target as f32,
+            level: target as f32,
+        }
+    }
+
+    /// Update with the current fill level and get the correction of the resampling ratio
+    pub fn update(&mut self, fill: usize) -> f32 {
+        self.level += DRIFT_ALPHA * (fill as f32 - self.level);
+        let error = (self.level - self.target) / self.target;
+        (-DRIFT_GAIN * error).clamp(-MAX_CORRECTION, MAX_CORRECTION)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn remap_channels() {
+        let mut out = Vec::new();
+        remap(&[1.0, 2.0], 1, 2, &mut out);
+        assert_eq!(out, [1.0, 1.0, 2.0, 2.0]);
+
+        let mut out = Vec::new();
+        remap(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 3, 2, &mut out);
+        assert_eq!(out, [1.0, 2.0, 4.0, 5.0]);
+
+        let mut out = Vec::new();
+        remap(&[1.0, 2.0, 3.0, 4.0], 2, 3, &mut out);
+        assert_eq!(out, [1.0, 2.0, 0.0, 3.0, 4.0, 0.0]);
+
+        let mut out = vec![9.0];
+        remap(&[1.0, 2.0], 2, 2, &mut out);
+        assert_eq!(out, [9.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn resample_interleaved() {
+        let mut r = AudioResampler::new(2.0, 2);
+        let input: Vec<f32> = (0..200).flat_map(|_| [0.5, -0.25]).collect();
+        let mut out = Vec::new();
+        r.process(&input[..100], &mut out);
+        r.process(&input[100..], &mut out);
+
+        assert_eq!(out.len() % 2, 0);
+        let frames = out.len() / 2;
+        assert!(frames > 380 && frames <= 400);
+        for frame in out.chunks_exact(2) {
+            assert!((frame[0] - 0.5).abs() < 1e-3);
+            assert!((frame[1] + 0.25).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn drift_correction() {
+        let mut d = DriftController::new(1000);
+        assert_eq!(d.update(1000), 0.0);
+
+        // queue too full, play faster, i.e., produce fewer samples
+        let c = d.update(2000);
+        assert!(c < 0.0 && c > -MAX_CORRECTION);
+        for _ in 0..100 {
+            d.update(1_000_000);
+        }
+        assert_eq!(d.update(1_000_000), -MAX_CORRECTION);
+
+        // queue too empty, produce more samples
+        let mut d = DriftController::new(1000);
+        let c = d.update(0);
+        assert!(c > 0.0 && c <= MAX_CORRECTION);
+    }
+}
@@ -81,4 +81,9 @@ where
         self.input.consume(items);
         Ok(())
     }
+
+    async fn deinit(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
 }
@@ -3,13 +3,18 @@ use async_net::UdpSocket;
 use std::net::ToSocketAddrs;
 
 use crate::prelude::*;
+use crate::runtime::PmtEncoding;
 
 /// Push [Blobs](crate::runtime::Pmt::Blob) into a UDP socket.
+///
+/// With an encoding (see [`BlobToUdp::with_encoding`]), any [`Pmt`] is encoded and sent as one
+/// datagram.
 #[derive(Block)]
 #[message_inputs(r#in)]
 pub struct BlobToUdp {
     socket: Option<UdpSocket>,
     remote: SocketAddr,
+    encoding: Option<PmtEncoding>,
 }
 
 impl BlobToUdp {
@@ -29,6 +34,22 @@ impl BlobToUdp {
                 .expect("could not resolve socket address")
                 .next()
                 .unwrap(),
+            encoding: None,
+        }
+    }
+
+    /// Create [`BlobToUdp`] block that sends [`Pmts`](Pmt) in the given encoding
+    ///
+    /// ## Parameter
+    /// - `remote`: UDP socket address, e.g., `localhost:2342`
+    /// - `encoding`: wire format of the [`Pmts`](Pmt)
+    pub fn with_encoding<S>(remote: S, encoding: PmtEncoding) -> Self
+    where
+        S: AsRef<str>,
+    {
+        BlobToUdp {
+            encoding: Some(encoding),
+            ..Self::new(remote)
         }
     }
 
@@ -39,6 +60,17 @@ impl BlobToUdp {
         _meta: &mut BlockMeta,
         p: Pmt,
     ) -> Result<Pmt> {
+        let p = match (self.encoding, p) {
+            (_, Pmt::Finished) => Pmt::Finished,
+            (Some(e), p) => match e.encode(&p) {
+                Ok(v) => Pmt::Blob(v),
+                Err(_) => {
+                    warn!("BlobToUdp: cannot encode {:?}", p);
+                    return Ok(Pmt::InvalidValue);
+                }
+            },
+            (None, p) => p,
+        };
         match p {
             Pmt::Blob(v) => match self.socket.as_ref().unwrap().send_to(&v, self.remote).await {
                 Ok(s) => {
@@ -0,0 +1,655 @@
+use crate::prelude::*;
+
+/// Integer sample formats of SDRs and capture files.
+pub trait IntSample: CpuSample + Copy {
+    /// Value that maps to zero (non-zero for unsigned formats)
+    const OFFSET: f32;
+    /// Value that maps to one
+    const FULL_SCALE: f32;
+    /// Convert to float without scaling
+    fn to_f32(self) -> f32;
+    /// Round and saturate a float
+    fn from_f32(v: f32) -> Self;
+    /// Convert interleaved samples to floats, i.e., `(v - OFFSET) * scale`
+    #[doc(hidden)]
+    fn to_floats(input: &[Self], output: &mut [f32], scale: f32) {
+        to_floats_scalar(input, output, scale);
+    }
+    /// Convert floats to interleaved samples, i.e., `from_f32(v * scale + OFFSET)`
+    #[doc(hidden)]
+    fn from_floats(input: &[f32], output: &mut [Self], scale: f32) {
+        from_floats_scalar(input, output, scale);
+    }
+}
+
+fn to_floats_scalar<T: IntSample>(input: &[T], output: &mut [f32], scale: f32) {
+    for (i, o) in input.iter().zip(output.iter_mut()) {
+        *o = (i.to_f32() - T::OFFSET) * scale;
+    }
+}
+
+fn from_floats_scalar<T: IntSample>(input: &[f32], output: &mut [T], scale: f32) {
+    for (i, o) in input.iter().zip(output.iter_mut()) {
+        *o = T::from_f32(i * scale + T::OFFSET);
+    }
+}
+
+macro_rules! impl_int_sample {
+    ($t:ty, $offset:expr, $full_scale:expr) => {
+        impl IntSample for $t {
+            const OFFSET: f32 = $offset;
+            const FULL_SCALE: f32 = $full_scale;
+            #[inline(always)]
+            fn to_f32(self) -> f32 {
+                self as f32
+            }
+            #[inline(always)]
+            fn from_f32(v: f32) -> Self {
+                // float to int casts saturate
+                v.round() as $t
+            }
+            #[cfg(target_arch = "x86_64")]
+            fn to_floats(input: &[Self], output: &mut [f32], scale: f32) {
+                if std::arch::is_x86_feature_detected!("avx2") {
+                    // SAFETY: AVX2 is available
+                    unsafe { avx2::to_floats(input, output, scale) }
+                } else {
+                    to_floats_scalar(input, output, scale);
+                }
+            }
+            #[cfg(target_arch = "x86_64")]
+            fn from_floats(input: &[f32], output: &mut [Self], scale: f32) {
+                if std::arch::is_x86_feature_detected!("avx2") {
+                    // SAFETY: AVX2 is available
+                    unsafe { avx2::from_floats(input, output, scale) }
+                } else {
+                    from_floats_scalar(input, output, scale);
+                }
+            }
+        }
+    };
+}
+
+impl_int_sample!(i8, 0.0, 128.0);
+impl_int_sample!(u8, 127.5, 127.5);
+impl_int_sample!(i16, 0.0, 32768.0);
+
+/// AVX2 kernels for the integer formats.
+///
+/// They process 16 values per iteration and leave the remainder to the scalar loops. Rounding
+/// (half away from zero), saturation, and NaN (to zero) match [`IntSample::from_f32`].
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use std::arch::x86_64::*;
+
+    use super::IntSample;
+    use super::from_floats_scalar;
+    use super::to_floats_scalar;
+
+    /// Integer types with AVX2 widening and narrowing
+    pub(super) trait Lanes: IntSample {
+        const MIN: f32;
+        const MAX: f32;
+        /// Load 16 values, sign or zero extended to two vectors of 8 `i32`
+        unsafe fn load(p: *const Self) -> (__m256i, __m256i);
+        /// Store 16 values, given as packed `i16`
+        unsafe fn store(p: *mut Self, v: __m256i);
+    }
+
+    impl Lanes for u8 {
+        const MIN: f32 = 0.0;
+        const MAX: f32 = 255.0;
+        #[inline(always)]
+        unsafe fn load(p: *const Self) -> (__m256i, __m256i) {
+            unsafe {
+                let v = _mm_loadu_si128(p as *const __m128i);
+                (
+                    _mm256_cvtepu8_epi32(v),
+                    _mm256_cvtepu8_epi32(_mm_srli_si128(v, 8)),
+                )
+            }
+        }
+        #[inline(always)]
+        unsafe fn store(p: *mut Self, v: __m256i) {
+            unsafe {
+                let lo = _mm256_castsi256_si128(v);
+                let hi = _mm256_extracti128_si256(v, 1);
+                _mm_storeu_si128(p as *mut __m128i, _mm_packus_epi16(lo, hi));
+            }
+        }
+    }
+
+    impl Lanes for i8 {
+        const MIN: f32 = -128.0;
+        const MAX: f32 = 127.0;
+        #[inline(always)]
+        unsafe fn load(p: *const Self) -> (__m256i, __m256i) {
+            unsafe {
+                let v = _mm_loadu_si128(p as *const __m128i);
+                (
+                    _mm256_cvtepi8_epi32(v),
+                    _mm256_cvtepi8_epi32(_mm_srli_si128(v, 8)),
+                )
+            }
+        }
+        #[inline(always)]
+        unsafe fn store(p: *mut Self, v: __m256i) {
+            unsafe {
+                let lo = _mm256_castsi256_si128(v);
+                let hi = _mm256_extracti128_si256(v, 1);
+                _mm_storeu_si128(p as *mut __m128i, _mm_packs_epi16(lo, hi));
+            }
+        }
+    }
+
+    impl Lanes for i16 {
+        const MIN: f32 = -32768.0;
+        const MAX: f32 = 32767.0;
+        #[inline(always)]
+        unsafe fn load(p: *const Self) -> (__m256i, __m256i) {
+            unsafe {
+                (
+                    _mm256_cvtepi16_epi32(_mm_loadu_si128(p as *const __m128i)),
+                    _mm256_cvtepi16_epi32(_mm_loadu_si128(p.add(8) as *const __m128i)),
+                )
+            }
+        }
+        #[inline(always)]
+        unsafe fn store(p: *mut Self, v: __m256i) {
+            unsafe { _mm256_storeu_si256(p as *mut __m256i, v) }
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn to_floats<T: Lanes>(input: &[T], output: &mut [f32], scale: f32) {
+        let n = std::cmp::min(input.len(), output.len()) / 16 * 16;
+        let offset = _mm256_set1_ps(T::OFFSET);
+        let scale_v = _mm256_set1_ps(scale);
+        for k in (0..n).step_by(16) {
+            // SAFETY: `k + 16 <= n` for input and output
+            unsafe {
+                let (a, b) = T::load(input.as_ptr().add(k));
+                let a = _mm256_mul_ps(_mm256_sub_ps(_mm256_cvtepi32_ps(a), offset), scale_v);
+                let b = _mm256_mul_ps(_mm256_sub_ps(_mm256_cvtepi32_ps(b), offset), scale_v);
+                _mm256_storeu_ps(output.as_mut_ptr().add(k), a);
+                _mm256_storeu_ps(output.as_mut_ptr().add(k + 8), b);
+            }
+        }
+        to_floats_scalar(&input[n..], &mut output[n..], scale);
+    }
+
+    /// Round half away from zero, saturate, and truncate to `i32`
+    #[target_feature(enable = "avx2")]
+    fn to_ints<T: Lanes>(v: __m256) -> __m256i {
+        let sign = _mm256_set1_ps(-0.0);
+        let t = _mm256_round_ps(v, _MM_FROUND_TO_ZERO | _MM_FROUND_NO_EXC);
+        // the fraction is exact, unlike adding 0.5 before truncating
+        let frac = _mm256_andnot_ps(sign, _mm256_sub_ps(v, t));
+        let up = _mm256_cmp_ps(frac, _mm256_set1_ps(0.5), _CMP_GE_OQ);
+        let one = _mm256_or_ps(_mm256_and_ps(v, sign), _mm256_set1_ps(1.0));
+        let r = _mm256_add_ps(t, _mm256_and_ps(up, one));
+        let r = _mm256_min_ps(
+            _mm256_max_ps(r, _mm256_set1_ps(T::MIN)),
+            _mm256_set1_ps(T::MAX),
+        );
+        // NaN is mapped to the limits above, but casts map it to zero
+        let r = _mm256_and_ps(r, _mm256_cmp_ps(v, v, _CMP_ORD_Q));
+        _mm256_cvttps_epi32(r)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn from_floats<T: Lanes>(input: &[f32], output: &mut [T], scale: f32) {
+        let n = std::cmp::min(input.len(), output.len()) / 16 * 16;
+        let offset = _mm256_set1_ps(T::OFFSET);
+        let scale_v = _mm256_set1_ps(scale);
+        for k in (0..n).step_by(16) {
+            // SAFETY: `k + 16 <= n` for input and output
+            unsafe {
+                let a = _mm256_loadu_ps(input.as_ptr().add(k));
+                let b = _mm256_loadu_ps(input.as_ptr().add(k + 8));
+                let a = to_ints::<T>(_mm256_add_ps(_mm256_mul_ps(a, scale_v), offset));
+                let b = to_ints::<T>(_mm256_add_ps(_mm256_mul_ps(b, scale_v), offset));
+                // packing works per 128-bit lane, restore the order of the 64-bit blocks
+                let v = _mm256_permute4x64_epi64(_mm256_packs_epi32(a, b), 0b11_01_10_00);
+                T::store(output.as_mut_ptr().add(k), v);
+            }
+        }
+        from_floats_scalar(&input[n..], &mut output[n..], scale);
+    }
+}
+
+/// View complex samples as interleaved real and imaginary parts
+fn flatten<T>(s: &[Complex<T>]) -> &[T] {
+    // SAFETY: `Complex` is `repr(C)` with two fields of type `T`
+    unsafe { std::slice::from_raw_parts(s.as_ptr() as *const T, s.len() * 2) }
+}
+
+fn flatten_mut<T>(s: &mut [Complex<T>]) -> &mut [T] {
+    // SAFETY: `Complex` is `repr(C)` with two fields of type `T`
+    unsafe { std::slice::from_raw_parts_mut(s.as_mut_ptr() as *mut T, s.len() * 2) }
+}
+
+/// Conversion of chunks of `IN` input items to `OUT` output items.
+pub trait Conversion: Send + 'static {
+    /// Input type
+    type In: CpuSample;
+    /// Output type
+    type Out: CpuSample;
+    /// Input items per chunk
+    const IN: usize;
+    /// Output items per chunk
+    const OUT: usize;
+    /// Convert `input.len() / IN` chunks into `output`, which has the matching length
+    fn convert(&self, input: &[Self::In], output: &mut [Self::Out]);
+}
+
+/// Interleaved integers (e.g., `u8` of an RTL-SDR or SC16) to [`Complex32`].
+pub struct IntToComplex<T: IntSample> {
+    scale: f32,
+    _type: std::marker::PhantomData<T>,
+}
+
+impl<T: IntSample> IntToComplex<T> {
+    /// Map full scale to one
+    pub fn new() -> Self {
+        Self::with_scale(1.0 / T::FULL_SCALE)
+    }
+    /// Multiply (offset-free) samples with `scale`
+    pub fn with_scale(scale: f32) -> Self {
+        Self {
+            scale,
+            _type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: IntSample> Default for IntToComplex<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: IntSample> Conversion for IntToComplex<T> {
+    type In = T;
+    type Out = Complex32;
+    const IN: usize = 2;
+    const OUT: usize = 1;
+    fn convert(&self, input: &[T], output: &mut [Complex32]) {
+        T::to_floats(input, flatten_mut(output), self.scale);
+    }
+}
+
+/// [`Complex32`] to interleaved integers.
+pub struct ComplexToInt<T: IntSample> {
+    scale: f32,
+    _type: std::marker::PhantomData<T>,
+}
+
+impl<T: IntSample> ComplexToInt<T> {
+    /// Map one to full scale
+    pub fn new() -> Self {
+        Self::with_scale(T::FULL_SCALE)
+    }
+    /// Multiply samples with `scale` before rounding and saturating
+    pub fn with_scale(scale: f32) -> Self {
+        Self {
+            scale,
+            _type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: IntSample> Default for ComplexToInt<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: IntSample> Conversion for ComplexToInt<T> {
+    type In = Complex32;
+    type Out = T;
+    const IN: usize = 1;
+    const OUT: usize = 2;
+    fn convert(&self, input: &[Complex32], output: &mut [T]) {
+        T::from_floats(flatten(input), output, self.scale);
+    }
+}
+
+/// `Complex<T>` of integers to [`Complex32`].
+pub struct ComplexIntToComplex<T: IntSample> {
+    scale: f32,
+    _type: std::marker::PhantomData<T>,
+}
+
+impl<T: IntSample> ComplexIntToComplex<T> {
+    /// Map full scale to one
+    pub fn new() -> Self {
+        Self::with_scale(1.0 / T::FULL_SCALE)
+    }
+    /// Multiply (offset-free) samples with `scale`
+    pub fn with_scale(scale: f32) -> Self {
+        Self {
+            scale,
+            _type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: IntSample> Default for ComplexIntToComplex<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: IntSample> Conversion for ComplexIntToComplex<T> {
+    type In = Complex<T>;
+    type Out = Complex32;
+    const IN: usize = 1;
+    const OUT: usize = 1;
+    fn convert(&self, input: &[Complex<T>], output: &mut [Complex32]) {
+        T::to_floats(flatten(input), flatten_mut(output), self.scale);
+    }
+}
+
+/// [`Complex32`] to `Complex<T>` of integers.
+pub struct ComplexToComplexInt<T: IntSample> {
+    scale: f32,
+    _type: std::marker::PhantomData<T>,
+}
+
+impl<T: IntSample> ComplexToComplexInt<T> {
+    /// Map one to full scale
+    pub fn new() -> Self {
+        Self::with_scale(T::FULL_SCALE)
+    }
+    /// Multiply samples with `scale` before rounding and saturating
+    pub fn with_scale(scale: f32) -> Self {
+        Self {
+            scale,
+            _type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: IntSample> Default for ComplexToComplexInt<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: IntSample> Conversion for ComplexToComplexInt<T> {
+    type In = Complex32;
+    type Out = Complex<T>;
+    const IN: usize = 1;
+    const OUT: usize = 1;
+    fn convert(&self, input: &[Complex32], output: &mut [Complex<T>]) {
+        T::from_floats(flatten(input), flatten_mut(output), self.scale);
+    }
+}
+
+/// Integers to `f32`.
+pub struct IntToFloat<T: IntSample> {
+    scale: f32,
+    _type: std::marker::PhantomData<T>,
+}
+
+impl<T: IntSample> IntToFloat<T> {
+    /// Map full scale to one
+    pub fn new() -> Self {
+        Self::with_scale(1.0 / T::FULL_SCALE)
+    }
+    /// Multiply (offset-free) samples with `scale`
+    pub fn with_scale(scale: f32) -> Self {
+        Self {
+            scale,
+            _type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: IntSample> Default for IntToFloat<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: IntSample> Conversion for IntToFloat<T> {
+    type In = T;
+    type Out = f32;
+    const IN: usize = 1;
+    const OUT: usize = 1;
+    fn convert(&self, input: &[T], output: &mut [f32]) {
+        T::to_floats(input, output, self.scale);
+    }
+}
+
+/// `f32` to integers.
+pub struct FloatToInt<T: IntSample> {
+    scale: f32,
+    _type: std::marker::PhantomData<T>,
+}
+
+impl<T: IntSample> FloatToInt<T> {
+    /// Map one to full scale
+    pub fn new() -> Self {
+        Self::with_scale(T::FULL_SCALE)
+    }
+    /// Multiply samples with `scale` before rounding and saturating
+    pub fn with_scale(scale: f32) -> Self {
+        Self {
+            scale,
+            _type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: IntSample> Default for FloatToInt<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: IntSample> Conversion for FloatToInt<T> {
+    type In = f32;
+    type Out = T;
+    const IN: usize = 1;
+    const OUT: usize = 1;
+    fn convert(&self, input: &[f32], output: &mut [T]) {
+        T::from_floats(input, output, self.scale);
+    }
+}
+
+/// Packed 12-bit IQ samples (three bytes per sample) to [`Complex32`].
+///
+/// Uses the CS12 layout of SoapySDR: `I[7:0]`, `Q[3:0] I[11:8]`, `Q[11:4]`.
+pub struct Packed12ToComplex {
+    scale: f32,
+}
+
+impl Packed12ToComplex {
+    /// Map full scale to one
+    pub fn new() -> Self {
+        Self::with_scale(1.0 / 2048.0)
+    }
+    /// Multiply samples with `scale`
+    pub fn with_scale(scale: f32) -> Self {
+        Self { scale }
+    }
+}
+
+impl Default for Packed12ToComplex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Conversion for Packed12ToComplex {
+    type In = u8;
+    type Out = Complex32;
+    const IN: usize = 3;
+    const OUT: usize = 1;
+    fn convert(&self, input: &[u8], output: &mut [Complex32]) {
+        for (i, o) in input.chunks_exact(3).zip(output.iter_mut()) {
+            // shift into the upper bits of an i16 to sign extend
+            let re = (((i[1] as u16) << 12) | ((i[0] as u16) << 4)) as i16 >> 4;
+            let im = (((i[2] as u16) << 8) | (i[1] as u16 & 0xf0)) as i16 >> 4;
+            *o = Complex32::new(re as f32 * self.scale, im as f32 * self.scale);
+        }
+    }
+}
+
+/// [`Complex32`] to packed 12-bit IQ samples (three bytes per sample).
+///
+/// Uses the CS12 layout of SoapySDR: `I[7:0]`, `Q[3:0] I[11:8]`, `Q[11:4]`.
+pub struct ComplexToPacked12 {
+    scale: f32,
+}
+
+impl ComplexToPacked12 {
+    /// Map one to full scale
+    pub fn new() -> Self {
+        Self::with_scale(2048.0)
+    }
+    /// Multiply samples with `scale` before rounding and saturating
+    pub fn with_scale(scale: f32) -> Self {
+        Self { scale }
+    }
+}
+
+impl Default for ComplexToPacked12 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Conversion for ComplexToPacked12 {
+    type In = Complex32;
+    type Out = u8;
+    const IN: usize = 1;
+    const OUT: usize = 3;
+    fn convert(&self, input: &[Complex32], output: &mut [u8]) {
+        for (i, o) in input.iter().zip(output.chunks_exact_mut(3)) {
+            let re = (i.re * self.scale).round().clamp(-2048.0, 2047.0) as i16 as u16;
+            let im = (i.im * self.scale).round().clamp(-2048.0, 2047.0) as i16 as u16;
+            o[0] = re as u8;
+            o[1] = ((re >> 8) & 0x0f) as u8 | ((im << 4) & 0xf0) as u8;
+            o[2] = (im >> 4) as u8;
+        }
+    }
+}
+
+/// Convert between stream types.
+///
+/// Converts between the integer formats of SDRs and capture files and the float types used in
+/// the flowgraph. The [`Conversion`] determines the types, the ratio of input and output items,
+/// and the scaling. On x86_64, conversions between `i8`, `u8`, `i16` and floats use AVX2, if the
+/// CPU supports it.
+///
+/// | Conversion | Input | Output |
+/// |---|---|---|
+/// | [`IntToComplex`] | interleaved `i8`, `u8`, `i16` | [`Complex32`] |
+/// | [`ComplexToInt`] | [`Complex32`] | interleaved `i8`, `u8`, `i16` |
+/// | [`ComplexIntToComplex`] | `Complex<i8>`, `Complex<u8>`, `Complex<i16>` | [`Complex32`] |
+/// | [`ComplexToComplexInt`] | [`Complex32`] | `Complex<i8>`, `Complex<u8>`, `Complex<i16>` |
+/// | [`IntToFloat`] | `i8`, `u8`, `i16` | `f32` |
+/// | [`FloatToInt`] | `f32` | `i8`, `u8`, `i16` |
+/// | [`Packed12ToComplex`] | packed 12-bit IQ as `u8` | [`Complex32`] |
+/// | [`ComplexToPacked12`] | [`Complex32`] | packed 12-bit IQ as `u8` |
+///
+/// By default, full scale of the integer format maps to one. Unsigned formats are centered
+/// around the middle of their range.
+///
+/// # Inputs
+///
+/// `input`: Input samples
+///
+/// # Outputs
+///
+/// `output`: Converted samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::Convert;
+/// use futuresdr::blocks::FileSource;
+/// use futuresdr::blocks::IntToComplex;
+/// use futuresdr::blocks::NullSink;
+/// use futuresdr::prelude::*;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// // RTL-SDR capture
+/// let src = FileSource::<u8>::new("capture.cu8", false);
+/// let conv: Convert<IntToComplex<u8>> = Convert::new(IntToComplex::new());
+/// let snk = NullSink::<Complex32>::new();
+/// connect!(fg, src > conv > snk);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Block)]
+#[tag_propagation(one_to_one)]
+pub struct Convert<
+    C,
+    I = DefaultCpuReader<<C as Conversion>::In>,
+    O = DefaultCpuWriter<<C as Conversion>::Out>,
+> where
+    C: Conversion,
+    I: CpuBufferReader<Item = C::In>,
+    O: CpuBufferWriter<Item = C::Out>,
+{
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+    conversion: C,
+}
+
+impl<C, I, O> Convert<C, I, O>
+where
+    C: Conversion,
+    I: CpuBufferReader<Item = C::In>,
+    O: CpuBufferWriter<Item = C::Out>,
+{
+    /// Create Convert block
+    pub fn new(conversion: C) -> Self {
+        Self {
+            input: I::default(),
+            output: O::default(),
+            conversion,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<C, I, O> Kernel for Convert<C, I, O>
+where
+    C: Conversion,
+    I: CpuBufferReader<Item = C::In>,
+    O: CpuBufferWriter<Item = C::Out>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, i_tags) = self.input.slice_with_tags();
+        let (o, mut o_tags) = self.output.slice_with_tags();
+        let i_len = i.len();
+
+        let m = std::cmp::min(i.len() / C::IN, o.len() / C::OUT);
+        if m > 0 {
+            self.conversion
+                .convert(&i[..C::IN * m], &mut o[..C::OUT * m]);
+
+            Self::tag_propagation().forward(0, 0, i_tags, C::IN * m, &mut o_tags, C::OUT * m);
+
+            self.input.consume(C::IN * m);
+            self.output.produce(C::OUT * m);
+        }
+
+        if self.input.finished() && (i_len - C::IN * m) < C::IN {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use num_complex::Complex64;
+
+use crate::prelude::*;
+
+/// Remove the DC offset of a complex stream.
+///
+/// Subtracts the mean of the last `length` samples from each sample, i.e., a moving average
+/// high-pass filter. Longer windows result in a narrower notch around DC. This suppresses the
+/// DC spike of direct conversion receivers like the RTL-SDR or HackRF.
+///
+/// # Inputs
+///
+/// `input`: Samples with DC offset
+///
+/// # Outputs
+///
+/// `output`: Samples without DC offset
+///
+/// # Messages
+///
+/// `length`: Set the window length (`Pmt::Usize`), resetting the estimate
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::DcBlocker;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let dc: DcBlocker = DcBlocker::new(1024);
+/// let dc = fg.add_block(dc);
+/// ```
+#[derive(Block)]
+#[tag_propagation(one_to_one)]
+#[message_inputs(length)]
+#[message_input_schema(length, description = "Length", kind = Usize, min = 1, unit = "samples", getter = current_length)]
+pub struct DcBlocker<I = DefaultCpuReader<Complex32>, O = DefaultCpuWriter<Complex32>>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+    history: Vec<Complex32>,
+    index: usize,
+    sum: Complex64,
+}
+
+impl<I, O> DcBlocker<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    /// Create DC blocker with a window of `length` samples
+    pub fn new(length: usize) -> Self {
+        assert!(length > 0, "DcBlocker: length has to be positive");
+        Self {
+            input: I::default(),
+            output: O::default(),
+            history: vec![Complex32::new(0.0, 0.0); length],
+            index: 0,
+            sum: Complex64::new(0.0, 0.0),
+        }
+    }
+
+    fn current_length(&self) -> usize {
+        self.history.len()
+    }
+
+    async fn length(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        length: Option<usize>,
+    ) -> Result<Pmt> {
+        match length {
+            Some(0) => return Ok(Pmt::InvalidValue),
+            Some(l) => {
+                self.history = vec![Complex32::new(0.0, 0.0); l];
+                self.index = 0;
+                self.sum = Complex64::new(0.0, 0.0);
+            }
+            None => {}
+        }
+        Ok(Pmt::Usize(self.history.len()))
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for DcBlocker<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, i_tags) = self.input.slice_with_tags();
+        let (o, mut o_tags) = self.output.slice_with_tags();
+        let i_len = i.len();
+
+        let m = std::cmp::min(i_len, o.len());
+        if m > 0 {
+            let len = self.history.len() as f64;
+            for (v, r) in i.iter().zip(o.iter_mut()) {
+                let old = std::mem::replace(&mut self.history[self.index], *v);
+                self.sum +=
+                    Complex64::new(v.re as f64 - old.re as f64, v.im as f64 - old.im as f64);
+                self.index = (self.index + 1) % self.history.len();
+                *r = v - Complex32::new((self.sum.re / len) as f32, (self.sum.im / len) as f32);
+            }
+
+            Self::tag_propagation().forward(0, 0, i_tags, m, &mut o_tags, m);
+
+            self.input.consume(m);
+            self.output.produce(m);
+        }
+
+        if self.input.finished() && m == i_len {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -27,6 +27,8 @@ use std::sync::Arc;
 /// ```
 #[derive(Block)]
 #[message_inputs(fft_size)]
+#[message_input_schema(fft_size, description = "FFT size", kind = Usize, getter = current_fft_size)]
+#[tag_propagation(one_to_one)]
 pub struct Fft<I = DefaultCpuReader<Complex32>, O = DefaultCpuWriter<Complex32>>
 where
     I: CpuBufferReader<Item = Complex32>,
@@ -100,6 +102,10 @@ where
         }
     }
 
+    fn current_fft_size(&self) -> usize {
+        self.len
+    }
+
     /// Handle incoming messages to change FFT size
     async fn fft_size(
         &mut self,
@@ -151,10 +157,7 @@ where
         let m = cmp::min(m, self.len * BUFF_FFTS);
 
         if m > 0 {
-            in_tags
-                .iter()
-                .filter(|t| t.index < m)
-                .for_each(|t| out_tags.add_tag(t.index, t.tag.clone()));
+            Self::tag_propagation().forward(0, 0, in_tags, m, &mut out_tags, m);
 
             if matches!(self.direction, FftDirection::Inverse) && self.fft_shift {
                 for f in 0..(m / self.len) {
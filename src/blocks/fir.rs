@@ -6,6 +6,7 @@ use futuredsp::MmseResampler;
 use futuredsp::PolyphaseResamplingFir;
 use futuredsp::firdes;
 use futuredsp::prelude::*;
+use num_complex::Complex64;
 use num_traits::Num;
 use std::iter::Sum;
 use std::ops::Mul;
@@ -13,7 +14,18 @@ use std::ops::Mul;
 use crate::prelude::*;
 
 /// FIR filter.
+///
+/// Filters created through [`FirBuilder`] have a `taps` message handler to read and replace
+/// their taps, which makes the taps part of flowgraph snapshots.
+///
+/// # Message Inputs
+///
+/// `taps`: Replace the taps with a vector of the tap type, e.g., `Pmt::VecF32` for `f32`
+/// taps, or return them for `Pmt::Null`
 #[derive(Block)]
+#[message_inputs(taps)]
+#[message_input_schema(taps, description = "Filter taps", getter = current_taps)]
+#[tag_propagation(one_to_one)]
 pub struct Fir<
     InputType,
     OutputType,
@@ -25,7 +37,7 @@ pub struct Fir<
     InputType: CpuSample,
     OutputType: CpuSample,
     TapType: 'static + Send,
-    Core: Filter<InputType, OutputType, TapType> + Send,
+    Core: Filter<InputType, OutputType, TapType> + Send + 'static,
     IN: CpuBufferReader<Item = InputType>,
     OUT: CpuBufferWriter<Item = OutputType>,
 {
@@ -34,6 +46,9 @@ pub struct Fir<
     #[output]
     output: OUT,
     filter: Core,
+    taps: Pmt,
+    new_filter: Option<Box<dyn Fn(&Pmt) -> Option<Core> + Send>>,
+    held_tags: Vec<Tag>,
     _tap_type: std::marker::PhantomData<TapType>,
 }
 
@@ -55,10 +70,48 @@ where
             input,
             output: OUT::default(),
             filter,
+            taps: Pmt::Null,
+            new_filter: None,
+            held_tags: Vec::new(),
             _tap_type: std::marker::PhantomData,
         }
     }
 
+    /// Make the taps accessible through the `taps` message handler.
+    fn with_taps(
+        mut self,
+        taps: Pmt,
+        new_filter: impl Fn(&Pmt) -> Option<Core> + Send + 'static,
+    ) -> Self {
+        self.taps = taps;
+        self.new_filter = Some(Box::new(new_filter));
+        self
+    }
+
+    fn current_taps(&self) -> Pmt {
+        self.taps.clone()
+    }
+
+    async fn taps(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        if matches!(p, Pmt::Null) {
+            return Ok(self.taps.clone());
+        }
+        match self.new_filter.as_ref().and_then(|f| f(&p)) {
+            Some(filter) if filter.length() > 0 => {
+                self.filter = filter;
+                self.taps = p;
+                Ok(Pmt::Ok)
+            }
+            _ => Ok(Pmt::InvalidValue),
+        }
+    }
+
     /// Returns the number of taps
     pub fn n_taps(&self) -> usize {
         self.filter.length()
@@ -82,11 +135,21 @@ where
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        let i = self.input.slice();
-        let o = self.output.slice();
+        let (i, i_tags) = self.input.slice_with_tags();
+        let (o, mut o_tags) = self.output.slice_with_tags();
 
         let (consumed, produced, status) = self.filter.filter(i, o);
 
+        Self::tag_propagation().forward_held(
+            0,
+            0,
+            i_tags,
+            consumed,
+            &mut o_tags,
+            produced,
+            &mut self.held_tags,
+        );
+
         self.input.consume(consumed);
         self.output.produce(produced);
 
@@ -98,8 +161,91 @@ where
     }
 }
 
+/// Taps that can be converted to and from a [`Pmt`].
+///
+/// Implemented for vectors and arrays of `f32`, `f64`, `Complex32`, and `Complex64` taps, which
+/// are represented as `Pmt::VecF32`, `Pmt::VecPmt` of `Pmt::F64`, `Pmt::VecCF32`, and
+/// `Pmt::VecCF64`. Array references cannot be created from a [`Pmt`].
+pub trait FirTaps: Sized {
+    /// Convert taps to a [`Pmt`]
+    fn to_pmt(&self) -> Pmt;
+    /// Create taps from a [`Pmt`]
+    fn from_pmt(p: &Pmt) -> Option<Self>;
+}
+
+macro_rules! impl_fir_taps {
+    ($t:ty, $to:expr, $from:expr) => {
+        impl FirTaps for Vec<$t> {
+            fn to_pmt(&self) -> Pmt {
+                $to(self.as_slice())
+            }
+            fn from_pmt(p: &Pmt) -> Option<Self> {
+                $from(p)
+            }
+        }
+
+        impl<const N: usize> FirTaps for [$t; N] {
+            fn to_pmt(&self) -> Pmt {
+                $to(self.as_slice())
+            }
+            fn from_pmt(p: &Pmt) -> Option<Self> {
+                $from(p)?.try_into().ok()
+            }
+        }
+
+        impl<const N: usize> FirTaps for &[$t; N] {
+            fn to_pmt(&self) -> Pmt {
+                $to(self.as_slice())
+            }
+            fn from_pmt(_p: &Pmt) -> Option<Self> {
+                None
+            }
+        }
+    };
+}
+
+impl_fir_taps!(
+    f32,
+    |t: &[f32]| Pmt::VecF32(t.to_vec()),
+    |p: &Pmt| match p {
+        Pmt::VecF32(v) => Some(v.clone()),
+        _ => None,
+    }
+);
+impl_fir_taps!(
+    f64,
+    |t: &[f64]| Pmt::VecPmt(t.iter().map(|x| Pmt::F64(*x)).collect()),
+    |p: &Pmt| match p {
+        Pmt::VecPmt(v) => v
+            .iter()
+            .map(|x| match x {
+                Pmt::F64(x) => Some(*x),
+                _ => None,
+            })
+            .collect::<Option<Vec<f64>>>(),
+        _ => None,
+    }
+);
+impl_fir_taps!(
+    Complex32,
+    |t: &[Complex32]| Pmt::VecCF32(t.to_vec()),
+    |p: &Pmt| match p {
+        Pmt::VecCF32(v) => Some(v.clone()),
+        _ => None,
+    }
+);
+impl_fir_taps!(
+    Complex64,
+    |t: &[Complex64]| Pmt::VecCF64(t.to_vec()),
+    |p: &Pmt| match p {
+        Pmt::VecCF64(v) => Some(v.clone()),
+        _ => None,
+    }
+);
+
 /// Stateful FIR filter.
 #[derive(Block)]
+#[tag_propagation(one_to_one)]
 pub struct StatefulFir<
     InputType,
     OutputType,
@@ -120,6 +266,7 @@ pub struct StatefulFir<
     #[output]
     output: OUT,
     filter: Core,
+    held_tags: Vec<Tag>,
     _tap_type: std::marker::PhantomData<TapType>,
 }
 
@@ -139,6 +286,7 @@ where
             input: IN::default(),
             output: OUT::default(),
             filter,
+            held_tags: Vec::new(),
             _tap_type: std::marker::PhantomData,
         }
     }
@@ -161,11 +309,21 @@ where
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        let i = self.input.slice();
-        let o = self.output.slice();
+        let (i, i_tags) = self.input.slice_with_tags();
+        let (o, mut o_tags) = self.output.slice_with_tags();
 
         let (consumed, produced, status) = self.filter.filter(i, o);
 
+        Self::tag_propagation().forward_held(
+            0,
+            0,
+            i_tags,
+            consumed,
+            &mut o_tags,
+            produced,
+            &mut self.held_tags,
+        );
+
         self.input.consume(consumed);
         self.output.produce(produced);
 
@@ -219,12 +377,14 @@ impl FirBuilder {
     where
         InputType: CpuSample,
         OutputType: CpuSample,
-        TapsType: 'static + Taps + Send,
+        TapsType: 'static + Taps + FirTaps + Send,
         TapsType::TapType: 'static + Send,
         FirFilter<InputType, OutputType, TapsType>:
             futuredsp::Filter<InputType, OutputType, TapsType::TapType>,
     {
+        let pmt = taps.to_pmt();
         Fir::<InputType, OutputType, TapsType::TapType, FirFilter<InputType, OutputType, TapsType>>::new(FirFilter::new(taps))
+            .with_taps(pmt, |p| TapsType::from_pmt(p).map(FirFilter::new))
     }
 
     /// Create a decimating FIR filter with standard low-pass taps.
@@ -254,17 +414,21 @@ impl FirBuilder {
     where
         InputType: CpuSample,
         OutputType: CpuSample,
-        TapsType: 'static + Taps + Send,
+        TapsType: 'static + Taps + FirTaps + Send,
         TapsType::TapType: 'static + Send,
         DecimatingFirFilter<InputType, OutputType, TapsType>:
             futuredsp::Filter<InputType, OutputType, TapsType::TapType>,
     {
+        let pmt = taps.to_pmt();
         Fir::<
             InputType,
             OutputType,
             TapsType::TapType,
             DecimatingFirFilter<InputType, OutputType, TapsType>,
         >::new(DecimatingFirFilter::new(decim, taps))
+        .with_taps(pmt, move |p| {
+            TapsType::from_pmt(p).map(|t| DecimatingFirFilter::new(decim, t))
+        })
     }
 
     /// Create a new rationally resampling FIR filter that changes the sampling
@@ -304,17 +468,23 @@ impl FirBuilder {
     where
         InputType: CpuSample,
         OutputType: CpuSample,
-        TapsType: 'static + Taps + Send,
+        TapsType: 'static + Taps + FirTaps + Send,
         TapsType::TapType: 'static + Send,
         PolyphaseResamplingFir<InputType, OutputType, TapsType>:
             Filter<InputType, OutputType, TapsType::TapType>,
     {
+        let pmt = taps.to_pmt();
         Fir::<
             InputType,
             OutputType,
             TapsType::TapType,
             PolyphaseResamplingFir<InputType, OutputType, TapsType>,
         >::new(PolyphaseResamplingFir::new(interp, decim, taps))
+        .with_taps(pmt, move |p| {
+            TapsType::from_pmt(p)
+                .filter(|t| t.num_taps() % interp == 0)
+                .map(|t| PolyphaseResamplingFir::new(interp, decim, t))
+        })
     }
     /// Create a new MMSE Resampler.
     pub fn mmse<SampleType>(
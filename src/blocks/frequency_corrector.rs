@@ -0,0 +1,168 @@
+use futuredsp::Rotator;
+use std::f64::consts::TAU;
+
+use crate::prelude::*;
+
+/// Correct the frequency offset of a complex stream.
+///
+/// Shifts the input by the negative offset, using a [`Rotator`]. The offset is either fixed,
+/// e.g., to compensate a known oscillator error, or tracked. In tracking mode, the block
+/// estimates the mean frequency of the input from the phase difference of consecutive samples
+/// and shifts it to DC. This works for signals with a dominant carrier or a symmetric spectrum,
+/// like FSK.
+///
+/// # Inputs
+///
+/// `input`: Samples with frequency offset
+///
+/// # Outputs
+///
+/// `output`: Corrected samples
+///
+/// # Messages
+///
+/// `offset`: Set the frequency offset in Hz (`Pmt::F64`) or get it with `Pmt::Null`. In tracking
+/// mode, this returns the current estimate, and setting it restarts the estimation from the
+/// given offset.
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::FrequencyCorrector;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// // crystal of the receiver is 1.5 kHz off
+/// let fixed: FrequencyCorrector = FrequencyCorrector::new(1.5e3, 1e6);
+/// let fixed = fg.add_block(fixed);
+/// let tracking: FrequencyCorrector = FrequencyCorrector::tracking(1e6, 1e-3);
+/// let tracking = fg.add_block(tracking);
+/// ```
+#[derive(Block)]
+#[tag_propagation(one_to_one)]
+#[message_inputs(offset)]
+#[message_input_schema(offset, description = "Frequency offset", kind = F64, unit = "Hz", getter = current_offset)]
+pub struct FrequencyCorrector<I = DefaultCpuReader<Complex32>, O = DefaultCpuWriter<Complex32>>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+    rotator: Rotator,
+    sample_rate: f64,
+    offset: f64,
+    tracking: Option<f32>,
+    estimate: Complex32,
+    last: Complex32,
+}
+
+impl<I, O> FrequencyCorrector<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    /// Create frequency corrector for a fixed offset
+    pub fn new(offset: f64, sample_rate: f64) -> Self {
+        assert!(
+            sample_rate > 0.0,
+            "FrequencyCorrector: sample rate has to be positive"
+        );
+        Self {
+            input: I::default(),
+            output: O::default(),
+            rotator: Rotator::new((-TAU * offset / sample_rate) as f32),
+            sample_rate,
+            offset,
+            tracking: None,
+            estimate: Complex32::new(0.0, 0.0),
+            last: Complex32::new(0.0, 0.0),
+        }
+    }
+
+    /// Create frequency corrector that tracks the offset
+    ///
+    /// `rate` is the averaging rate of the estimation in `(0, 1]`.
+    pub fn tracking(sample_rate: f64, rate: f32) -> Self {
+        assert!(
+            rate > 0.0 && rate <= 1.0,
+            "FrequencyCorrector: rate has to be in (0, 1]"
+        );
+        let mut s = Self::new(0.0, sample_rate);
+        s.tracking = Some(rate);
+        s
+    }
+
+    fn current_offset(&self) -> f64 {
+        self.offset
+    }
+
+    fn set_offset(&mut self, offset: f64) {
+        self.offset = offset;
+        self.rotator
+            .set_phase_incr((-TAU * offset / self.sample_rate) as f32);
+    }
+
+    async fn offset(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        offset: Option<f64>,
+    ) -> Result<Pmt> {
+        if let Some(o) = offset {
+            self.set_offset(o);
+            self.estimate = Complex32::from_polar(
+                self.estimate.norm().max(1.0),
+                (TAU * o / self.sample_rate) as f32,
+            );
+        }
+        Ok(Pmt::F64(self.offset))
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for FrequencyCorrector<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, i_tags) = self.input.slice_with_tags();
+        let (o, mut o_tags) = self.output.slice_with_tags();
+        let i_len = i.len();
+
+        let m = std::cmp::min(i_len, o.len());
+        if m > 0 {
+            if let Some(a) = self.tracking {
+                for v in &i[..m] {
+                    self.estimate += (v * self.last.conj() - self.estimate) * a;
+                    self.last = *v;
+                }
+                self.offset = self.estimate.arg() as f64 * self.sample_rate / TAU;
+                self.rotator
+                    .set_phase_incr((-TAU * self.offset / self.sample_rate) as f32);
+            }
+
+            self.rotator.rotate(&i[..m], &mut o[..m]);
+
+            Self::tag_propagation().forward(0, 0, i_tags, m, &mut o_tags, m);
+
+            self.input.consume(m);
+            self.output.produce(m);
+        }
+
+        if self.input.finished() && m == i_len {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use crate::prelude::*;
+
+/// Estimate and correct the IQ imbalance of a complex stream.
+///
+/// Gain and phase mismatches between the I and Q branches of a receiver cause image tones,
+/// mirrored around DC. The block estimates the powers of both branches and their correlation
+/// with an exponential moving average and orthogonalizes the Q branch w.r.t. the I branch
+/// (Gram-Schmidt), scaling it to the power of the I branch.
+///
+/// The estimation assumes a signal without DC offset, i.e., it is usually combined with a
+/// [`DcBlocker`](crate::blocks::DcBlocker) in front of it.
+///
+/// # Inputs
+///
+/// `input`: Samples with IQ imbalance
+///
+/// # Outputs
+///
+/// `output`: Corrected samples
+///
+/// # Messages
+///
+/// `rate`: Set the averaging rate of the estimation (`Pmt::F32` in `(0, 1]`)
+///
+/// `imbalance`: Get the current estimate as `Pmt::MapStrPmt` with the amplitude ratio of Q to I
+/// (`gain`, `Pmt::F64`) and the phase error (`phase`, `Pmt::F64`, in radians)
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::IqCorrector;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let iq: IqCorrector = IqCorrector::new(1e-4);
+/// let iq = fg.add_block(iq);
+/// ```
+#[derive(Block)]
+#[tag_propagation(one_to_one)]
+#[message_inputs(rate, imbalance)]
+#[message_input_schema(rate, description = "Averaging rate", kind = F32, min = 0, max = 1, getter = current_rate)]
+pub struct IqCorrector<I = DefaultCpuReader<Complex32>, O = DefaultCpuWriter<Complex32>>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+    rate: f32,
+    power_i: f32,
+    power_q: f32,
+    correlation: f32,
+}
+
+impl<I, O> IqCorrector<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    /// Create IQ corrector with the given averaging rate of the estimation
+    ///
+    /// Smaller rates result in more precise estimates but converge slower.
+    pub fn new(rate: f32) -> Self {
+        assert!(
+            rate > 0.0 && rate <= 1.0,
+            "IqCorrector: rate has to be in (0, 1]"
+        );
+        Self {
+            input: I::default(),
+            output: O::default(),
+            rate,
+            power_i: 1.0,
+            power_q: 1.0,
+            correlation: 0.0,
+        }
+    }
+
+    fn current_rate(&self) -> f32 {
+        self.rate
+    }
+
+    async fn rate(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        rate: Option<f32>,
+    ) -> Result<Pmt> {
+        match rate {
+            Some(r) if r > 0.0 && r <= 1.0 => self.rate = r,
+            Some(_) => return Ok(Pmt::InvalidValue),
+            None => {}
+        }
+        Ok(Pmt::F32(self.rate))
+    }
+
+    async fn imbalance(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        _p: Pmt,
+    ) -> Result<Pmt> {
+        let gain = (self.power_q / self.power_i).sqrt();
+        let phase = (self.correlation / (self.power_i * self.power_q).sqrt())
+            .clamp(-1.0, 1.0)
+            .asin();
+        Ok(Pmt::MapStrPmt(HashMap::from([
+            ("gain".to_string(), Pmt::F64(gain as f64)),
+            ("phase".to_string(), Pmt::F64(phase as f64)),
+        ])))
+    }
+}
+
+#[doc(hidden)]
+impl<I, O> Kernel for IqCorrector<I, O>
+where
+    I: CpuBufferReader<Item = Complex32>,
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, i_tags) = self.input.slice_with_tags();
+        let (o, mut o_tags) = self.output.slice_with_tags();
+        let i_len = i.len();
+
+        let m = std::cmp::min(i_len, o.len());
+        if m > 0 {
+            let a = self.rate;
+            for (v, r) in i.iter().zip(o.iter_mut()) {
+                self.power_i += a * (v.re * v.re - self.power_i);
+                self.power_q += a * (v.im * v.im - self.power_q);
+                self.correlation += a * (v.re * v.im - self.correlation);
+
+                if self.power_i > f32::MIN_POSITIVE {
+                    let k = self.correlation / self.power_i;
+                    let residual = self.power_q - k * self.correlation;
+                    let scale = if residual > 0.0 {
+                        (self.power_i / residual).sqrt()
+                    } else {
+                        1.0
+                    };
+                    *r = Complex32::new(v.re, (v.im - k * v.re) * scale);
+                } else {
+                    // no estimate without signal on I
+                    *r = *v;
+                }
+            }
+
+            Self::tag_propagation().forward(0, 0, i_tags, m, &mut o_tags, m);
+
+            self.input.consume(m);
+            self.output.produce(m);
+        }
+
+        if self.input.finished() && m == i_len {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
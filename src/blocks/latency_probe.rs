@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use crate::prelude::*;
+use crate::runtime::latency::LatencyRecorder;
+use crate::runtime::latency::Probe;
+
+/// Inject latency probe tags and report per-path latency histograms.
+///
+/// Forwards samples and tags and, every `interval` samples, attaches a
+/// [`Probe`] tag. Each block that sees the probe in its input buffer records
+/// the time since the probe was injected. Probes only travel through blocks
+/// that forward tags.
+///
+/// # Inputs
+///
+/// `in`: Input
+///
+/// # Outputs
+///
+/// `out`: Input samples with additional probe tags
+///
+/// # Message Inputs
+///
+/// `latency`: Returns a [`Pmt::MapStrPmt`], mapping the ids of all blocks
+/// reached by the probes to their latency histograms.
+///
+/// `reset`: Clear the histograms.
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::LatencyProbe;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let probe = fg.add_block(LatencyProbe::<f32>::new(10_000));
+/// ```
+#[derive(Block)]
+#[message_inputs(latency, reset)]
+pub struct LatencyProbe<T, I = DefaultCpuReader<T>, O = DefaultCpuWriter<T>>
+where
+    T: Copy + Send + 'static,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+    interval: u64,
+    n_items: u64,
+    n_probes: u64,
+    recorder: Arc<LatencyRecorder>,
+}
+
+impl<T, I, O> LatencyProbe<T, I, O>
+where
+    T: Copy + Send + 'static,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    /// Create Latency Probe block, injecting a probe every `interval` samples
+    pub fn new(interval: u64) -> Self {
+        assert!(interval > 0, "probe interval has to be positive");
+        Self {
+            input: I::default(),
+            output: O::default(),
+            interval,
+            n_items: 0,
+            n_probes: 0,
+            recorder: Arc::new(LatencyRecorder::new()),
+        }
+    }
+
+    /// Recorder that collects the latencies of the probes
+    pub fn recorder(&self) -> Arc<LatencyRecorder> {
+        self.recorder.clone()
+    }
+
+    async fn latency(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        _p: Pmt,
+    ) -> Result<Pmt> {
+        Ok(self.recorder.to_pmt())
+    }
+
+    async fn reset(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        _p: Pmt,
+    ) -> Result<Pmt> {
+        self.recorder.reset();
+        Ok(Pmt::Ok)
+    }
+}
+
+#[doc(hidden)]
+impl<T, I, O> Kernel for LatencyProbe<T, I, O>
+where
+    T: Copy + Send + 'static,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, i_tags) = self.input.slice_with_tags();
+        let (o, mut o_tags) = self.output.slice_with_tags();
+        let i_len = i.len();
+
+        let m = std::cmp::min(i_len, o.len());
+        if m > 0 {
+            o[..m].copy_from_slice(&i[..m]);
+
+            i_tags.iter().for_each(|t| {
+                if t.index < m {
+                    o_tags.add_tag(t.index, t.tag.clone())
+                }
+            });
+
+            let mut next = self.n_items.next_multiple_of(self.interval);
+            while next < self.n_items + m as u64 {
+                let probe = Probe::new(self.n_probes, self.recorder.clone());
+                o_tags.add_tag((next - self.n_items) as usize, probe.to_tag());
+                self.n_probes += 1;
+                next += self.interval;
+            }
+
+            self.n_items += m as u64;
+            self.input.consume(m);
+            self.output.produce(m);
+        }
+
+        if self.input.finished() && m == i_len {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -15,15 +15,19 @@
 //! ## Streams
 //! | Block | Usage | WebAssembly? |
 //! |---|---|---|
+//! | [Convert] | Convert between integer sample formats and floats. | ✅ |
 //! | [StreamDeinterleaver](StreamDeinterleaver) | Stream Deinterleave | ✅ |
 //! | [StreamDuplicator](StreamDuplicator) | Stream Duplicator | ✅ |
 //!
 //! ## DSP blocks
 //! | Block | Usage | WebAssembly? |
 //! |---|---|---|
+//! | [DcBlocker] | Remove the DC offset of a complex stream. | ✅ |
 //! | [Fft](Fft) | Compute an FFT. | ✅ |
 //! | [Fir](FirBuilder) | FIR filter and resampler. | ✅ |
+//! | [FrequencyCorrector] | Correct a fixed or tracked frequency offset. | ✅ |
 //! | [Iir](Iir) | IIR filter. | ✅ |
+//! | [IqCorrector] | Estimate and correct IQ imbalance. | ✅ |
 //! | [PfbArbResampler](PfbArbResampler) | Polyphase Arbitrary Rate Resampler | ✅ |
 //! | [PfbChannelizer](PfbChannelizer) | Polyphase Channelizer | ✅ |
 //! | [PfbSynthesizer](PfbSynthesizer) | Polyphase Synthesizer | ✅ |
@@ -35,9 +39,11 @@
 //! | [ConsoleSink] | Log stream data with [log::info!]. | ✅ |
 //! | [Delay] | Delays samples. | ✅ |
 //! | [Head] | Copies only a given number of samples and stops. | ✅ |
+//! | [LatencyProbe] | Inject latency probe tags and report per-path latency histograms. | ✅ |
 //! ! [MovingAvg] | Applies an exponential moving average over a window samples. | ✅ |
 //! | [NullSink] | Drops samples. | ✅ |
 //! | [NullSource] | Generates a stream of zeros. | ✅ |
+//! | [Scanner] | Sweep a source through a list of center frequencies. | ✅ |
 //! | [Selector] | Forward the input stream with a given index to the output stream with a given index. | ✅ |
 //! | [TagDebug] | Drop samples, printing tags. | ✅ |
 //! | [Throttle] | Limit sample rate. | ✅ |
@@ -63,17 +69,29 @@
 //! ## I/O
 //! | Block | Usage | WebAssembly? |
 //! |---|---|---|
-//! | [BlobToUdp] | Push [Blobs](crate::runtime::Pmt::Blob) into a UDP socket. | ❌ |
+//! | [BlobToUdp] | Push [Blobs](crate::runtime::Pmt::Blob) or encoded [Pmts](crate::runtime::Pmt) into a UDP socket. | ❌ |
 //! | [ChannelSource] | Push samples through a channel into a stream connection. | ✅ |
 //! | [ChannelSink] | Read samples from Flowgraph and send them into a channel | ✅ |
 //! | [FileSink] | Write samples to a file. | ❌ |
 //! | [FileSource] | Read samples from a file. | ❌ |
-//! | [TcpSource] | Reads samples from a TCP socket. | ❌ |
-//! | [TcpSink] | Push samples into a TCP socket. | ❌ |
+//! | [TcpSource](TcpSourceBuilder) | Reads samples from a TCP socket (server or client). | ❌ |
+//! | [TcpSink](TcpSinkBuilder) | Push samples into TCP sockets (server with fan-out or client). | ❌ |
+//! | [UdpSink] | Push samples into a UDP socket. | ❌ |
 //! | [UdpSource] | Reads samples from a UDP socket. | ❌ |
+//! | [UdpToBlob] | Output UDP datagrams as [Blobs](crate::runtime::Pmt::Blob) or decoded [Pmts](crate::runtime::Pmt). | ❌ |
+//! | [vita::VitaSink] | Send samples as [VITA-49](https://www.vita.com/) packets. | ❌ |
+//! | [vita::VitaSource] | Receive [VITA-49](https://www.vita.com/) packets. | ❌ |
 //! | [WebsocketSink] | Push samples in a WebSocket. | ❌ |
 //! | [WebsocketPmtSink] | Push samples from Pmts a WebSocket. | ❌ |
+//! | [WebsocketPmtSource] | Receive encoded Pmts from a WebSocket. | ❌ |
+//! | [WebsocketSource] | Read samples from a WebSocket. | ❌ |
+//! | [zeromq::MessageSink] | Send [Pmts](crate::runtime::Pmt) to a [ZeroMQ](https://zeromq.org/) PUB, PUSH, or REP socket. | ❌ |
+//! | [zeromq::MessageSource] | Receive [Pmts](crate::runtime::Pmt) from a [ZeroMQ](https://zeromq.org/) SUB, PULL, or REQ socket. | ❌ |
 //! | [zeromq::PubSink] | Push samples into [ZeroMQ](https://zeromq.org/) socket. | ❌ |
+//! | [zeromq::PullSource] | Read samples from a [ZeroMQ](https://zeromq.org/) PULL socket. | ❌ |
+//! | [zeromq::PushSink] | Push samples into a [ZeroMQ](https://zeromq.org/) PUSH socket. | ❌ |
+//! | [zeromq::RepSink] | Serve samples on a [ZeroMQ](https://zeromq.org/) REP socket. | ❌ |
+//! | [zeromq::ReqSource] | Request samples from a [ZeroMQ](https://zeromq.org/) REP socket. | ❌ |
 //! | [zeromq::SubSource] | Read samples from [ZeroMQ](https://zeromq.org/) socket. | ❌ |
 //!
 //! ## SDR Hardware
@@ -81,6 +99,7 @@
 //! |---|---|---|---|
 //! | [SeifySink](seify::SinkBuilder) | Transmit samples with a Seify device. | seify | ❌ |
 //! | [SeifySource](seify::SourceBuilder) | Receive samples from a Seify device. | seify | ❌ |
+//! | [Simulated](seify::Simulated) | Simulated Seify device, playing back recordings and synthetic signals. | seify | ❌ |
 //!
 //! ## Hardware Acceleration
 //! | Block | Usage | WebAssembly? | Feature |
@@ -104,9 +123,14 @@
 //! ## Audio (requires `audio` feature)
 //! | Block | Usage | WebAssembly? |
 //! |---|---|---|
-//! | [AudioSink](audio::AudioSink) | Audio sink. | ❌ |
-//! | [AudioSource](audio::AudioSource) | Audio source. | ❌ |
+//! | [AudioSink](audio::AudioSink) | Audio sink with resampling, device selection and drift compensation. | ❌ |
+//! | [AudioSource](audio::AudioSource) | Audio source with resampling, device selection and drift compensation. | ❌ |
+//! | [Codec2Decoder](audio::Codec2Decoder) | Decode Codec2 frames to audio samples (requires `codec2` feature). | ✅ |
+//! | [Codec2Encoder](audio::Codec2Encoder) | Encode audio samples to Codec2 frames (requires `codec2` feature). | ✅ |
 //! | [FileSource](audio::FileSource) | Read an audio file and output its samples. | ❌ |
+//! | [FlacSink](audio::FlacSink) | Writes samples to a FLAC file. | ❌ |
+//! | [OpusSink](audio::OpusSink) | Writes samples to an Ogg Opus file (requires `opus` feature). | ❌ |
+//! | [OpusSource](audio::OpusSource) | Reads samples from an Ogg Opus file (requires `opus` feature). | ❌ |
 //! | [WavSink](audio::WavSink) | Writes samples to a WAV file | ❌ |
 //!
 
@@ -130,8 +154,22 @@ mod combine;
 pub use combine::Combine;
 mod console_sink;
 pub use console_sink::ConsoleSink;
+mod convert;
+pub use convert::ComplexIntToComplex;
+pub use convert::ComplexToComplexInt;
+pub use convert::ComplexToInt;
+pub use convert::ComplexToPacked12;
+pub use convert::Conversion;
+pub use convert::Convert;
+pub use convert::FloatToInt;
+pub use convert::IntSample;
+pub use convert::IntToComplex;
+pub use convert::IntToFloat;
+pub use convert::Packed12ToComplex;
 mod copy;
 pub use copy::Copy;
+mod dc_blocker;
+pub use dc_blocker::DcBlocker;
 mod delay;
 pub use delay::Delay;
 mod fft;
@@ -153,10 +191,17 @@ pub use finite_source::FiniteSource;
 mod fir;
 pub use fir::Fir;
 pub use fir::FirBuilder;
+pub use fir::FirTaps;
+mod frequency_corrector;
+pub use frequency_corrector::FrequencyCorrector;
 mod head;
 pub use head::Head;
 mod iir;
 pub use iir::Iir;
+mod iq_corrector;
+pub use iq_corrector::IqCorrector;
+mod latency_probe;
+pub use latency_probe::LatencyProbe;
 mod message_annotator;
 pub use message_annotator::MessageAnnotator;
 mod message_apply;
@@ -185,6 +230,8 @@ mod pfb;
 pub use pfb::arb_resampler::PfbArbResampler;
 pub use pfb::channelizer::PfbChannelizer;
 pub use pfb::synthesizer::PfbSynthesizer;
+mod scanner;
+pub use scanner::Scanner;
 /// Seify hardware driver blocks
 #[cfg(all(feature = "seify", not(target_arch = "wasm32")))]
 pub mod seify;
@@ -208,23 +255,41 @@ pub use stream_duplicator::StreamDuplicator;
 mod tag_debug;
 pub use tag_debug::TagDebug;
 #[cfg(not(target_arch = "wasm32"))]
+mod tcp_connection;
+#[cfg(not(target_arch = "wasm32"))]
+pub use tcp_connection::TcpFraming;
+#[cfg(not(target_arch = "wasm32"))]
 mod tcp_sink;
 #[cfg(not(target_arch = "wasm32"))]
 pub use tcp_sink::TcpSink;
 #[cfg(not(target_arch = "wasm32"))]
+pub use tcp_sink::TcpSinkBuilder;
+#[cfg(not(target_arch = "wasm32"))]
 mod tcp_source;
 #[cfg(not(target_arch = "wasm32"))]
 pub use tcp_source::TcpSource;
+#[cfg(not(target_arch = "wasm32"))]
+pub use tcp_source::TcpSourceBuilder;
 mod throttle;
 pub use throttle::Throttle;
 #[cfg(not(target_arch = "wasm32"))]
+mod udp_sink;
+#[cfg(not(target_arch = "wasm32"))]
+pub use udp_sink::UdpSink;
+#[cfg(not(target_arch = "wasm32"))]
 mod udp_source;
 #[cfg(not(target_arch = "wasm32"))]
 pub use udp_source::UdpSource;
+#[cfg(not(target_arch = "wasm32"))]
+mod udp_to_blob;
+#[cfg(not(target_arch = "wasm32"))]
+pub use udp_to_blob::UdpToBlob;
 mod vector_sink;
 pub use vector_sink::VectorSink;
 mod vector_source;
 pub use vector_source::VectorSource;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod vita;
 #[cfg(feature = "vulkan")]
 mod vulkan;
 #[cfg(feature = "vulkan")]
@@ -233,10 +298,16 @@ pub use vulkan::Vulkan;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 #[cfg(not(target_arch = "wasm32"))]
+mod websocket_connection;
+#[cfg(not(target_arch = "wasm32"))]
 mod websocket_pmt_sink;
 #[cfg(not(target_arch = "wasm32"))]
 pub use websocket_pmt_sink::WebsocketPmtSink;
 #[cfg(not(target_arch = "wasm32"))]
+mod websocket_pmt_source;
+#[cfg(not(target_arch = "wasm32"))]
+pub use websocket_pmt_source::WebsocketPmtSource;
+#[cfg(not(target_arch = "wasm32"))]
 mod websocket_sink;
 #[cfg(not(target_arch = "wasm32"))]
 pub use websocket_sink::WebsocketSink;
@@ -244,6 +315,10 @@ pub use websocket_sink::WebsocketSink;
 pub use websocket_sink::WebsocketSinkBuilder;
 #[cfg(not(target_arch = "wasm32"))]
 pub use websocket_sink::WebsocketSinkMode;
+#[cfg(not(target_arch = "wasm32"))]
+mod websocket_source;
+#[cfg(not(target_arch = "wasm32"))]
+pub use websocket_source::WebsocketSource;
 pub mod xlating_fir;
 pub use xlating_fir::XlatingFir;
 #[cfg(feature = "wgpu")]
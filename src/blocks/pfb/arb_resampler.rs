@@ -55,6 +55,7 @@ struct State {
 
 /// Polyphase Arbitrary Rate Resampler
 #[derive(Block)]
+#[tag_propagation(one_to_one)]
 pub struct PfbArbResampler<
     I: CpuBufferReader<Item = Complex32> = DefaultCpuReader<Complex32>,
     O: CpuBufferWriter<Item = Complex32> = DefaultCpuWriter<Complex32>,
@@ -64,6 +65,7 @@ pub struct PfbArbResampler<
     input: I,
     #[output]
     output: O,
+    held_tags: Vec<Tag>,
 }
 
 impl<I, O> PfbArbResampler<I, O>
@@ -109,6 +111,7 @@ where
             },
             input: I::default(),
             output,
+            held_tags: Vec::new(),
         }
     }
 }
@@ -182,7 +185,7 @@ impl Kernel for PfbArbResampler {
         _mio: &mut MessageOutputs,
         _b: &mut BlockMeta,
     ) -> Result<()> {
-        let input = self.input.slice();
+        let (input, i_tags) = self.input.slice_with_tags();
         let ninput_items = input.len();
         // fill filter history
         if !self.s.window_buf.filled() {
@@ -191,6 +194,13 @@ impl Kernel for PfbArbResampler {
                 self.s.window_buf.push(input[consumed]);
                 consumed += 1;
             }
+            // no output yet, keep the tags for the first output item
+            self.held_tags.extend(
+                i_tags
+                    .iter()
+                    .filter(|t| t.index < consumed)
+                    .map(|t| t.tag.clone()),
+            );
             self.input.consume(consumed);
             if ninput_items - consumed > 0 {
                 io.call_again = true;
@@ -199,7 +209,7 @@ impl Kernel for PfbArbResampler {
             }
             return Ok(());
         }
-        let out = self.output.slice();
+        let (out, mut o_tags) = self.output.slice_with_tags();
         let noutput_items = out.len();
         let nitem_to_process = min(ninput_items, (noutput_items as f32 / self.s.rate) as usize);
         if nitem_to_process > 0 {
@@ -207,6 +217,15 @@ impl Kernel for PfbArbResampler {
             for sample in input.iter().take(nitem_to_process) {
                 produced += self.s.consume_single(*sample, &mut out[produced..])
             }
+            Self::tag_propagation().forward_held(
+                0,
+                0,
+                i_tags,
+                nitem_to_process,
+                &mut o_tags,
+                produced,
+                &mut self.held_tags,
+            );
             self.input.consume(nitem_to_process);
             self.output.produce(produced);
         }
@@ -34,6 +34,7 @@ use futuredsp::FirFilter;
 use futuredsp::prelude::*;
 
 use crate::prelude::*;
+use crate::runtime::buffer::Tags;
 
 use super::utilities::partition_filter_taps;
 use super::window_buffer::WindowBuffer;
@@ -51,6 +52,7 @@ struct State {
 
 /// Polyphase Channelizer
 #[derive(Block)]
+#[tag_propagation(all_to_all)]
 pub struct PfbChannelizer<I = DefaultCpuReader<Complex32>, O = DefaultCpuWriter<Complex32>>
 where
     I: CpuBufferReader<Item = Complex32>,
@@ -127,10 +129,14 @@ where
         _m: &mut MessageOutputs,
         _b: &mut BlockMeta,
     ) -> Result<()> {
-        let input = self.input.slice();
+        let (input, i_tags) = self.input.slice_with_tags();
         let n_items_to_consume = input.len();
-        let mut outs: Vec<&mut [Complex32]> = self.outputs.iter_mut().map(|x| x.slice()).collect();
-        let n_items_producible = outs.iter().map(|x| x.len()).min().unwrap();
+        let mut outs: Vec<(&mut [Complex32], Tags)> = self
+            .outputs
+            .iter_mut()
+            .map(|x| x.slice_with_tags())
+            .collect();
+        let n_items_producible = outs.iter().map(|x| x.0.len()).min().unwrap();
         let n_items_to_produce_per_channel = min(
             n_items_producible,
             n_items_to_consume / self.s.decimation_factor,
@@ -184,9 +190,19 @@ where
             // Send to output channels
             #[allow(clippy::needless_range_loop)]
             for channel_index in 0..self.s.num_channels {
-                outs[channel_index][output_sample_index] = self.s.fft_buf[channel_index];
+                outs[channel_index].0[output_sample_index] = self.s.fft_buf[channel_index];
             }
         }
+        for (channel_index, (_, o_tags)) in outs.iter_mut().enumerate() {
+            Self::tag_propagation().forward(
+                0,
+                channel_index,
+                i_tags,
+                n_items_to_produce_per_channel * self.s.decimation_factor,
+                o_tags,
+                n_items_to_produce_per_channel,
+            );
+        }
         // commit sio buffers
         self.input
             .consume(n_items_to_produce_per_channel * self.s.decimation_factor);
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+
+use crate::prelude::*;
+
+#[derive(Debug)]
+enum State {
+    Retune,
+    Settle(usize),
+    Dwell(usize, bool),
+}
+
+/// Sweep a source through a list of center frequencies.
+///
+/// The `freq` message output is connected to the `freq` message input of the source, e.g., a
+/// [`seify::Source`](crate::blocks::seify::Source). The scanner retunes the source, drops `settle`
+/// samples, and forwards the next `dwell` samples before moving on to the next frequency. After the
+/// last frequency, it starts again with the first one.
+///
+/// The settling time should cover the retuning of the hardware and the samples that are still in
+/// the buffer between the source and the scanner.
+///
+/// The first sample of each dwell is tagged with a `Tag::Data(Pmt::MapStrPmt)`, containing the
+/// center frequency (`freq`, `Pmt::F64`) and its position in the list (`index`, `Pmt::Usize`),
+/// allowing downstream blocks to stitch a wideband spectrum.
+///
+/// # Inputs
+///
+/// `in`: Samples of the source
+///
+/// # Outputs
+///
+/// `out`: Samples after settling, tagged with the frequency
+///
+/// **Message**: `freq`: Center frequency (`Pmt::F64`)
+///
+/// # Messages
+///
+/// `frequencies`: Set the list of frequencies (`Pmt::VecPmt` of numbers) and restart the sweep
+///
+/// `dwell`: Set the number of samples per frequency (`Pmt::Usize`)
+///
+/// `settle`: Set the number of samples to drop after retuning (`Pmt::Usize`)
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::Scanner;
+/// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex32;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// // 88 to 108 MHz in 2 MHz steps, 16k samples per step, 4k settling samples
+/// let scanner = fg.add_block(Scanner::<Complex32>::range(88e6, 108e6, 2e6, 16384, 4096));
+/// ```
+#[derive(Block)]
+#[message_inputs(frequencies, dwell, settle)]
+#[message_input_schema(frequencies, description = "Frequencies", unit = "Hz", getter = current_frequencies)]
+#[message_input_schema(dwell, description = "Dwell", kind = Usize, unit = "samples", getter = current_dwell)]
+#[message_input_schema(settle, description = "Settle", kind = Usize, unit = "samples", getter = current_settle)]
+#[message_outputs(freq)]
+pub struct Scanner<T, I = DefaultCpuReader<T>, O = DefaultCpuWriter<T>>
+where
+    T: Copy + Send + 'static,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    #[input]
+    input: I,
+    #[output]
+    output: O,
+    frequencies: Vec<f64>,
+    index: usize,
+    dwell: usize,
+    settle: usize,
+    state: State,
+}
+
+impl<T, I, O> Scanner<T, I, O>
+where
+    T: Copy + Send + 'static,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    /// Create Scanner block for a list of frequencies
+    pub fn new(frequencies: Vec<f64>, dwell: usize, settle: usize) -> Self {
+        assert!(!frequencies.is_empty(), "Scanner: no frequencies");
+        assert!(dwell > 0, "Scanner: dwell has to be positive");
+        Self {
+            input: I::default(),
+            output: O::default(),
+            frequencies,
+            index: 0,
+            dwell,
+            settle,
+            state: State::Retune,
+        }
+    }
+
+    /// Create Scanner block for the frequencies from `start` to `stop` (inclusive) in steps of
+    /// `step`
+    pub fn range(start: f64, stop: f64, step: f64, dwell: usize, settle: usize) -> Self {
+        assert!(step > 0.0, "Scanner: step has to be positive");
+        let n = ((stop - start) / step + 1e-9).floor() as usize + 1;
+        let frequencies = (0..n).map(|i| start + i as f64 * step).collect();
+        Self::new(frequencies, dwell, settle)
+    }
+
+    fn current_frequencies(&self) -> Serde<Vec<f64>> {
+        Serde(self.frequencies.clone())
+    }
+
+    fn current_dwell(&self) -> usize {
+        self.dwell
+    }
+
+    fn current_settle(&self) -> usize {
+        self.settle
+    }
+
+    async fn frequencies(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        Serde(frequencies): Serde<Vec<f64>>,
+    ) -> Result<Pmt> {
+        if frequencies.is_empty() {
+            return Ok(Pmt::InvalidValue);
+        }
+        self.frequencies = frequencies;
+        self.index = 0;
+        self.state = State::Retune;
+        io.call_again = true;
+        Ok(Pmt::Ok)
+    }
+
+    async fn dwell(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        dwell: Option<usize>,
+    ) -> Result<Pmt> {
+        match dwell {
+            Some(0) => return Ok(Pmt::InvalidValue),
+            Some(d) => self.dwell = d,
+            None => {}
+        }
+        Ok(Pmt::Usize(self.dwell))
+    }
+
+    async fn settle(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        settle: Option<usize>,
+    ) -> Result<Pmt> {
+        if let Some(s) = settle {
+            self.settle = s;
+        }
+        Ok(Pmt::Usize(self.settle))
+    }
+
+    fn tag(&self) -> Tag {
+        Tag::Data(Pmt::MapStrPmt(HashMap::from([
+            ("freq".to_string(), Pmt::F64(self.frequencies[self.index])),
+            ("index".to_string(), Pmt::Usize(self.index)),
+        ])))
+    }
+}
+
+#[doc(hidden)]
+impl<T, I, O> Kernel for Scanner<T, I, O>
+where
+    T: Copy + Send + 'static,
+    I: CpuBufferReader<Item = T>,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        if let State::Retune = self.state {
+            mio.post("freq", Pmt::F64(self.frequencies[self.index]))
+                .await?;
+            self.state = State::Settle(self.settle);
+        }
+
+        let i_len = self.input.slice().len();
+        let mut consumed = 0;
+
+        if let State::Settle(n) = self.state {
+            let m = n.min(i_len);
+            consumed += m;
+            self.state = if m == n {
+                State::Dwell(self.dwell, true)
+            } else {
+                State::Settle(n - m)
+            };
+        }
+
+        if let State::Dwell(n, tag_pending) = self.state {
+            let tag = tag_pending.then(|| self.tag());
+            let i = self.input.slice();
+            let (o, mut o_tags) = self.output.slice_with_tags();
+            let m = n.min(i_len - consumed).min(o.len());
+            if m > 0 {
+                o[..m].copy_from_slice(&i[consumed..consumed + m]);
+                if let Some(tag) = tag {
+                    o_tags.add_tag(0, tag);
+                }
+                consumed += m;
+                self.output.produce(m);
+                self.state = if m == n {
+                    self.index = (self.index + 1) % self.frequencies.len();
+                    io.call_again = true;
+                    State::Retune
+                } else {
+                    State::Dwell(n - m, false)
+                };
+            }
+        }
+
+        self.input.consume(consumed);
+        if self.input.finished() && consumed == i_len {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
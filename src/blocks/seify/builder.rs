@@ -114,7 +114,7 @@ impl<D: DeviceTrait + Clone> Builder<D> {
         self.config.sample_rate = Some(s);
         self
     }
-    /// Start Time
+    /// Start Time, delay in nanoseconds after the activation of the stream
     pub fn start_time(mut self, s: i64) -> Self {
         self.start_time = Some(s);
         self
@@ -1,9 +1,11 @@
+use futuresdr_types::PmtConversionError;
 use seify::Device;
 use seify::DeviceTrait;
 use seify::Direction;
 use std::collections::HashMap;
 
 use crate::runtime::Error;
+use crate::runtime::HandlerArg;
 use crate::runtime::Pmt;
 
 /// Seify Config
@@ -139,3 +141,76 @@ impl TryFrom<Pmt> for Config {
         }
     }
 }
+
+/// Remove the `chan` entry of a `"cmd"` message.
+///
+/// Returns the index into the channels of the block, if the message only applies to one channel.
+pub(super) fn take_channel(p: &mut Pmt) -> Result<Option<usize>, Error> {
+    let Pmt::MapStrPmt(m) = p else {
+        return Ok(None);
+    };
+    match m.remove("chan") {
+        None => Ok(None),
+        Some(Pmt::U32(c)) => Ok(Some(c as usize)),
+        Some(Pmt::U64(c)) => Ok(Some(c as usize)),
+        Some(Pmt::Usize(c)) => Ok(Some(c)),
+        Some(_) => Err(Error::PmtConversionError),
+    }
+}
+
+/// Argument of the `freq`, `gain`, and `sample_rate` message handlers of the Seify blocks.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChannelValue {
+    /// `Pmt::Null`: query the value of the first channel
+    Query,
+    /// A number: set the value of all channels
+    All(f64),
+    /// A list of numbers (e.g., `Pmt::VecPmt` or `Pmt::VecF32`): one value per channel
+    PerChannel(Vec<f64>),
+}
+
+impl ChannelValue {
+    /// Query or set the value of the `channels` of a device.
+    pub(super) fn apply(
+        self,
+        channels: &[usize],
+        get: impl Fn(usize) -> Result<f64, seify::Error>,
+        set: impl Fn(usize, f64) -> Result<(), seify::Error>,
+    ) -> Result<Pmt, seify::Error> {
+        match self {
+            ChannelValue::Query => Ok(Pmt::F64(get(channels[0])?)),
+            ChannelValue::All(v) => {
+                channels.iter().try_for_each(|c| set(*c, v))?;
+                Ok(Pmt::Ok)
+            }
+            ChannelValue::PerChannel(v) => {
+                if v.len() != channels.len() {
+                    return Ok(Pmt::InvalidValue);
+                }
+                channels.iter().zip(v).try_for_each(|(c, v)| set(*c, v))?;
+                Ok(Pmt::Ok)
+            }
+        }
+    }
+}
+
+impl HandlerArg for ChannelValue {
+    fn from_pmt(p: Pmt) -> Result<Self, PmtConversionError> {
+        match p {
+            Pmt::Null => Ok(ChannelValue::Query),
+            Pmt::VecF32(v) => Ok(ChannelValue::PerChannel(
+                v.into_iter().map(|v| v as f64).collect(),
+            )),
+            Pmt::VecU64(v) => Ok(ChannelValue::PerChannel(
+                v.into_iter().map(|v| v as f64).collect(),
+            )),
+            Pmt::VecPmt(v) => Ok(ChannelValue::PerChannel(
+                v.into_iter().map(f64::try_from).collect::<Result<_, _>>()?,
+            )),
+            p => Ok(ChannelValue::All(p.try_into()?)),
+        }
+    }
+    fn type_description() -> String {
+        "Option<f64 | Vec<f64>>".to_string()
+    }
+}
@@ -0,0 +1,69 @@
+use seify::Args;
+use seify::Device;
+use seify::DeviceTrait;
+use seify::Direction;
+use seify::Direction::Rx;
+use seify::Direction::Tx;
+use seify::Range;
+
+use crate::runtime::ChannelDescription;
+use crate::runtime::DeviceDescription;
+use crate::runtime::Error;
+use crate::runtime::Pmt;
+
+fn range(r: Result<Range, seify::Error>) -> Pmt {
+    r.map_or(Pmt::Null, Pmt::from)
+}
+
+fn channels<D: DeviceTrait + Clone>(
+    dev: &Device<D>,
+    direction: Direction,
+) -> Result<Vec<ChannelDescription>, Error> {
+    (0..dev.num_channels(direction)?)
+        .map(|c| {
+            Ok(ChannelDescription {
+                full_duplex: dev.full_duplex(direction, c).unwrap_or(false),
+                antennas: dev.antennas(direction, c).unwrap_or_default(),
+                gain_elements: dev.gain_elements(direction, c).unwrap_or_default(),
+                gain_range: range(dev.gain_range(direction, c)),
+                frequency_range: range(dev.frequency_range(direction, c)),
+                sample_rate_range: range(dev.get_sample_rate_range(direction, c)),
+                bandwidth_range: range(dev.get_bandwidth_range(direction, c)),
+            })
+        })
+        .collect()
+}
+
+/// Describe the channels and capabilities of an opened device
+pub fn describe<D: DeviceTrait + Clone>(
+    args: impl Into<String>,
+    dev: &Device<D>,
+) -> Result<DeviceDescription, Error> {
+    Ok(DeviceDescription {
+        args: args.into(),
+        driver: format!("{:?}", dev.driver()),
+        id: dev.id().ok(),
+        rx: channels(dev, Rx)?,
+        tx: channels(dev, Tx)?,
+    })
+}
+
+/// Enumerate the devices that match `args` and describe their capabilities
+///
+/// Devices that cannot be opened, e.g., since they are used by a running flowgraph, are
+/// skipped.
+pub fn discover<A: TryInto<Args>>(args: A) -> Result<Vec<DeviceDescription>, Error> {
+    let args: Args = args.try_into().or(Err(Error::SeifyArgsConversionError))?;
+    let mut devices = Vec::new();
+    for a in seify::enumerate_with_args(args)? {
+        let s = a.to_string();
+        match Device::from_args(a)
+            .map_err(Error::from)
+            .and_then(|d| describe(&s, &d))
+        {
+            Ok(d) => devices.push(d),
+            Err(e) => warn!("Seify: cannot describe device {s} ({e})"),
+        }
+    }
+    Ok(devices)
+}
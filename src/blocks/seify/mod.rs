@@ -2,8 +2,21 @@ mod builder;
 pub use builder::Builder;
 
 mod config;
+pub use crate::blocks::seify::config::ChannelValue;
 pub use crate::blocks::seify::config::Config;
 
+mod discovery;
+pub use discovery::describe;
+pub use discovery::discover;
+
+mod simulated;
+pub use simulated::Recording;
+pub use simulated::Signal;
+pub use simulated::Simulated;
+pub use simulated::SimulatedRxStreamer;
+pub use simulated::SimulatedTxStreamer;
+pub use simulated::TxWrite;
+
 mod sink;
 pub use sink::Sink;
 
@@ -0,0 +1,1000 @@
+use seify::Args;
+use seify::DeviceTrait;
+use seify::Direction;
+use seify::Driver;
+use seify::Error;
+use seify::Range;
+use seify::RangeItem;
+use std::any::Any;
+use std::collections::VecDeque;
+use std::f64::consts::TAU;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::num_complex::Complex32;
+
+const MTU: usize = 8192;
+
+/// Recorded I/Q samples, played back by a [`Simulated`] device.
+#[derive(Clone, Debug)]
+pub struct Recording {
+    samples: Arc<Vec<Complex32>>,
+    sample_rate: f64,
+    freq: f64,
+}
+
+impl Recording {
+    /// Create a recording from samples, captured at `sample_rate` and center frequency `freq`
+    pub fn new(samples: Vec<Complex32>, sample_rate: f64, freq: f64) -> Self {
+        assert!(!samples.is_empty(), "Recording: no samples");
+        assert!(sample_rate > 0.0, "Recording: invalid sample rate");
+        Self {
+            samples: Arc::new(samples),
+            sample_rate,
+            freq,
+        }
+    }
+
+    /// Load a raw file of interleaved little-endian `f32` I/Q samples
+    pub fn from_file<P: AsRef<Path>>(path: P, sample_rate: f64, freq: f64) -> anyhow::Result<Self> {
+        let data = std::fs::read(path)?;
+        Ok(Self::new(decode(&data, "cf32_le")?, sample_rate, freq))
+    }
+
+    /// Load a [SigMF](https://sigmf.org/) recording
+    ///
+    /// `path` can point to the `.sigmf-meta` or the `.sigmf-data` file. The sample rate, the
+    /// frequency of the first capture, and the datatype (`cf32_le`, `ci16_le`, `ci8`, or `cu8`)
+    /// are read from the metadata.
+    pub fn from_sigmf<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let meta: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(path.with_extension("sigmf-meta"))?)?;
+        let global = &meta["global"];
+        let datatype = global["core:datatype"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("SigMF: no datatype"))?;
+        let sample_rate = global["core:sample_rate"]
+            .as_f64()
+            .ok_or_else(|| anyhow::anyhow!("SigMF: no sample rate"))?;
+        let freq = meta["captures"][0]["core:frequency"]
+            .as_f64()
+            .unwrap_or(0.0);
+        let data = std::fs::read(path.with_extension("sigmf-data"))?;
+        Ok(Self::new(decode(&data, datatype)?, sample_rate, freq))
+    }
+}
+
+fn decode(data: &[u8], datatype: &str) -> anyhow::Result<Vec<Complex32>> {
+    let samples = match datatype {
+        "cf32_le" => data
+            .chunks_exact(8)
+            .map(|c| {
+                Complex32::new(
+                    f32::from_le_bytes(c[0..4].try_into().unwrap()),
+                    f32::from_le_bytes(c[4..8].try_into().unwrap()),
+                )
+            })
+            .collect(),
+        "ci16_le" => data
+            .chunks_exact(4)
+            .map(|c| {
+                Complex32::new(
+                    i16::from_le_bytes([c[0], c[1]]) as f32 / 32768.0,
+                    i16::from_le_bytes([c[2], c[3]]) as f32 / 32768.0,
+                )
+            })
+            .collect(),
+        "ci8" | "ci8_le" => data
+            .chunks_exact(2)
+            .map(|c| Complex32::new(c[0] as i8 as f32 / 128.0, c[1] as i8 as f32 / 128.0))
+            .collect(),
+        "cu8" | "cu8_le" => data
+            .chunks_exact(2)
+            .map(|c| Complex32::new((c[0] as f32 - 127.5) / 128.0, (c[1] as f32 - 127.5) / 128.0))
+            .collect(),
+        d => anyhow::bail!("unsupported datatype {d}"),
+    };
+    Ok(samples)
+}
+
+/// Signal that is received by a [`Simulated`] device.
+#[derive(Clone, Debug)]
+pub enum Signal {
+    /// Complex sinusoid at an absolute frequency (Hz) with a given amplitude
+    Tone {
+        /// Frequency
+        freq: f64,
+        /// Amplitude
+        amplitude: f32,
+    },
+    /// Recording, played in a loop
+    Recording(Recording),
+}
+
+/// Write of a [`SimulatedTxStreamer`], recorded by the [`Simulated`] device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxWrite {
+    /// Number of samples written
+    pub samples: usize,
+    /// Transmit time, relative to now
+    pub at_ns: Option<i64>,
+    /// Whether the write ended a burst
+    pub end_burst: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Loopback {
+    delay: usize,
+    attenuation: f64,
+}
+
+#[derive(Clone, Debug)]
+struct Scenario {
+    channels: usize,
+    signals: Vec<Signal>,
+    noise: f32,
+    realtime: bool,
+    buffer_size: usize,
+    loopback: Option<Loopback>,
+    seed: u64,
+}
+
+#[derive(Clone, Debug)]
+struct ChannelConfig {
+    agc: bool,
+    bandwidth: f64,
+    freq: f64,
+    gain: f64,
+    sample_rate: f64,
+}
+
+impl Default for ChannelConfig {
+    fn default() -> Self {
+        Self {
+            agc: false,
+            bandwidth: 1e6,
+            freq: 100e6,
+            gain: 0.0,
+            sample_rate: 1e6,
+        }
+    }
+}
+
+struct Settings {
+    rx: Vec<ChannelConfig>,
+    tx: Vec<ChannelConfig>,
+}
+
+impl Settings {
+    fn get(&self, direction: Direction, channel: usize) -> Result<&ChannelConfig, Error> {
+        match direction {
+            Direction::Rx => self.rx.get(channel),
+            Direction::Tx => self.tx.get(channel),
+        }
+        .ok_or(Error::ValueError)
+    }
+
+    fn get_mut(
+        &mut self,
+        direction: Direction,
+        channel: usize,
+    ) -> Result<&mut ChannelConfig, Error> {
+        match direction {
+            Direction::Rx => self.rx.get_mut(channel),
+            Direction::Tx => self.tx.get_mut(channel),
+        }
+        .ok_or(Error::ValueError)
+    }
+}
+
+/// Samples in flight from TX to RX, per channel
+struct LoopbackQueue {
+    queues: Mutex<Vec<VecDeque<Complex32>>>,
+    cond: Condvar,
+}
+
+/// Simulated SDR
+///
+/// A seify device that synthesizes the received signal from a scenario of [tones](Signal::Tone),
+/// [recordings](Recording), and white Gaussian noise. The received signal follows the configured
+/// frequency (frequency shift of the signals relative to the center frequency), gain (scaling),
+/// and sample rate (recordings are resampled). In loopback mode, transmitted samples are received
+/// after a delay, attenuated, and shifted by the difference of TX and RX frequency.
+///
+/// In real-time mode (default), the RX streamer produces samples at the configured sample rate and
+/// reports an overflow if the reader falls behind by more than the buffer size. Otherwise, samples
+/// are produced as fast as they are read, which is useful for tests. In loopback mode, the RX
+/// streamer then waits for transmitted samples.
+///
+/// ```
+/// use futuresdr::blocks::seify::Builder;
+/// use futuresdr::blocks::seify::Simulated;
+/// use futuresdr::seify::Device;
+///
+/// let sim = Simulated::new().tone(100.1e6, 0.5).noise(-40.0);
+/// let src = Builder::from_device(Device::from_impl(sim))
+///     .frequency(100e6)
+///     .sample_rate(1e6)
+///     .build_source()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Simulated {
+    scenario: Arc<Scenario>,
+    settings: Arc<Mutex<Settings>>,
+    loopback: Arc<LoopbackQueue>,
+    tx_writes: Arc<Mutex<Vec<TxWrite>>>,
+}
+
+impl Simulated {
+    /// Create a simulated device with one channel, receiving nothing
+    pub fn new() -> Self {
+        Self {
+            scenario: Arc::new(Scenario {
+                channels: 1,
+                signals: Vec::new(),
+                noise: 0.0,
+                realtime: true,
+                buffer_size: 1 << 16,
+                loopback: None,
+                seed: 0x5eed,
+            }),
+            settings: Arc::new(Mutex::new(Settings {
+                rx: vec![ChannelConfig::default()],
+                tx: vec![ChannelConfig::default()],
+            })),
+            loopback: Arc::new(LoopbackQueue {
+                queues: Mutex::new(vec![VecDeque::new()]),
+                cond: Condvar::new(),
+            }),
+            tx_writes: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn scenario(&mut self) -> &mut Scenario {
+        Arc::make_mut(&mut self.scenario)
+    }
+
+    /// Number of RX and TX channels
+    pub fn channels(mut self, n: usize) -> Self {
+        assert!(n > 0, "Simulated: no channels");
+        self.scenario().channels = n;
+        let mut s = self.settings.lock().unwrap();
+        s.rx.resize(n, ChannelConfig::default());
+        s.tx.resize(n, ChannelConfig::default());
+        drop(s);
+        self.loopback
+            .queues
+            .lock()
+            .unwrap()
+            .resize(n, VecDeque::new());
+        self
+    }
+
+    /// Add a [`Signal`]
+    pub fn signal(mut self, s: Signal) -> Self {
+        self.scenario().signals.push(s);
+        self
+    }
+
+    /// Add a tone at an absolute frequency (Hz)
+    pub fn tone(self, freq: f64, amplitude: f32) -> Self {
+        self.signal(Signal::Tone { freq, amplitude })
+    }
+
+    /// Add a [`Recording`]
+    pub fn recording(self, r: Recording) -> Self {
+        self.signal(Signal::Recording(r))
+    }
+
+    /// Power of the white Gaussian noise (dB full scale)
+    pub fn noise(mut self, power_db: f64) -> Self {
+        self.scenario().noise = 10f64.powf(power_db / 10.0) as f32;
+        self
+    }
+
+    /// Produce samples at the configured sample rate (default) or as fast as they are read
+    pub fn realtime(mut self, r: bool) -> Self {
+        self.scenario().realtime = r;
+        self
+    }
+
+    /// Number of samples that are buffered in real-time mode before an overflow occurs
+    pub fn buffer_size(mut self, n: usize) -> Self {
+        self.scenario().buffer_size = n;
+        self
+    }
+
+    /// Receive transmitted samples after `delay` samples, attenuated by `attenuation_db`
+    pub fn loopback(mut self, delay: usize, attenuation_db: f64) -> Self {
+        self.scenario().loopback = Some(Loopback {
+            delay,
+            attenuation: 10f64.powf(-attenuation_db / 20.0),
+        });
+        self
+    }
+
+    /// Seed of the noise generator
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.scenario().seed = seed;
+        self
+    }
+
+    /// Writes of all TX streamers of the device, in order
+    pub fn tx_writes(&self) -> Vec<TxWrite> {
+        self.tx_writes.lock().unwrap().clone()
+    }
+
+    fn check_channel(&self, channel: usize) -> Result<(), Error> {
+        if channel < self.scenario.channels {
+            Ok(())
+        } else {
+            Err(Error::ValueError)
+        }
+    }
+
+    fn config(&self, direction: Direction, channel: usize) -> Result<ChannelConfig, Error> {
+        self.settings
+            .lock()
+            .unwrap()
+            .get(direction, channel)
+            .cloned()
+    }
+
+    fn update(
+        &self,
+        direction: Direction,
+        channel: usize,
+        range: Range,
+        value: f64,
+        f: impl FnOnce(&mut ChannelConfig),
+    ) -> Result<(), Error> {
+        if !range.contains(value) {
+            return Err(Error::OutOfRange(range, value));
+        }
+        let mut s = self.settings.lock().unwrap();
+        f(s.get_mut(direction, channel)?);
+        Ok(())
+    }
+
+    fn check_streamer_channels(&self, channels: &[usize]) -> Result<(), Error> {
+        if channels.is_empty() {
+            return Err(Error::ValueError);
+        }
+        channels.iter().try_for_each(|c| self.check_channel(*c))
+    }
+}
+
+impl Default for Simulated {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn freq_range() -> Range {
+    Range::new(vec![RangeItem::Interval(0.0, 6e9)])
+}
+
+fn gain_range() -> Range {
+    Range::new(vec![RangeItem::Interval(0.0, 60.0)])
+}
+
+fn rate_range() -> Range {
+    Range::new(vec![RangeItem::Interval(1e3, 100e6)])
+}
+
+fn bandwidth_range() -> Range {
+    Range::new(vec![RangeItem::Interval(0.0, 100e6)])
+}
+
+impl DeviceTrait for Simulated {
+    type RxStreamer = SimulatedRxStreamer;
+    type TxStreamer = SimulatedTxStreamer;
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn driver(&self) -> Driver {
+        Driver::Dummy
+    }
+
+    fn id(&self) -> Result<String, Error> {
+        Ok("simulated".to_string())
+    }
+
+    fn info(&self) -> Result<Args, Error> {
+        let mut a = Args::new();
+        a.set("driver", "simulated");
+        Ok(a)
+    }
+
+    fn num_channels(&self, _direction: Direction) -> Result<usize, Error> {
+        Ok(self.scenario.channels)
+    }
+
+    fn full_duplex(&self, _direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+        Ok(true)
+    }
+
+    fn rx_streamer(&self, channels: &[usize], _args: Args) -> Result<Self::RxStreamer, Error> {
+        self.check_streamer_channels(channels)?;
+        Ok(SimulatedRxStreamer::new(self.clone(), channels))
+    }
+
+    fn tx_streamer(&self, channels: &[usize], _args: Args) -> Result<Self::TxStreamer, Error> {
+        self.check_streamer_channels(channels)?;
+        Ok(SimulatedTxStreamer {
+            dev: self.clone(),
+            channels: channels.to_vec(),
+            clock: None,
+        })
+    }
+
+    fn antennas(&self, _direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
+        self.check_channel(channel)?;
+        Ok(vec!["A".to_string()])
+    }
+
+    fn antenna(&self, _direction: Direction, channel: usize) -> Result<String, Error> {
+        self.check_channel(channel)?;
+        Ok("A".to_string())
+    }
+
+    fn set_antenna(&self, _direction: Direction, channel: usize, name: &str) -> Result<(), Error> {
+        self.check_channel(channel)?;
+        match name {
+            "A" => Ok(()),
+            _ => Err(Error::ValueError),
+        }
+    }
+
+    fn gain_elements(&self, _direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
+        self.check_channel(channel)?;
+        Ok(vec!["RF".to_string()])
+    }
+
+    fn supports_agc(&self, _direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+        Ok(true)
+    }
+
+    fn enable_agc(&self, direction: Direction, channel: usize, agc: bool) -> Result<(), Error> {
+        let mut s = self.settings.lock().unwrap();
+        s.get_mut(direction, channel)?.agc = agc;
+        Ok(())
+    }
+
+    fn agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        Ok(self.config(direction, channel)?.agc)
+    }
+
+    fn set_gain(&self, direction: Direction, channel: usize, gain: f64) -> Result<(), Error> {
+        self.update(direction, channel, gain_range(), gain, |c| c.gain = gain)
+    }
+
+    fn gain(&self, direction: Direction, channel: usize) -> Result<Option<f64>, Error> {
+        let c = self.config(direction, channel)?;
+        Ok(if c.agc { None } else { Some(c.gain) })
+    }
+
+    fn gain_range(&self, _direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.check_channel(channel)?;
+        Ok(gain_range())
+    }
+
+    fn set_gain_element(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+        gain: f64,
+    ) -> Result<(), Error> {
+        match name {
+            "RF" => self.set_gain(direction, channel, gain),
+            _ => Err(Error::ValueError),
+        }
+    }
+
+    fn gain_element(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<Option<f64>, Error> {
+        match name {
+            "RF" => self.gain(direction, channel),
+            _ => Err(Error::ValueError),
+        }
+    }
+
+    fn gain_element_range(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<Range, Error> {
+        match name {
+            "RF" => self.gain_range(direction, channel),
+            _ => Err(Error::ValueError),
+        }
+    }
+
+    fn frequency_range(&self, _direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.check_channel(channel)?;
+        Ok(freq_range())
+    }
+
+    fn frequency(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        Ok(self.config(direction, channel)?.freq)
+    }
+
+    fn set_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        frequency: f64,
+        _args: Args,
+    ) -> Result<(), Error> {
+        self.update(direction, channel, freq_range(), frequency, |c| {
+            c.freq = frequency
+        })
+    }
+
+    fn frequency_components(
+        &self,
+        _direction: Direction,
+        channel: usize,
+    ) -> Result<Vec<String>, Error> {
+        self.check_channel(channel)?;
+        Ok(vec!["RF".to_string()])
+    }
+
+    fn component_frequency_range(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<Range, Error> {
+        match name {
+            "RF" => self.frequency_range(direction, channel),
+            _ => Err(Error::ValueError),
+        }
+    }
+
+    fn component_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<f64, Error> {
+        match name {
+            "RF" => self.frequency(direction, channel),
+            _ => Err(Error::ValueError),
+        }
+    }
+
+    fn set_component_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+        frequency: f64,
+    ) -> Result<(), Error> {
+        match name {
+            "RF" => self.set_frequency(direction, channel, frequency, Args::new()),
+            _ => Err(Error::ValueError),
+        }
+    }
+
+    fn sample_rate(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        Ok(self.config(direction, channel)?.sample_rate)
+    }
+
+    fn set_sample_rate(
+        &self,
+        direction: Direction,
+        channel: usize,
+        rate: f64,
+    ) -> Result<(), Error> {
+        self.update(direction, channel, rate_range(), rate, |c| {
+            c.sample_rate = rate
+        })
+    }
+
+    fn get_sample_rate_range(&self, _direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.check_channel(channel)?;
+        Ok(rate_range())
+    }
+
+    fn bandwidth(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        Ok(self.config(direction, channel)?.bandwidth)
+    }
+
+    fn set_bandwidth(&self, direction: Direction, channel: usize, bw: f64) -> Result<(), Error> {
+        self.update(direction, channel, bandwidth_range(), bw, |c| {
+            c.bandwidth = bw
+        })
+    }
+
+    fn get_bandwidth_range(&self, _direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.check_channel(channel)?;
+        Ok(bandwidth_range())
+    }
+
+    fn has_dc_offset_mode(&self, _direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+        Ok(false)
+    }
+
+    fn set_dc_offset_mode(
+        &self,
+        _direction: Direction,
+        _channel: usize,
+        _automatic: bool,
+    ) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    fn dc_offset_mode(&self, _direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.check_channel(channel)?;
+        Ok(false)
+    }
+}
+
+/// Wall clock to pace the streamers in real-time mode
+struct Clock {
+    start: Instant,
+    sample_rate: f64,
+    samples: u64,
+}
+
+impl Clock {
+    fn new(sample_rate: f64) -> Self {
+        Self {
+            start: Instant::now(),
+            sample_rate,
+            samples: 0,
+        }
+    }
+
+    /// Samples since the start
+    fn now(&self) -> u64 {
+        (self.start.elapsed().as_secs_f64() * self.sample_rate) as u64
+    }
+
+    /// Samples that are due, but not yet streamed
+    fn due(&self) -> u64 {
+        self.now().saturating_sub(self.samples)
+    }
+
+    /// Time until sample `n` is due
+    fn until(&self, n: u64) -> Duration {
+        Duration::from_secs_f64(n as f64 / self.sample_rate).saturating_sub(self.start.elapsed())
+    }
+}
+
+/// Normal-distributed noise (xorshift and Box-Muller)
+struct Noise {
+    state: u64,
+}
+
+impl Noise {
+    fn new(seed: u64) -> Self {
+        Self { state: seed.max(1) }
+    }
+
+    fn uniform(&mut self) -> f64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        ((self.state >> 11) as f64 + 1.0) / (1u64 << 53) as f64
+    }
+
+    fn sample(&mut self, std: f32) -> Complex32 {
+        let r = (-2.0 * self.uniform().ln()).sqrt();
+        let phi = TAU * self.uniform();
+        Complex32::new((r * phi.cos()) as f32, (r * phi.sin()) as f32) * std
+    }
+}
+
+struct RxChannel {
+    channel: usize,
+    phases: Vec<f64>,
+    positions: Vec<f64>,
+    loopback_phase: f64,
+    noise: Noise,
+}
+
+/// RX streamer of the [`Simulated`] device
+pub struct SimulatedRxStreamer {
+    dev: Simulated,
+    channels: Vec<RxChannel>,
+    clock: Option<Clock>,
+}
+
+impl SimulatedRxStreamer {
+    fn new(dev: Simulated, channels: &[usize]) -> Self {
+        let n = dev.scenario.signals.len();
+        let channels = channels
+            .iter()
+            .map(|c| RxChannel {
+                channel: *c,
+                phases: vec![0.0; n],
+                positions: vec![0.0; n],
+                loopback_phase: 0.0,
+                noise: Noise::new(dev.scenario.seed.wrapping_add(*c as u64)),
+            })
+            .collect();
+        Self {
+            dev,
+            channels,
+            clock: None,
+        }
+    }
+
+    /// Number of samples to produce, waiting at most `timeout`
+    fn available(&mut self, n: usize, timeout: Duration) -> Result<usize, Error> {
+        let scenario = &self.dev.scenario;
+        if scenario.realtime {
+            let rate = self
+                .dev
+                .config(Direction::Rx, self.channels[0].channel)?
+                .sample_rate;
+            let clock = self.clock.as_mut().ok_or(Error::Inactive)?;
+            if clock.sample_rate != rate {
+                *clock = Clock::new(rate);
+            }
+            if clock.due() > scenario.buffer_size as u64 {
+                clock.samples += clock.due();
+                return Err(Error::Overflow);
+            }
+            std::thread::sleep(clock.until(clock.samples + n as u64).min(timeout));
+            Ok((clock.due() as usize).min(n))
+        } else if scenario.loopback.is_some() {
+            let q = self.dev.loopback.queues.lock().unwrap();
+            let (q, _) = self
+                .dev
+                .loopback
+                .cond
+                .wait_timeout_while(q, timeout, |q| {
+                    self.channels.iter().any(|c| q[c.channel].is_empty())
+                })
+                .unwrap();
+            Ok(self
+                .channels
+                .iter()
+                .map(|c| q[c.channel].len())
+                .min()
+                .unwrap_or(0)
+                .min(n))
+        } else {
+            Ok(n)
+        }
+    }
+}
+
+impl seify::RxStreamer for SimulatedRxStreamer {
+    fn mtu(&self) -> Result<usize, Error> {
+        Ok(MTU)
+    }
+
+    fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        let rate = self
+            .dev
+            .config(Direction::Rx, self.channels[0].channel)?
+            .sample_rate;
+        self.clock = Some(Clock::new(rate));
+        Ok(())
+    }
+
+    fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        self.clock = None;
+        Ok(())
+    }
+
+    fn read(&mut self, buffers: &mut [&mut [Complex32]], timeout_us: i64) -> Result<usize, Error> {
+        if self.clock.is_none() {
+            return Err(Error::Inactive);
+        }
+        let n = buffers.iter().map(|b| b.len()).min().unwrap_or(0);
+        let timeout = Duration::from_micros(timeout_us.max(0) as u64);
+        let n = self.available(n, timeout)?;
+
+        let scenario = self.dev.scenario.clone();
+        let mut queues = self.dev.loopback.queues.lock().unwrap();
+
+        for (buf, ch) in buffers.iter_mut().zip(self.channels.iter_mut()) {
+            let rx = self.dev.config(Direction::Rx, ch.channel)?;
+            let tx = self.dev.config(Direction::Tx, ch.channel)?;
+            let buf = &mut buf[..n];
+            buf.fill(Complex32::new(0.0, 0.0));
+
+            for (i, s) in scenario.signals.iter().enumerate() {
+                match s {
+                    Signal::Tone { freq, amplitude } => {
+                        let step = TAU * (freq - rx.freq) / rx.sample_rate;
+                        for v in buf.iter_mut() {
+                            *v += Complex32::from_polar(*amplitude, ch.phases[i] as f32);
+                            ch.phases[i] = (ch.phases[i] + step) % TAU;
+                        }
+                    }
+                    Signal::Recording(r) => {
+                        let step = TAU * (r.freq - rx.freq) / rx.sample_rate;
+                        let advance = r.sample_rate / rx.sample_rate;
+                        let len = r.samples.len();
+                        for v in buf.iter_mut() {
+                            let p = ch.positions[i];
+                            let k = p as usize;
+                            let frac = (p - k as f64) as f32;
+                            let s = r.samples[k] * (1.0 - frac) + r.samples[(k + 1) % len] * frac;
+                            *v += s * Complex32::from_polar(1.0, ch.phases[i] as f32);
+                            ch.phases[i] = (ch.phases[i] + step) % TAU;
+                            ch.positions[i] = (p + advance) % len as f64;
+                        }
+                    }
+                }
+            }
+
+            if let Some(l) = &scenario.loopback {
+                let q = &mut queues[ch.channel];
+                let gain = (l.attenuation * 10f64.powf(tx.gain / 20.0)) as f32;
+                let step = TAU * (tx.freq - rx.freq) / rx.sample_rate;
+                let m = n.min(q.len());
+                for (v, s) in buf.iter_mut().zip(q.drain(..m)) {
+                    *v += s * gain * Complex32::from_polar(1.0, ch.loopback_phase as f32);
+                    ch.loopback_phase = (ch.loopback_phase + step) % TAU;
+                }
+            }
+
+            if scenario.noise > 0.0 {
+                let std = (scenario.noise / 2.0).sqrt();
+                for v in buf.iter_mut() {
+                    *v += ch.noise.sample(std);
+                }
+            }
+
+            let gain = 10f64.powf(rx.gain / 20.0) as f32;
+            for v in buf.iter_mut() {
+                *v *= gain;
+            }
+        }
+
+        drop(queues);
+        self.dev.loopback.cond.notify_all();
+        if let Some(c) = self.clock.as_mut() {
+            c.samples += n as u64;
+        }
+        Ok(n)
+    }
+}
+
+/// TX streamer of the [`Simulated`] device
+pub struct SimulatedTxStreamer {
+    dev: Simulated,
+    channels: Vec<usize>,
+    clock: Option<Clock>,
+}
+
+impl seify::TxStreamer for SimulatedTxStreamer {
+    fn mtu(&self) -> Result<usize, Error> {
+        Ok(MTU)
+    }
+
+    fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        let rate = self
+            .dev
+            .config(Direction::Tx, self.channels[0])?
+            .sample_rate;
+        self.clock = Some(Clock::new(rate));
+        if let Some(l) = &self.dev.scenario.loopback {
+            let mut q = self.dev.loopback.queues.lock().unwrap();
+            for c in &self.channels {
+                q[*c].extend(std::iter::repeat_n(Complex32::new(0.0, 0.0), l.delay));
+            }
+        }
+        Ok(())
+    }
+
+    fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        self.clock = None;
+        Ok(())
+    }
+
+    fn write(
+        &mut self,
+        buffers: &[&[Complex32]],
+        at_ns: Option<i64>,
+        end_burst: bool,
+        timeout_us: i64,
+    ) -> Result<usize, Error> {
+        let scenario = self.dev.scenario.clone();
+        let timeout = Duration::from_micros(timeout_us.max(0) as u64);
+        let mut n = buffers.iter().map(|b| b.len()).min().unwrap_or(0);
+
+        if scenario.realtime {
+            let rate = self
+                .dev
+                .config(Direction::Tx, self.channels[0])?
+                .sample_rate;
+            let clock = self.clock.as_mut().ok_or(Error::Inactive)?;
+            if clock.sample_rate != rate {
+                *clock = Clock::new(rate);
+            }
+            // samples can be written up to one buffer ahead, late samples are not modeled
+            let buffer = scenario.buffer_size as u64;
+            clock.samples = clock.samples.max(clock.now());
+            let wait = clock.until((clock.samples + n as u64).saturating_sub(buffer));
+            std::thread::sleep(wait.min(timeout));
+            n = n.min((clock.now() + buffer - clock.samples) as usize);
+            clock.samples += n as u64;
+        } else if self.clock.is_none() {
+            return Err(Error::Inactive);
+        }
+
+        if scenario.loopback.is_some() {
+            let capacity = scenario.buffer_size.max(MTU);
+            let q = self.dev.loopback.queues.lock().unwrap();
+            // in real time, samples that are not received in time are lost
+            let (mut q, _) = self
+                .dev
+                .loopback
+                .cond
+                .wait_timeout_while(q, timeout, |q| {
+                    !scenario.realtime && self.channels.iter().any(|c| q[*c].len() >= capacity)
+                })
+                .unwrap();
+            let free = self
+                .channels
+                .iter()
+                .map(|c| capacity.saturating_sub(q[*c].len()))
+                .min()
+                .unwrap_or(0);
+            if !scenario.realtime {
+                n = n.min(free);
+            }
+            for (c, b) in self.channels.iter().zip(buffers) {
+                q[*c].extend(&b[..n.min(free)]);
+            }
+            drop(q);
+            self.dev.loopback.cond.notify_all();
+        }
+
+        self.dev.tx_writes.lock().unwrap().push(TxWrite {
+            samples: n,
+            at_ns,
+            end_burst,
+        });
+        Ok(n)
+    }
+
+    fn write_all(
+        &mut self,
+        buffers: &[&[Complex32]],
+        at_ns: Option<i64>,
+        end_burst: bool,
+        timeout_us: i64,
+    ) -> Result<(), Error> {
+        let len = buffers.iter().map(|b| b.len()).min().unwrap_or(0);
+        let mut i = 0;
+        while i < len {
+            let bufs: Vec<&[Complex32]> = buffers.iter().map(|b| &b[i..len]).collect();
+            let n = self.write(&bufs, at_ns, end_burst, timeout_us)?;
+            if n == 0 {
+                return Err(Error::Misc("simulated device: write timeout".to_string()));
+            }
+            i += n;
+        }
+        Ok(())
+    }
+}
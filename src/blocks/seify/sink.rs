@@ -2,32 +2,52 @@ use seify::Device;
 use seify::DeviceTrait;
 use seify::Direction::Tx;
 use seify::TxStreamer;
+use std::collections::HashMap;
 use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
+use crate::blocks::seify::ChannelValue;
 use crate::blocks::seify::Config;
+use crate::blocks::seify::config::take_channel;
 use crate::num_complex::Complex32;
 use crate::prelude::*;
 
 /// Seify Sink block
 ///
+/// Without tags, samples are streamed continuously. Bursts are delimited with `Tag::NamedUsize`
+/// tags on the first input:
+///
+/// * `"burst_start"` on the first sample of a burst, with the burst length in samples. The sink
+///   waits until the complete burst is buffered, if it fits in the input buffer, and sets the
+///   end-of-burst flag on its last sample. A length of zero starts a burst of unknown length.
+/// * `"burst_end"` on the last sample of a burst, ending it independent of its length.
+/// * `"tx_time"` with a transmission time in nanoseconds since the Unix epoch, like the `"rx_time"`
+///   tags of the [`Source`](super::Source). Samples that arrive too late are sent immediately and
+///   reported on the `"events"` output.
+///
 /// # Ports
 ///
 /// * Stream inputs:
-///     - `"in"` (if single channel): `Complex32` I/Q samples
-///     - `"in1"`, `"in2"`, ... (if multiple channels): `Complex32` I/Q samples
+///     - `"inputs[0]"`, `"inputs[1]"`, ...: `Complex32` I/Q samples, one port per channel
 /// * Stream outputs: None
 /// * Message inputs:
-///     - `"freq"`: `f32`, `f64`, `u32`, or `u64` (Hertz) set center tuning frequency, or `Null` to query
-///     - `"gain"`: `f32`, `f64`, `u32`, or `u64` (dB) set gain, or `Null` to query
-///     - `"sample_rate"`: `f32`, `f64`, `u32`, or `u64` (Hertz) sample rate frequency, or `Null` to query
-///     - `"cmd"`: `Pmt` encoded `Config` to apply to all channels at once
+///     - `"freq"`: `f32`, `f64`, `u32`, or `u64` (Hertz) set center tuning frequency, a list with one value per channel, or `Null` to query
+///     - `"gain"`: `f32`, `f64`, `u32`, or `u64` (dB) set gain, a list with one value per channel, or `Null` to query
+///     - `"sample_rate"`: `f32`, `f64`, `u32`, or `u64` (Hertz) sample rate frequency, a list with one value per channel, or `Null` to query
+///     - `"cmd"`: `Pmt` encoded `Config` to apply to all channels at once, or to one channel if it contains a `"chan"` index
 ///     - `"config"`: `u32`, `u64`, `usize` (channel id) returns the `Config` for the specified channel as a `Pmt::MapStrPmt`
 /// * Message outputs:
 ///     - `"terminate_out"`: `Pmt::Ok` when stream has finished
+///     - `"events"`: `Pmt::MapStrPmt` with an `"event"` name, i.e., `"late"` (including the
+///       `"tx_time"`) or `"underflow"` when the input ran empty within a burst
 #[derive(Block)]
 #[blocking]
+#[message_input_schema(freq, description = "Center frequency", kind = F64, unit = "Hz", getter = current_freq)]
+#[message_input_schema(gain, description = "Gain", kind = F64, unit = "dB", getter = current_gain)]
+#[message_input_schema(sample_rate, description = "Sample rate", kind = F64, unit = "Hz", getter = current_sample_rate)]
 #[message_inputs(freq, gain, sample_rate, cmd, config)]
-#[message_outputs(terminate_out)]
+#[message_outputs(terminate_out, events)]
 #[type_name(SeifySink)]
 pub struct Sink<D, IN = DefaultCpuReader<Complex32>>
 where
@@ -41,6 +61,9 @@ where
     streamer: Option<D::TxStreamer>,
     start_time: Option<i64>,
     max_input_buffer_size_in_samples: usize,
+    burst_remaining: Option<usize>,
+    in_burst: bool,
+    underflow: bool,
 }
 
 impl<D, IN> Sink<D, IN>
@@ -72,7 +95,25 @@ where
             start_time,
             streamer: None,
             max_input_buffer_size_in_samples: 0,
+            burst_remaining: None,
+            in_burst: false,
+            underflow: false,
+        }
+    }
+
+    /// System time in nanoseconds since the Unix epoch
+    fn now() -> usize {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as usize)
+    }
+
+    fn event(name: &str, tx_time: Option<usize>) -> Pmt {
+        let mut m = HashMap::from([("event".to_string(), Pmt::String(name.to_string()))]);
+        if let Some(t) = tx_time {
+            m.insert("tx_time".to_string(), Pmt::Usize(t));
         }
+        Pmt::MapStrPmt(m)
     }
 
     async fn cmd(
@@ -80,30 +121,50 @@ where
         _io: &mut WorkIo,
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
-        p: Pmt,
+        mut p: Pmt,
     ) -> Result<Pmt> {
+        let chan = take_channel(&mut p)?;
         let c: Config = p.try_into()?;
-        c.apply(&self.dev, &self.channels, Tx)?;
+        match chan {
+            Some(i) => match self.channels.get(i) {
+                Some(ch) => c.apply(&self.dev, &vec![*ch], Tx)?,
+                None => return Ok(Pmt::InvalidValue),
+            },
+            None => c.apply(&self.dev, &self.channels, Tx)?,
+        }
         Ok(Pmt::Ok)
     }
 
+    /// Frequency of the first channel
+    fn current_freq(&self) -> Option<f64> {
+        let c = *self.channels.first()?;
+        self.dev.frequency(Tx, c).ok()
+    }
+
+    /// Gain of the first channel
+    fn current_gain(&self) -> Option<f64> {
+        let c = *self.channels.first()?;
+        self.dev.gain(Tx, c).ok().flatten()
+    }
+
+    /// Sample rate of the first channel
+    fn current_sample_rate(&self) -> Option<f64> {
+        let c = *self.channels.first()?;
+        self.dev.sample_rate(Tx, c).ok()
+    }
+
     async fn freq(
         &mut self,
         _io: &mut WorkIo,
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
-        p: Pmt,
+        v: ChannelValue,
     ) -> Result<Pmt> {
-        for c in &self.channels {
-            match &p {
-                Pmt::F32(v) => self.dev.set_frequency(Tx, *c, *v as f64)?,
-                Pmt::F64(v) => self.dev.set_frequency(Tx, *c, *v)?,
-                Pmt::U32(v) => self.dev.set_frequency(Tx, *c, *v as f64)?,
-                Pmt::U64(v) => self.dev.set_frequency(Tx, *c, *v as f64)?,
-                _ => return Ok(Pmt::InvalidValue),
-            };
-        }
-        Ok(Pmt::Ok)
+        Ok(v.apply(
+            &self.channels,
+            |c| self.dev.frequency(Tx, c),
+            |c, v| self.dev.set_frequency(Tx, c, v),
+        )?)
     }
 
     async fn gain(
@@ -111,18 +172,13 @@ where
         _io: &mut WorkIo,
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
-        p: Pmt,
+        v: ChannelValue,
     ) -> Result<Pmt> {
-        for c in &self.channels {
-            match &p {
-                Pmt::F32(v) => self.dev.set_gain(Tx, *c, *v as f64)?,
-                Pmt::F64(v) => self.dev.set_gain(Tx, *c, *v)?,
-                Pmt::U32(v) => self.dev.set_gain(Tx, *c, *v as f64)?,
-                Pmt::U64(v) => self.dev.set_gain(Tx, *c, *v as f64)?,
-                _ => return Ok(Pmt::InvalidValue),
-            };
-        }
-        Ok(Pmt::Ok)
+        Ok(v.apply(
+            &self.channels,
+            |c| Ok(self.dev.gain(Tx, c)?.unwrap_or(f64::NAN)),
+            |c, v| self.dev.set_gain(Tx, c, v),
+        )?)
     }
 
     async fn sample_rate(
@@ -130,18 +186,13 @@ where
         _io: &mut WorkIo,
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
-        p: Pmt,
+        v: ChannelValue,
     ) -> Result<Pmt> {
-        for c in &self.channels {
-            match &p {
-                Pmt::F32(v) => self.dev.set_sample_rate(Tx, *c, *v as f64)?,
-                Pmt::F64(v) => self.dev.set_sample_rate(Tx, *c, *v)?,
-                Pmt::U32(v) => self.dev.set_sample_rate(Tx, *c, *v as f64)?,
-                Pmt::U64(v) => self.dev.set_sample_rate(Tx, *c, *v as f64)?,
-                _ => return Ok(Pmt::InvalidValue),
-            };
-        }
-        Ok(Pmt::Ok)
+        Ok(v.apply(
+            &self.channels,
+            |c| self.dev.sample_rate(Tx, c),
+            |c, v| self.dev.set_sample_rate(Tx, c, v),
+        )?)
     }
 
     async fn config(
@@ -161,7 +212,7 @@ where
         if id >= self.channels.len() {
             return Ok(Pmt::InvalidValue);
         }
-        Ok(Config::from(&self.dev, Tx, id)?.to_serializable_pmt())
+        Ok(Config::from(&self.dev, Tx, self.channels[id])?.to_serializable_pmt())
     }
 }
 
@@ -178,60 +229,109 @@ where
         _meta: &mut BlockMeta,
     ) -> Result<()> {
         let tags = self.inputs[0].slice_with_tags().1.clone();
+        let input_finished = self.inputs.iter_mut().any(|i| i.finished());
         let bufs: Vec<&[Complex32]> = self.inputs.iter_mut().map(|b| b.slice()).collect();
 
         let streamer = self.streamer.as_mut().unwrap();
         let nitems_per_input_stream: Vec<usize> = bufs.iter().map(|b| b.len()).collect();
-
         let n = nitems_per_input_stream.iter().copied().min().unwrap_or(0);
-        let consumed = if n > 0 {
-            let t = tags.iter().find_map(|x| match x {
-                ItemTag {
-                    index,
-                    tag: Tag::NamedUsize(n, len),
-                } => {
-                    if *index == 0 && n == "burst_start" {
-                        Some(*len)
-                    } else {
-                        None
-                    }
+
+        let mut burst_start = None;
+        let mut tx_time = None;
+        let mut burst_end = None;
+        // samples up to the next burst or timestamp are written in one go
+        let mut limit = n;
+        for t in tags.iter().filter(|t| t.index < n) {
+            if let ItemTag {
+                index,
+                tag: Tag::NamedUsize(name, v),
+            } = t
+            {
+                match (name.as_str(), *index) {
+                    ("burst_start", 0) => burst_start = Some(*v),
+                    ("tx_time", 0) => tx_time = Some(*v),
+                    ("burst_start" | "tx_time", i) => limit = limit.min(i),
+                    ("burst_end", i) => burst_end = Some(burst_end.unwrap_or(i).min(i)),
+                    _ => {}
+                }
+            }
+        }
+
+        let consumed = match burst_start {
+            // wait until the complete burst is in the buffer
+            Some(len)
+                if len > 0
+                    && n < len
+                    && len <= self.max_input_buffer_size_in_samples
+                    && !input_finished =>
+            {
+                0
+            }
+            _ if n == 0 => {
+                if self.in_burst && !input_finished && !self.underflow {
+                    warn!("Seify Sink: underflow in burst");
+                    self.underflow = true;
+                    mio.post("events", Self::event("underflow", None)).await?;
+                }
+                0
+            }
+            _ => {
+                if let Some(len) = burst_start {
+                    self.burst_remaining = (len > 0).then_some(len);
+                    self.in_burst = true;
+                    self.underflow = false;
                 }
-                _ => None,
-            });
 
-            let consumed = if let Some(len) = t {
-                if n >= len {
-                    // send burst
-                    let bufs: Vec<&[Complex32]> = bufs.iter().map(|b| &b[0..len]).collect();
-                    let ret = streamer.write(&bufs, None, true, 2_000_000)?;
-                    debug_assert_eq!(ret, len);
-                    ret
-                } else if len > self.max_input_buffer_size_in_samples {
-                    warn!(
-                        "input buffers of seify sink too small ({} samples) to fit complete burst ({len} samples). sending in non-burst mode",
-                        self.max_input_buffer_size_in_samples
-                    );
-                    let bufs: Vec<&[Complex32]> = bufs.iter().map(|b| &b[0..n]).collect();
-                    let ret = streamer.write(&bufs, None, true, 2_000_000)?;
-                    debug_assert_eq!(ret, n);
-                    ret
+                let mut len = limit;
+                let mut end_burst = false;
+                if let Some(r) = self.burst_remaining {
+                    len = len.min(r);
+                    end_burst = len == r;
+                }
+                if let Some(e) = burst_end.filter(|e| *e < len) {
+                    len = e + 1;
+                    end_burst = true;
+                }
+                if self.burst_remaining.is_some() && !end_burst && input_finished && len == n {
+                    warn!("Seify Sink: input finished in burst, ending burst early");
+                    end_burst = true;
+                }
+
+                let at_ns = match tx_time {
+                    Some(t) => {
+                        let now = Self::now();
+                        if t < now {
+                            warn!("Seify Sink: late by {} ns, sending now", now - t);
+                            mio.post("events", Self::event("late", Some(t))).await?;
+                            None
+                        } else {
+                            Some((t - now) as i64)
+                        }
+                    }
+                    None => None,
+                };
+
+                let bufs: Vec<&[Complex32]> = bufs.iter().map(|b| &b[0..len]).collect();
+                let ret = if at_ns.is_some() || end_burst {
+                    streamer.write_all(&bufs, at_ns, end_burst, 2_000_000)?;
+                    len
                 } else {
-                    // wait for more samples
-                    0
+                    streamer.write(&bufs, None, false, 2_000_000)?
+                };
+
+                if let Some(r) = self.burst_remaining.as_mut() {
+                    *r -= ret;
+                }
+                if end_burst {
+                    self.burst_remaining = None;
+                    self.in_burst = false;
                 }
-            } else {
-                // send in non-burst mode
-                let ret = streamer.write(&bufs, None, false, 2_000_000)?;
-                if ret != n {
+                if ret < n {
                     io.call_again = true;
                 }
+                self.inputs.iter_mut().for_each(|i| i.consume(ret));
                 ret
-            };
-
-            self.inputs.iter_mut().for_each(|i| i.consume(consumed));
-            consumed
-        } else {
-            0
+            }
         };
 
         io.finished = self
@@ -4,8 +4,12 @@ use seify::DeviceTrait;
 use seify::Direction::Rx;
 use seify::RxStreamer;
 use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
+use crate::blocks::seify::ChannelValue;
 use crate::blocks::seify::Config;
+use crate::blocks::seify::config::take_channel;
 use crate::prelude::*;
 
 /// Seify Source block
@@ -14,18 +18,26 @@ use crate::prelude::*;
 ///
 /// * Stream inputs: None
 /// * Stream outputs:
-///     - `"out"` (if single channel): `Complex32` I/Q samples
-///     - `"out1"`, `"out2"`, ... (if multiple channels): `Complex32` I/Q samples
+///     - `"outputs[0]"`, `"outputs[1]"`, ...: `Complex32` I/Q samples, one port per channel
 /// * Message inputs:
-///     - `"freq"`: `f32`, `f64`, `u32`, or `u64` (Hertz) center tuning frequency, or `Null` to query
-///     - `"gain"`: `f32`, `f64`, `u32`, or `u64` (dB) gain setting, or `Null` to query
-///     - `"sample_rate"`: `f32`, `f64`, `u32`, or `u64` (Hertz) sample rate frequency, or `Null` to query
-///     - `"cmd"`: `Pmt` encoded `Config` to apply to all channels at once
+///     - `"freq"`: `f32`, `f64`, `u32`, or `u64` (Hertz) center tuning frequency, a list with one value per channel, or `Null` to query
+///     - `"gain"`: `f32`, `f64`, `u32`, or `u64` (dB) gain setting, a list with one value per channel, or `Null` to query
+///     - `"sample_rate"`: `f32`, `f64`, `u32`, or `u64` (Hertz) sample rate frequency, a list with one value per channel, or `Null` to query
+///     - `"cmd"`: `Pmt` encoded `Config` to apply to all channels at once, or to one channel if it contains a `"chan"` index
 ///     - `"terminate"`: `Pmt::Ok` to terminate the block
 ///     - `"config"`: `u32`, `u64`, `usize` (channel id) returns the `Config` for the specified channel as a `Pmt::MapStrPmt`
 /// * Message outputs: None
+///
+/// All channels are received by one streamer, i.e., they are aligned and started at the same
+/// time, delayed by `start_time` nanoseconds (if set). The first sample of each channel is tagged
+/// with `Tag::NamedUsize("rx_time", t)`, where `t` is the system time (in nanoseconds since the
+/// Unix epoch) when streaming was activated plus the `start_time` delay. After an overflow, the
+/// next sample is tagged with the current system time.
 #[derive(Block)]
 #[blocking]
+#[message_input_schema(freq, description = "Center frequency", kind = F64, unit = "Hz", getter = current_freq)]
+#[message_input_schema(gain, description = "Gain", kind = F64, unit = "dB", getter = current_gain)]
+#[message_input_schema(sample_rate, description = "Sample rate", kind = F64, unit = "Hz", getter = current_sample_rate)]
 #[message_inputs(freq, gain, sample_rate, cmd, terminate, config, overflows)]
 #[type_name(SeifySource)]
 pub struct Source<D, OUT = DefaultCpuWriter<Complex32>>
@@ -40,6 +52,7 @@ where
     streamer: Option<D::RxStreamer>,
     start_time: Option<i64>,
     overflows: u64,
+    rx_time: Option<usize>,
 }
 
 impl<D, OUT> Source<D, OUT>
@@ -62,9 +75,17 @@ where
             start_time,
             streamer: None,
             overflows: 0,
+            rx_time: None,
         }
     }
 
+    /// System time in nanoseconds since the Unix epoch
+    fn now() -> usize {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as usize)
+    }
+
     async fn terminate(
         &mut self,
         io: &mut WorkIo,
@@ -88,31 +109,50 @@ where
         _io: &mut WorkIo,
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
-        p: Pmt,
+        mut p: Pmt,
     ) -> Result<Pmt> {
+        let chan = take_channel(&mut p)?;
         let c: Config = p.try_into()?;
-        c.apply(&self.dev, &self.channels, Rx)?;
+        match chan {
+            Some(i) => match self.channels.get(i) {
+                Some(ch) => c.apply(&self.dev, &vec![*ch], Rx)?,
+                None => return Ok(Pmt::InvalidValue),
+            },
+            None => c.apply(&self.dev, &self.channels, Rx)?,
+        }
         Ok(Pmt::Ok)
     }
 
+    /// Frequency of the first channel
+    fn current_freq(&self) -> Option<f64> {
+        let c = *self.channels.first()?;
+        self.dev.frequency(Rx, c).ok()
+    }
+
+    /// Gain of the first channel
+    fn current_gain(&self) -> Option<f64> {
+        let c = *self.channels.first()?;
+        self.dev.gain(Rx, c).ok().flatten()
+    }
+
+    /// Sample rate of the first channel
+    fn current_sample_rate(&self) -> Option<f64> {
+        let c = *self.channels.first()?;
+        self.dev.sample_rate(Rx, c).ok()
+    }
+
     async fn freq(
         &mut self,
         _io: &mut WorkIo,
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
-        p: Pmt,
+        v: ChannelValue,
     ) -> Result<Pmt> {
-        for c in &self.channels {
-            match &p {
-                Pmt::F32(v) => self.dev.set_frequency(Rx, *c, *v as f64)?,
-                Pmt::F64(v) => self.dev.set_frequency(Rx, *c, *v)?,
-                Pmt::U32(v) => self.dev.set_frequency(Rx, *c, *v as f64)?,
-                Pmt::U64(v) => self.dev.set_frequency(Rx, *c, *v as f64)?,
-                Pmt::Null => return Ok(Pmt::F64(self.dev.frequency(Rx, *c)?)),
-                _ => return Ok(Pmt::InvalidValue),
-            };
-        }
-        Ok(Pmt::Ok)
+        Ok(v.apply(
+            &self.channels,
+            |c| self.dev.frequency(Rx, c),
+            |c, v| self.dev.set_frequency(Rx, c, v),
+        )?)
     }
 
     async fn gain(
@@ -120,19 +160,13 @@ where
         _io: &mut WorkIo,
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
-        p: Pmt,
+        v: ChannelValue,
     ) -> Result<Pmt> {
-        for c in &self.channels {
-            match &p {
-                Pmt::F32(v) => self.dev.set_gain(Rx, *c, *v as f64)?,
-                Pmt::F64(v) => self.dev.set_gain(Rx, *c, *v)?,
-                Pmt::U32(v) => self.dev.set_gain(Rx, *c, *v as f64)?,
-                Pmt::U64(v) => self.dev.set_gain(Rx, *c, *v as f64)?,
-                Pmt::Null => return Ok(Pmt::F64(self.dev.gain(Rx, *c)?.unwrap_or(f64::NAN))),
-                _ => return Ok(Pmt::InvalidValue),
-            };
-        }
-        Ok(Pmt::Ok)
+        Ok(v.apply(
+            &self.channels,
+            |c| Ok(self.dev.gain(Rx, c)?.unwrap_or(f64::NAN)),
+            |c, v| self.dev.set_gain(Rx, c, v),
+        )?)
     }
 
     async fn sample_rate(
@@ -140,19 +174,13 @@ where
         _io: &mut WorkIo,
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
-        p: Pmt,
+        v: ChannelValue,
     ) -> Result<Pmt> {
-        for c in &self.channels {
-            match &p {
-                Pmt::F32(v) => self.dev.set_sample_rate(Rx, *c, *v as f64)?,
-                Pmt::F64(v) => self.dev.set_sample_rate(Rx, *c, *v)?,
-                Pmt::U32(v) => self.dev.set_sample_rate(Rx, *c, *v as f64)?,
-                Pmt::U64(v) => self.dev.set_sample_rate(Rx, *c, *v as f64)?,
-                Pmt::Null => return Ok(Pmt::F64(self.dev.sample_rate(Rx, *c)?)),
-                _ => return Ok(Pmt::InvalidValue),
-            };
-        }
-        Ok(Pmt::Ok)
+        Ok(v.apply(
+            &self.channels,
+            |c| self.dev.sample_rate(Rx, c),
+            |c, v| self.dev.set_sample_rate(Rx, c, v),
+        )?)
     }
 
     async fn config(
@@ -172,7 +200,7 @@ where
         if id >= self.channels.len() {
             return Ok(Pmt::InvalidValue);
         }
-        Ok(Config::from(&self.dev, Rx, id)?.to_serializable_pmt())
+        Ok(Config::from(&self.dev, Rx, self.channels[id])?.to_serializable_pmt())
     }
 
     async fn overflows(
@@ -209,10 +237,20 @@ where
 
         match streamer.read(&mut bufs, 500_000) {
             Ok(len) => {
+                if len > 0
+                    && let Some(t) = self.rx_time.take()
+                {
+                    for o in self.outputs.iter_mut() {
+                        o.slice_with_tags()
+                            .1
+                            .add_tag(0, Tag::NamedUsize("rx_time".to_string(), t));
+                    }
+                }
                 self.outputs.iter_mut().for_each(|o| o.produce(len));
             }
             Err(seify::Error::Overflow) => {
                 self.overflows += 1;
+                self.rx_time = Some(Self::now());
                 warn!("Seify Source Overflow");
             }
             Err(e) => {
@@ -227,10 +265,13 @@ where
 
     async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
         self.streamer = Some(self.dev.rx_streamer(&self.channels)?);
+        // the start time is a delay relative to the activation
+        let now = Self::now();
         self.streamer
             .as_mut()
             .context("no stream")?
             .activate_at(self.start_time)?;
+        self.rx_time = Some(now + self.start_time.map_or(0, |t| t as usize));
 
         Ok(())
     }
@@ -62,6 +62,8 @@ impl fmt::Display for DropPolicy {
 /// given index.
 #[derive(Block)]
 #[message_inputs(input_index, output_index)]
+#[message_input_schema(input_index, description = "Selected input", kind = U32, min = 0, max = N - 1, step = 1, getter = current_input_index)]
+#[message_input_schema(output_index, description = "Selected output", kind = U32, min = 0, max = M - 1, step = 1, getter = current_output_index)]
 pub struct Selector<
     A,
     const N: usize,
@@ -109,6 +111,14 @@ where
         }
     }
 
+    fn current_input_index(&self) -> u32 {
+        self.input_index as u32
+    }
+
+    fn current_output_index(&self) -> u32 {
+        self.output_index as u32
+    }
+
     async fn input_index(
         &mut self,
         _io: &mut WorkIo,
@@ -0,0 +1,241 @@
+//! Connection handling and framing shared by [`TcpSource`](super::TcpSource) and
+//! [`TcpSink`](super::TcpSink).
+use anyhow::bail;
+use async_io::Timer;
+use async_net::TcpListener;
+use async_net::TcpStream;
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use futures::future::Shared;
+use serde::Deserialize;
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// Delay before the first reconnection attempt.
+const MIN_BACKOFF: Duration = Duration::from_millis(100);
+/// Maximum delay between reconnection attempts.
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Stream format of [`TcpSource`](super::TcpSource) and [`TcpSink`](super::TcpSink).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TcpFraming {
+    /// Plain samples
+    #[default]
+    Raw,
+    /// Length-prefixed frames that carry samples and tags
+    ///
+    /// Each frame starts with the number of items and the length of the tag section (both
+    /// big-endian `u32`), followed by the tags (JSON array of `[index, tag]`) and the items.
+    /// Only tags that can be serialized are forwarded, i.e., no `Tag::NamedAny`. The header and
+    /// tags of a frame may not exceed 1 MiB.
+    LengthPrefixed,
+}
+
+/// Size of the frame header.
+pub(super) const FRAME_HEADER_BYTES: usize = 8;
+/// Maximum size of frame header and tags, which the receiver buffers completely.
+pub(super) const MAX_FRAME_HEADER_BYTES: usize = 1 << 20;
+
+#[derive(Clone, Debug)]
+pub(super) enum Role {
+    Server(String),
+    Client(String),
+}
+
+/// Establishes connections in server or client role, reconnecting with exponential backoff.
+pub(super) struct Connector {
+    role: Role,
+    reconnect: bool,
+    listener: Option<TcpListener>,
+    pending: Option<Shared<BoxFuture<'static, Option<TcpStream>>>>,
+    backoff: Duration,
+    delay: Duration,
+}
+
+impl Connector {
+    pub(super) fn new(role: Role, reconnect: bool) -> Self {
+        Self {
+            role,
+            reconnect,
+            listener: None,
+            pending: None,
+            backoff: MIN_BACKOFF,
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub(super) fn reconnect(&self) -> bool {
+        self.reconnect
+    }
+
+    pub(super) async fn init(&mut self) -> Result<()> {
+        if let Role::Server(bind) = &self.role {
+            self.listener = Some(TcpListener::bind(bind.clone()).await?);
+        }
+        Ok(())
+    }
+
+    /// A connection was lost. Client connections are re-established after the backoff delay.
+    pub(super) fn lost(&mut self) {
+        self.delay = self.backoff;
+    }
+
+    /// Check for a new connection without blocking.
+    ///
+    /// Returns `None` while connecting. If `wait` is set, the block is woken up once the
+    /// connection attempt completes.
+    pub(super) fn poll(&mut self, io: &mut WorkIo, wait: bool) -> Result<Option<TcpStream>> {
+        let pending = match &self.pending {
+            Some(p) => p.clone(),
+            None => {
+                let p = self.start();
+                self.pending = Some(p.clone());
+                p
+            }
+        };
+
+        match pending.clone().now_or_never() {
+            Some(Some(s)) => {
+                self.pending = None;
+                self.backoff = MIN_BACKOFF;
+                Ok(Some(s))
+            }
+            Some(None) => {
+                self.pending = None;
+                if let Role::Client(_) = self.role {
+                    if !self.reconnect {
+                        bail!("tcp: could not connect");
+                    }
+                    self.delay = self.backoff;
+                    self.backoff = std::cmp::min(self.backoff * 2, MAX_BACKOFF);
+                }
+                // try again
+                io.call_again = true;
+                Ok(None)
+            }
+            None => {
+                if wait {
+                    io.block_on(pending.map(|_| ()));
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn start(&mut self) -> Shared<BoxFuture<'static, Option<TcpStream>>> {
+        match &self.role {
+            Role::Server(_) => {
+                let listener = self.listener.clone().expect("no listener");
+                async move {
+                    match listener.accept().await {
+                        Ok((s, addr)) => {
+                            debug!("tcp: accepted connection from {}", addr);
+                            Some(s)
+                        }
+                        Err(e) => {
+                            warn!("tcp: accept failed ({})", e);
+                            None
+                        }
+                    }
+                }
+                .boxed()
+                .shared()
+            }
+            Role::Client(remote) => {
+                let remote = remote.clone();
+                let delay = std::mem::take(&mut self.delay);
+                async move {
+                    if !delay.is_zero() {
+                        Timer::after(delay).await;
+                    }
+                    match TcpStream::connect(remote.clone()).await {
+                        Ok(s) => {
+                            debug!("tcp: connected to {}", remote);
+                            Some(s)
+                        }
+                        Err(e) => {
+                            warn!("tcp: connecting to {} failed ({})", remote, e);
+                            None
+                        }
+                    }
+                }
+                .boxed()
+                .shared()
+            }
+        }
+    }
+}
+
+/// Serializable subset of [`Tag`].
+#[derive(Serialize, Deserialize)]
+enum WireTag {
+    Id(u64),
+    String(String),
+    Data(Pmt),
+    NamedUsize(String, usize),
+    NamedF32(String, f32),
+}
+
+/// Append a frame header and the tags with `index < n_items` to `out`.
+pub(super) fn encode_frame_header(n_items: usize, tags: &[ItemTag], out: &mut Vec<u8>) {
+    let tags: Vec<(usize, WireTag)> = tags
+        .iter()
+        .filter(|t| t.index < n_items)
+        .filter_map(|t| {
+            let w = match &t.tag {
+                Tag::Id(v) => WireTag::Id(*v),
+                Tag::String(s) => WireTag::String(s.clone()),
+                Tag::Data(p) => WireTag::Data(p.clone()),
+                Tag::NamedUsize(n, v) => WireTag::NamedUsize(n.clone(), *v),
+                Tag::NamedF32(n, v) => WireTag::NamedF32(n.clone(), *v),
+                _ => return None,
+            };
+            Some((t.index, w))
+        })
+        .collect();
+    let mut tags = if tags.is_empty() {
+        Vec::new()
+    } else {
+        serde_json::to_vec(&tags).unwrap()
+    };
+    if FRAME_HEADER_BYTES + tags.len() > MAX_FRAME_HEADER_BYTES {
+        warn!("tcp: tags of frame exceed {MAX_FRAME_HEADER_BYTES} bytes, dropping them");
+        tags.clear();
+    }
+    out.extend_from_slice(&(n_items as u32).to_be_bytes());
+    out.extend_from_slice(&(tags.len() as u32).to_be_bytes());
+    out.extend_from_slice(&tags);
+}
+
+/// Parse a frame header, returning the number of items and the length of the tag section.
+pub(super) fn decode_frame_header(header: &[u8]) -> Result<(usize, usize)> {
+    let n_items = u32::from_be_bytes(header[0..4].try_into().unwrap()) as usize;
+    let tag_bytes = u32::from_be_bytes(header[4..8].try_into().unwrap()) as usize;
+    if FRAME_HEADER_BYTES + tag_bytes > MAX_FRAME_HEADER_BYTES {
+        bail!("tag section of {tag_bytes} bytes exceeds limit");
+    }
+    Ok((n_items, tag_bytes))
+}
+
+/// Parse the tag section of a frame.
+pub(super) fn decode_frame_tags(tags: &[u8]) -> Result<Vec<ItemTag>> {
+    if tags.is_empty() {
+        return Ok(Vec::new());
+    }
+    let tags: Vec<(usize, WireTag)> = serde_json::from_slice(tags)?;
+    Ok(tags
+        .into_iter()
+        .map(|(index, t)| ItemTag {
+            index,
+            tag: match t {
+                WireTag::Id(v) => Tag::Id(v),
+                WireTag::String(s) => Tag::String(s),
+                WireTag::Data(p) => Tag::Data(p),
+                WireTag::NamedUsize(n, v) => Tag::NamedUsize(n, v),
+                WireTag::NamedF32(n, v) => Tag::NamedF32(n, v),
+            },
+        })
+        .collect())
+}
@@ -1,12 +1,44 @@
-use anyhow::Context;
 use anyhow::bail;
-use async_net::TcpListener;
 use async_net::TcpStream;
 use futures::AsyncWriteExt;
 
+use crate::blocks::tcp_connection::Connector;
+use crate::blocks::tcp_connection::Role;
+use crate::blocks::tcp_connection::TcpFraming;
+use crate::blocks::tcp_connection::encode_frame_header;
 use crate::prelude::*;
 
 /// Push samples into a TCP socket.
+///
+/// The block either listens for connections (server) or connects to a remote server (client).
+/// Servers send the samples to all connected clients (fan-out) and accept new clients while
+/// running. The block waits for a connection before it consumes samples.
+///
+/// Without reconnection, the block fails when the last connection is lost. With reconnection,
+/// servers wait for a new client and clients reconnect with exponential backoff.
+///
+/// In [`TcpFraming::LengthPrefixed`] mode, samples are sent in frames that also carry their tags.
+///
+/// # Inputs
+///
+/// `input`: Samples to send
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::TcpFraming;
+/// use futuresdr::blocks::TcpSink;
+/// use futuresdr::blocks::TcpSinkBuilder;
+/// use futuresdr::num_complex::Complex32;
+///
+/// let local = TcpSink::<Complex32>::new(2342);
+/// let fan_out = TcpSinkBuilder::<Complex32>::server("0.0.0.0:2343")
+///     .reconnect(true)
+///     .framing(TcpFraming::LengthPrefixed)
+///     .build();
+/// let client = TcpSinkBuilder::<Complex32>::client("192.168.1.10:2342")
+///     .reconnect(true)
+///     .build();
+/// ```
 #[derive(Block)]
 pub struct TcpSink<T, I = DefaultCpuReader<T>>
 where
@@ -15,9 +47,11 @@ where
 {
     #[input]
     input: I,
-    port: u32,
-    listener: Option<TcpListener>,
-    socket: Option<TcpStream>,
+    connector: Connector,
+    server: bool,
+    framing: TcpFraming,
+    clients: Vec<TcpStream>,
+    buffer: Vec<u8>,
 }
 
 impl<T, I> TcpSink<T, I>
@@ -25,13 +59,23 @@ where
     T: Send + 'static,
     I: CpuBufferReader<Item = T>,
 {
-    /// Create TCP Sink block
+    /// Create TCP Sink block, listening for connections on `127.0.0.1:port`
     pub fn new(port: u32) -> Self {
+        Self::with_options(
+            Role::Server(format!("127.0.0.1:{port}")),
+            false,
+            TcpFraming::Raw,
+        )
+    }
+
+    fn with_options(role: Role, reconnect: bool, framing: TcpFraming) -> Self {
         Self {
             input: I::default(),
-            port,
-            listener: None,
-            socket: None,
+            server: matches!(role, Role::Server(_)),
+            connector: Connector::new(role, reconnect),
+            framing,
+            clients: Vec::new(),
+            buffer: Vec::new(),
         }
     }
 }
@@ -48,45 +92,130 @@ where
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        if self.socket.is_none() {
-            let (socket, _) = self
-                .listener
-                .as_mut()
-                .context("no listener")?
-                .accept()
-                .await?;
-            self.socket = Some(socket);
-            debug!("tcp sink accepted connection");
+        // servers accept additional clients, clients only keep one connection
+        while self.server || self.clients.is_empty() {
+            match self.connector.poll(io, self.clients.is_empty())? {
+                Some(s) => self.clients.push(s),
+                None => break,
+            }
+        }
+        if self.clients.is_empty() {
+            return Ok(());
         }
 
-        let i = self.input.slice();
+        // check before getting the slice, so that no samples arrive in between
+        let finished = self.input.finished();
+        let (i, tags) = self.input.slice_with_tags();
         let i_len = i.len();
-        let ptr = i.as_ptr() as *const u8;
-        let byte_len = std::mem::size_of_val(i);
-        let data = unsafe { std::slice::from_raw_parts(ptr, byte_len) };
-
-        match self
-            .socket
-            .as_mut()
-            .context("no socket")?
-            .write_all(data)
-            .await
-        {
-            Ok(()) => {}
-            Err(_) => bail!("tcp sink socket error"),
+
+        if i_len > 0 {
+            let bytes = unsafe {
+                std::slice::from_raw_parts(i.as_ptr() as *const u8, std::mem::size_of_val(i))
+            };
+            let data = match self.framing {
+                TcpFraming::Raw => bytes,
+                TcpFraming::LengthPrefixed => {
+                    self.buffer.clear();
+                    encode_frame_header(i_len, tags, &mut self.buffer);
+                    self.buffer.extend_from_slice(bytes);
+                    &self.buffer[..]
+                }
+            };
+
+            let mut lost = Vec::new();
+            for (n, c) in self.clients.iter_mut().enumerate() {
+                if c.write_all(data).await.is_err() {
+                    lost.push(n);
+                }
+            }
+            for n in lost.into_iter().rev() {
+                debug!("tcp sink connection lost");
+                self.clients.remove(n);
+                self.connector.lost();
+            }
+
+            if self.clients.is_empty() {
+                if !self.connector.reconnect() {
+                    bail!("tcp sink: connection lost");
+                }
+                // keep the samples for the next connection
+                io.call_again = true;
+                return Ok(());
+            }
+            self.input.consume(i_len);
         }
 
-        if self.input.finished() {
+        if finished {
             io.finished = true;
         }
 
-        self.input.consume(i_len);
-
         Ok(())
     }
 
     async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
-        self.listener = Some(TcpListener::bind(format!("127.0.0.1:{}", self.port)).await?);
+        self.connector.init().await
+    }
+
+    async fn deinit(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        for c in self.clients.iter_mut() {
+            let _ = c.close().await;
+        }
         Ok(())
     }
 }
+
+/// Build a [TcpSink].
+pub struct TcpSinkBuilder<T, I = DefaultCpuReader<T>>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    role: Role,
+    reconnect: bool,
+    framing: TcpFraming,
+    _type: std::marker::PhantomData<(T, I)>,
+}
+
+impl<T, I> TcpSinkBuilder<T, I>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    fn new(role: Role) -> Self {
+        Self {
+            role,
+            reconnect: false,
+            framing: TcpFraming::Raw,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    /// Listen for connections on `bind`, e.g., `0.0.0.0:2342`
+    pub fn server(bind: impl Into<String>) -> Self {
+        Self::new(Role::Server(bind.into()))
+    }
+
+    /// Connect to `remote`, e.g., `192.168.1.10:2342`
+    pub fn client(remote: impl Into<String>) -> Self {
+        Self::new(Role::Client(remote.into()))
+    }
+
+    /// Wait for new connections instead of failing when the last connection is lost
+    #[must_use]
+    pub fn reconnect(mut self, reconnect: bool) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Set stream format
+    #[must_use]
+    pub fn framing(mut self, framing: TcpFraming) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Build TcpSink
+    pub fn build(self) -> TcpSink<T, I> {
+        TcpSink::with_options(self.role, self.reconnect, self.framing)
+    }
+}
@@ -1,11 +1,50 @@
-use anyhow::Context;
-use async_net::TcpListener;
 use async_net::TcpStream;
 use futures::AsyncReadExt;
+use futures::FutureExt;
 
+use crate::blocks::tcp_connection::Connector;
+use crate::blocks::tcp_connection::FRAME_HEADER_BYTES;
+use crate::blocks::tcp_connection::MAX_FRAME_HEADER_BYTES;
+use crate::blocks::tcp_connection::Role;
+use crate::blocks::tcp_connection::TcpFraming;
+use crate::blocks::tcp_connection::decode_frame_header;
+use crate::blocks::tcp_connection::decode_frame_tags;
 use crate::prelude::*;
 
+/// Size of a single read from the socket.
+const READ_BYTES: usize = 1 << 16;
+/// Maximum number of received bytes that are buffered in the block.
+///
+/// Has to hold at least the header and tags of a complete frame.
+const MAX_BUFFER_BYTES: usize = MAX_FRAME_HEADER_BYTES;
+
 /// Read samples from a TCP socket.
+///
+/// The block either listens for a connection (server) or connects to a remote server (client).
+/// Without reconnection, the block finishes when the connection is closed. With reconnection,
+/// servers accept a new connection and clients reconnect with exponential backoff. Partial
+/// frames are discarded when the connection is lost.
+///
+/// In [`TcpFraming::LengthPrefixed`] mode, tags sent by a [`TcpSink`](crate::blocks::TcpSink)
+/// are restored.
+///
+/// # Outputs
+///
+/// `output`: Received samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::TcpFraming;
+/// use futuresdr::blocks::TcpSource;
+/// use futuresdr::blocks::TcpSourceBuilder;
+/// use futuresdr::num_complex::Complex32;
+///
+/// let server = TcpSource::<Complex32>::new("0.0.0.0:2342");
+/// let client = TcpSourceBuilder::<Complex32>::client("192.168.1.10:2342")
+///     .reconnect(true)
+///     .framing(TcpFraming::LengthPrefixed)
+///     .build();
+/// ```
 #[derive(Block)]
 pub struct TcpSource<T, O = DefaultCpuWriter<T>>
 where
@@ -14,9 +53,15 @@ where
 {
     #[output]
     output: O,
-    bind: String,
-    listener: Option<TcpListener>,
+    connector: Connector,
+    framing: TcpFraming,
     socket: Option<TcpStream>,
+    closed: bool,
+    chunk: Vec<u8>,
+    buffer: Vec<u8>,
+    frame_items: usize,
+    frame_offset: usize,
+    frame_tags: Vec<ItemTag>,
 }
 
 impl<T, O> TcpSource<T, O>
@@ -24,15 +69,96 @@ where
     T: Send + 'static,
     O: CpuBufferWriter<Item = T>,
 {
-    /// Create TCP Source block
+    /// Create TCP Source block, listening for a connection on `bind`
     pub fn new(bind: impl Into<String>) -> Self {
+        Self::with_options(Role::Server(bind.into()), false, TcpFraming::Raw)
+    }
+
+    fn with_options(role: Role, reconnect: bool, framing: TcpFraming) -> Self {
         Self {
             output: O::default(),
-            bind: bind.into(),
-            listener: None,
+            connector: Connector::new(role, reconnect),
+            framing,
             socket: None,
+            closed: false,
+            chunk: vec![0; READ_BYTES],
+            buffer: Vec::new(),
+            frame_items: 0,
+            frame_offset: 0,
+            frame_tags: Vec::new(),
         }
     }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.frame_items = 0;
+        self.frame_offset = 0;
+        self.frame_tags.clear();
+    }
+
+    /// Copy complete items from the receive buffer to the output.
+    fn output_items(&mut self) -> Result<usize> {
+        let item_size = std::mem::size_of::<T>();
+        let (out, mut tags) = self.output.slice_with_tags();
+        let out_bytes = unsafe {
+            std::slice::from_raw_parts_mut(out.as_mut_ptr() as *mut u8, std::mem::size_of_val(out))
+        };
+        let out_len = out.len();
+        let mut produced = 0;
+        let mut read = 0;
+
+        match self.framing {
+            TcpFraming::Raw => {
+                produced = std::cmp::min(out_len, self.buffer.len() / item_size);
+                read = produced * item_size;
+                out_bytes[..read].copy_from_slice(&self.buffer[..read]);
+            }
+            TcpFraming::LengthPrefixed => loop {
+                let buffer = &self.buffer[read..];
+                if self.frame_items == 0 {
+                    if buffer.len() < FRAME_HEADER_BYTES {
+                        break;
+                    }
+                    let (n_items, tag_bytes) = decode_frame_header(buffer)?;
+                    if buffer.len() < FRAME_HEADER_BYTES + tag_bytes {
+                        break;
+                    }
+                    self.frame_tags = decode_frame_tags(
+                        &buffer[FRAME_HEADER_BYTES..FRAME_HEADER_BYTES + tag_bytes],
+                    )?;
+                    self.frame_items = n_items;
+                    self.frame_offset = 0;
+                    read += FRAME_HEADER_BYTES + tag_bytes;
+                    continue;
+                }
+
+                let n = std::cmp::min(
+                    std::cmp::min(self.frame_items, buffer.len() / item_size),
+                    out_len - produced,
+                );
+                if n == 0 {
+                    break;
+                }
+                out_bytes[produced * item_size..(produced + n) * item_size]
+                    .copy_from_slice(&buffer[..n * item_size]);
+                for t in self
+                    .frame_tags
+                    .iter()
+                    .filter(|t| t.index >= self.frame_offset && t.index < self.frame_offset + n)
+                {
+                    tags.add_tag(produced + t.index - self.frame_offset, t.tag.clone());
+                }
+                produced += n;
+                read += n * item_size;
+                self.frame_items -= n;
+                self.frame_offset += n;
+            },
+        }
+
+        self.buffer.drain(..read);
+        self.output.produce(produced);
+        Ok(produced)
+    }
 }
 
 #[doc(hidden)]
@@ -47,48 +173,127 @@ where
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        if self.socket.is_none() {
-            let (socket, _) = self
-                .listener
-                .as_mut()
-                .context("no listener")?
-                .accept()
-                .await?;
-            self.socket = Some(socket);
-            debug!("tcp source accepted connection");
+        if self.closed {
+            if self.output_items()? > 0 {
+                io.call_again = true;
+            } else {
+                io.finished = true;
+            }
+            return Ok(());
         }
 
-        let out = self.output.slice();
-        if out.is_empty() {
-            return Ok(());
+        let mut socket = match &self.socket {
+            Some(s) => s.clone(),
+            None => match self.connector.poll(io, true)? {
+                Some(s) => {
+                    self.reset();
+                    self.socket = Some(s.clone());
+                    s
+                }
+                None => return Ok(()),
+            },
+        };
+
+        let mut lost = false;
+        if self.buffer.len() < MAX_BUFFER_BYTES {
+            // do not block, so that the block can still react to messages
+            match socket.read(&mut self.chunk).now_or_never() {
+                Some(Ok(0)) | Some(Err(_)) => lost = true,
+                Some(Ok(n)) => {
+                    self.buffer.extend_from_slice(&self.chunk[..n]);
+                    io.call_again = true;
+                }
+                None => {
+                    let s = socket.clone();
+                    io.block_on(async move {
+                        let mut b = [0u8; 1];
+                        let _ = s.peek(&mut b).await;
+                    });
+                }
+            }
         }
-        let out_len = out.len();
-        let ptr = out.as_mut_ptr() as *mut u8;
-        let byte_len = std::mem::size_of_val(out);
-        let data = unsafe { std::slice::from_raw_parts_mut(ptr, byte_len) };
-
-        match self
-            .socket
-            .as_mut()
-            .context("no socket")?
-            .read_exact(data)
-            .await
-        {
-            Ok(_) => {
-                debug!("tcp source read bytes {}", out.len());
-                self.output.produce(out_len);
+
+        match self.output_items() {
+            Ok(n) if n > 0 => io.call_again = true,
+            Ok(_) => {}
+            Err(e) => {
+                warn!("tcp source: invalid frame ({}), dropping connection", e);
+                self.reset();
+                lost = true;
             }
-            Err(_) => {
-                debug!("tcp source socket closed");
-                io.finished = true;
+        }
+
+        if lost {
+            debug!("tcp source socket closed");
+            self.socket = None;
+            if self.connector.reconnect() {
+                self.connector.lost();
+            } else {
+                self.closed = true;
             }
+            io.call_again = true;
         }
 
         Ok(())
     }
 
     async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
-        self.listener = Some(TcpListener::bind(self.bind.clone()).await?);
-        Ok(())
+        self.connector.init().await
+    }
+}
+
+/// Build a [TcpSource].
+pub struct TcpSourceBuilder<T, O = DefaultCpuWriter<T>>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    role: Role,
+    reconnect: bool,
+    framing: TcpFraming,
+    _type: std::marker::PhantomData<(T, O)>,
+}
+
+impl<T, O> TcpSourceBuilder<T, O>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    fn new(role: Role) -> Self {
+        Self {
+            role,
+            reconnect: false,
+            framing: TcpFraming::Raw,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    /// Listen for connections on `bind`, e.g., `0.0.0.0:2342`
+    pub fn server(bind: impl Into<String>) -> Self {
+        Self::new(Role::Server(bind.into()))
+    }
+
+    /// Connect to `remote`, e.g., `192.168.1.10:2342`
+    pub fn client(remote: impl Into<String>) -> Self {
+        Self::new(Role::Client(remote.into()))
+    }
+
+    /// Re-establish lost connections instead of finishing
+    #[must_use]
+    pub fn reconnect(mut self, reconnect: bool) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Set stream format
+    #[must_use]
+    pub fn framing(mut self, framing: TcpFraming) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Build TcpSource
+    pub fn build(self) -> TcpSource<T, O> {
+        TcpSource::with_options(self.role, self.reconnect, self.framing)
     }
 }
@@ -0,0 +1,149 @@
+use async_net::SocketAddr;
+use async_net::UdpSocket;
+use std::net::ToSocketAddrs;
+
+use crate::blocks::udp_source::SEQUENCE_HEADER_BYTES;
+use crate::prelude::*;
+
+/// Push samples into a UDP socket.
+///
+/// Samples are sent in datagrams of `payload_bytes` (rounded down to a multiple of the sample
+/// size). Only the last datagram, sent when the input is finished, can be shorter. Optionally,
+/// each datagram starts with an 8-byte sequence number header (little-endian `u64`), which
+/// [`UdpSource`](crate::blocks::UdpSource) uses to detect lost and reordered datagrams.
+///
+/// # Inputs
+///
+/// `input`: Samples to send
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::UdpSink;
+/// use futuresdr::num_complex::Complex32;
+///
+/// let plain = UdpSink::<Complex32>::new("127.0.0.1:1234", 1472);
+/// let sequenced = UdpSink::<Complex32>::with_sequence_numbers("127.0.0.1:1235", 1464);
+/// ```
+#[derive(Block)]
+pub struct UdpSink<T, I = DefaultCpuReader<T>>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    #[input]
+    input: I,
+    remote: SocketAddr,
+    payload_items: usize,
+    socket: Option<UdpSocket>,
+    sequence_numbers: bool,
+    seq: u64,
+    buffer: Vec<u8>,
+}
+
+impl<T, I> UdpSink<T, I>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    /// Create UDP Sink block
+    ///
+    /// ## Parameter
+    /// - `remote`: UDP socket address, e.g., `localhost:2342`
+    /// - `payload_bytes`: maximum size of the samples in a datagram
+    pub fn new(remote: impl AsRef<str>, payload_bytes: usize) -> Self {
+        let payload_items = payload_bytes / std::mem::size_of::<T>();
+        assert!(
+            payload_items > 0,
+            "UdpSink: payload smaller than one sample"
+        );
+        let mut input = I::default();
+        input.set_min_items(payload_items);
+        Self {
+            input,
+            remote: remote
+                .as_ref()
+                .to_socket_addrs()
+                .expect("could not resolve socket address")
+                .next()
+                .unwrap(),
+            payload_items,
+            socket: None,
+            sequence_numbers: false,
+            seq: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Create UDP Sink block that prepends a sequence number header to each datagram
+    ///
+    /// `payload_bytes` does not include the header.
+    pub fn with_sequence_numbers(remote: impl AsRef<str>, payload_bytes: usize) -> Self {
+        let mut s = Self::new(remote, payload_bytes);
+        s.sequence_numbers = true;
+        s.buffer =
+            Vec::with_capacity(s.payload_items * std::mem::size_of::<T>() + SEQUENCE_HEADER_BYTES);
+        s
+    }
+}
+
+#[doc(hidden)]
+impl<T, I> Kernel for UdpSink<T, I>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        // check before getting the slice, so that no samples arrive in between
+        let finished = self.input.finished();
+        let i = self.input.slice();
+        let i_len = i.len();
+        let bytes = unsafe {
+            std::slice::from_raw_parts(i.as_ptr() as *const u8, std::mem::size_of_val(i))
+        };
+        let socket = self.socket.as_ref().unwrap();
+
+        let mut consumed = 0;
+        while i_len - consumed >= self.payload_items || (finished && consumed < i_len) {
+            let n = std::cmp::min(self.payload_items, i_len - consumed);
+            let payload =
+                &bytes[consumed * std::mem::size_of::<T>()..][..n * std::mem::size_of::<T>()];
+
+            let datagram = if self.sequence_numbers {
+                self.buffer.clear();
+                self.buffer.extend_from_slice(&self.seq.to_le_bytes());
+                self.buffer.extend_from_slice(payload);
+                self.seq += 1;
+                &self.buffer[..]
+            } else {
+                payload
+            };
+
+            let s = socket.send_to(datagram, self.remote).await?;
+            assert_eq!(s, datagram.len());
+            consumed += n;
+        }
+
+        self.input.consume(consumed);
+
+        if finished && consumed == i_len {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _b: &mut BlockMeta) -> Result<()> {
+        let bind = if self.remote.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        self.socket = Some(UdpSocket::bind(bind).await?);
+        Ok(())
+    }
+}
@@ -1,9 +1,31 @@
 use anyhow::Context;
 use async_net::UdpSocket;
+use futures::FutureExt;
 
 use crate::prelude::*;
 
 /// Read samples from a UDP socket.
+///
+/// Optionally, datagrams start with an 8-byte sequence number header (little-endian `u64`,
+/// compatible with GNU Radio's UDP blocks in `seqnum` mode and [`UdpSink`](crate::blocks::UdpSink)).
+/// The header is stripped. Lost datagrams are counted and marked with a
+/// `Tag::NamedUsize("udp_lost", n)` on the first sample after the gap. Datagrams that arrive
+/// out of order, i.e., up to [`UdpSource::REORDER_WINDOW`] sequence numbers behind the
+/// expected one, are counted and dropped. A larger backward jump is considered a restart of
+/// the sender and resynchronizes the expected sequence number.
+///
+/// # Outputs
+///
+/// `output`: Received samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::UdpSource;
+/// use futuresdr::num_complex::Complex32;
+///
+/// let plain = UdpSource::<Complex32>::new("127.0.0.1:1234", 1472);
+/// let sequenced = UdpSource::<Complex32>::with_sequence_numbers("127.0.0.1:1235", 1464);
+/// ```
 #[derive(Block)]
 pub struct UdpSource<T, O = DefaultCpuWriter<T>>
 where
@@ -15,6 +37,11 @@ where
     bind: String,
     max_packet_bytes: usize,
     socket: Option<UdpSocket>,
+    sequence_numbers: bool,
+    buffer: Vec<u8>,
+    expected: Option<u64>,
+    lost: u64,
+    reordered: u64,
 }
 
 impl<T, O> UdpSource<T, O>
@@ -29,10 +56,43 @@ where
             bind: bind.into(),
             max_packet_bytes,
             socket: None,
+            sequence_numbers: false,
+            buffer: Vec::new(),
+            expected: None,
+            lost: 0,
+            reordered: 0,
         }
     }
+
+    /// Create UDP Source block for datagrams with sequence number header
+    ///
+    /// `max_payload_bytes` is the maximum size of a datagram without the header.
+    pub fn with_sequence_numbers(bind: impl Into<String>, max_payload_bytes: usize) -> Self {
+        let mut s = Self::new(bind, max_payload_bytes);
+        s.sequence_numbers = true;
+        s.buffer = vec![0; max_payload_bytes + SEQUENCE_HEADER_BYTES];
+        s
+    }
+
+    /// Maximum distance of a late datagram to the expected sequence number
+    ///
+    /// Datagrams further behind indicate a restart of the sender.
+    pub const REORDER_WINDOW: u64 = 64;
+
+    /// Number of lost datagrams (only with sequence numbers)
+    pub fn lost(&self) -> u64 {
+        self.lost
+    }
+
+    /// Number of datagrams received out of order and dropped (only with sequence numbers)
+    pub fn reordered(&self) -> u64 {
+        self.reordered
+    }
 }
 
+/// Size of the sequence number header.
+pub(super) const SEQUENCE_HEADER_BYTES: usize = 8;
+
 #[doc(hidden)]
 impl<T, O> Kernel for UdpSource<T, O>
 where
@@ -45,7 +105,8 @@ where
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        let out = self.output.slice();
+        let socket = self.socket.as_ref().context("no socket")?.clone();
+        let (out, mut tags) = self.output.slice_with_tags();
         let ptr = out.as_mut_ptr() as *mut u8;
         let byte_len = std::mem::size_of_val(out);
         let data = unsafe { std::slice::from_raw_parts_mut(ptr, byte_len) };
@@ -54,22 +115,67 @@ where
             return Ok(());
         }
 
-        match self
-            .socket
-            .as_ref()
-            .context("no socket")?
-            .recv_from(data)
-            .await
-        {
-            Ok((s, _)) => {
-                debug!("udp source read bytes {}", s);
-                self.output.produce(s / std::mem::size_of::<T>());
-            }
-            Err(_) => {
+        let buf = if self.sequence_numbers {
+            &mut self.buffer[..]
+        } else {
+            &mut data[..]
+        };
+
+        // do not block, so that the block can still react to messages
+        let s = match socket.recv_from(buf).now_or_never() {
+            Some(Ok((s, _))) => s,
+            Some(Err(_)) => {
                 debug!("udp source socket closed");
                 io.finished = true;
+                return Ok(());
+            }
+            None => {
+                io.block_on(async move {
+                    let mut b = [0u8; 1];
+                    let _ = socket.peek_from(&mut b).await;
+                });
+                return Ok(());
             }
+        };
+        debug!("udp source read bytes {}", s);
+        io.call_again = true;
+
+        if !self.sequence_numbers {
+            self.output.produce(s / std::mem::size_of::<T>());
+            return Ok(());
+        }
+
+        if s < SEQUENCE_HEADER_BYTES {
+            warn!("udp source: datagram shorter than sequence number header, dropping");
+            return Ok(());
         }
+        let seq = u64::from_le_bytes(self.buffer[..SEQUENCE_HEADER_BYTES].try_into().unwrap());
+        match self.expected {
+            Some(e) if seq < e && e - seq <= Self::REORDER_WINDOW => {
+                self.reordered += 1;
+                return Ok(());
+            }
+            Some(e) if seq < e => {
+                info!(
+                    "udp source: sequence number jumped back from {e} to {seq}, sender restarted"
+                );
+            }
+            Some(e) if seq > e => {
+                self.lost += seq - e;
+                tags.add_tag(
+                    0,
+                    Tag::NamedUsize("udp_lost".to_string(), (seq - e) as usize),
+                );
+            }
+            _ => {}
+        }
+        self.expected = Some(seq + 1);
+
+        let payload = &self.buffer[SEQUENCE_HEADER_BYTES..s];
+        let items = payload.len() / std::mem::size_of::<T>();
+        let bytes = items * std::mem::size_of::<T>();
+        data[..bytes].copy_from_slice(&payload[..bytes]);
+        self.output.produce(items);
 
         Ok(())
     }
@@ -0,0 +1,103 @@
+use anyhow::Context;
+use async_net::UdpSocket;
+use futures::FutureExt;
+
+use crate::prelude::*;
+use crate::runtime::PmtEncoding;
+
+/// Receive UDP datagrams and output them as [Blobs](crate::runtime::Pmt::Blob).
+///
+/// With an encoding (see [`UdpToBlob::with_encoding`]), datagrams are decoded into [`Pmts`](Pmt)
+/// instead, e.g., to receive messages from a [`BlobToUdp`](crate::blocks::BlobToUdp) block.
+/// Datagrams that cannot be decoded are dropped.
+///
+/// # Outputs
+///
+/// **Message**: `out`: One [`Pmt::Blob`] per datagram
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::UdpToBlob;
+///
+/// let src = UdpToBlob::new("127.0.0.1:2342", 1500);
+/// ```
+#[derive(Block)]
+#[message_outputs(out)]
+pub struct UdpToBlob {
+    bind: String,
+    socket: Option<UdpSocket>,
+    buffer: Vec<u8>,
+    encoding: Option<PmtEncoding>,
+}
+
+impl UdpToBlob {
+    /// Create [`UdpToBlob`] block
+    ///
+    /// ## Parameter
+    /// - `bind`: local UDP socket address, e.g., `0.0.0.0:2342`
+    /// - `max_packet_bytes`: maximum datagram size, longer datagrams are truncated
+    pub fn new(bind: impl Into<String>, max_packet_bytes: usize) -> Self {
+        Self {
+            bind: bind.into(),
+            socket: None,
+            buffer: vec![0; max_packet_bytes],
+            encoding: None,
+        }
+    }
+
+    /// Create [`UdpToBlob`] block that decodes datagrams into [`Pmts`](Pmt)
+    pub fn with_encoding(
+        bind: impl Into<String>,
+        max_packet_bytes: usize,
+        encoding: PmtEncoding,
+    ) -> Self {
+        Self {
+            encoding: Some(encoding),
+            ..Self::new(bind, max_packet_bytes)
+        }
+    }
+}
+
+#[doc(hidden)]
+impl Kernel for UdpToBlob {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let socket = self.socket.as_ref().context("no socket")?.clone();
+
+        // do not block, so that the block can still react to messages
+        match socket.recv_from(&mut self.buffer).now_or_never() {
+            Some(Ok((s, _))) => {
+                let data = &self.buffer[..s];
+                match self.encoding {
+                    Some(e) => match e.decode(data) {
+                        Ok(p) => mio.post("out", p).await?,
+                        Err(_) => warn!("UdpToBlob: dropping datagram that cannot be decoded"),
+                    },
+                    None => mio.post("out", Pmt::Blob(data.to_vec())).await?,
+                }
+                io.call_again = true;
+            }
+            Some(Err(_)) => {
+                debug!("udp to blob socket closed");
+                io.finished = true;
+            }
+            None => {
+                io.block_on(async move {
+                    let mut b = [0u8; 1];
+                    let _ = socket.peek_from(&mut b).await;
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        self.socket = Some(UdpSocket::bind(self.bind.clone()).await?);
+        Ok(())
+    }
+}
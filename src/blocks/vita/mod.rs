@@ -0,0 +1,23 @@
+//! ## [VITA-49](https://www.vita.com/) Radio Transport Blocks
+//!
+//! Signal data and IF context packets over UDP or TCP. Stream IDs, timestamps, and context
+//! packets are mapped to [`Tag`](crate::runtime::Tag)s.
+mod packet;
+pub use packet::Context;
+pub use packet::Format;
+pub use packet::Timestamp;
+pub use packet::Tsf;
+pub use packet::Tsi;
+
+mod sink;
+pub use sink::VitaSink;
+
+mod source;
+pub use source::VitaSource;
+
+/// Name of the `Tag::NamedUsize` with the stream ID of data packets.
+pub const STREAM_ID_TAG: &str = "vita_stream_id";
+/// Name of the `Tag::NamedAny` with the [`Timestamp`] of a data packet.
+pub const TIME_TAG: &str = "vita_time";
+/// Name of the `Tag::NamedAny` with the [`Context`] of a context packet.
+pub const CONTEXT_TAG: &str = "vita_context";
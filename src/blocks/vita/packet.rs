@@ -0,0 +1,387 @@
+use anyhow::Context as _;
+use anyhow::bail;
+
+use crate::num_complex::Complex32;
+
+/// Integer timestamp type (TSI field of the header)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Tsi {
+    /// No integer timestamp
+    #[default]
+    None,
+    /// Seconds since the UTC epoch
+    Utc,
+    /// Seconds since the GPS epoch
+    Gps,
+    /// Other, application-defined
+    Other,
+}
+
+/// Fractional timestamp type (TSF field of the header)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Tsf {
+    /// No fractional timestamp
+    #[default]
+    None,
+    /// Sample count
+    SampleCount,
+    /// Picoseconds
+    RealTime,
+    /// Free-running count
+    FreeRunning,
+}
+
+/// VITA-49 timestamp
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timestamp {
+    /// Type of the integer timestamp
+    pub tsi: Tsi,
+    /// Integer timestamp, only valid if `tsi` is not [`Tsi::None`]
+    pub integer: u32,
+    /// Type of the fractional timestamp
+    pub tsf: Tsf,
+    /// Fractional timestamp, only valid if `tsf` is not [`Tsf::None`]
+    pub fractional: u64,
+}
+
+impl Timestamp {
+    /// Whether the timestamp has an integer or fractional part
+    pub fn is_some(&self) -> bool {
+        self.tsi != Tsi::None || self.tsf != Tsf::None
+    }
+}
+
+/// VITA-49 IF context
+///
+/// Only the context fields up to the sample rate are supported. Frequencies and rates are in Hz,
+/// levels and gains in dB(m).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Context {
+    /// Stream ID of the context packet
+    pub stream_id: u32,
+    /// Timestamp of the context packet
+    pub timestamp: Timestamp,
+    /// Reference point ID
+    pub reference_point: Option<u32>,
+    /// Bandwidth
+    pub bandwidth: Option<f64>,
+    /// IF reference frequency
+    pub if_reference_frequency: Option<f64>,
+    /// RF reference frequency
+    pub rf_reference_frequency: Option<f64>,
+    /// RF reference frequency offset
+    pub rf_reference_frequency_offset: Option<f64>,
+    /// IF band offset
+    pub if_band_offset: Option<f64>,
+    /// Reference level
+    pub reference_level: Option<f32>,
+    /// Gain (sum of stage 1 and stage 2)
+    pub gain: Option<f32>,
+    /// Over-range count
+    pub over_range_count: Option<u32>,
+    /// Sample rate
+    pub sample_rate: Option<f64>,
+}
+
+/// Sample format of the signal data payload
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Complex 16-bit signed integers, one sample per word, scaled to `[-1, 1)`
+    #[default]
+    Sc16,
+    /// Complex 32-bit floats, one sample per two words
+    Cf32,
+}
+
+impl Format {
+    pub(super) fn bytes_per_sample(&self) -> usize {
+        match self {
+            Format::Sc16 => 4,
+            Format::Cf32 => 8,
+        }
+    }
+
+    pub(super) fn decode(&self, payload: &[u8], out: &mut Vec<Complex32>) {
+        match self {
+            Format::Sc16 => out.extend(payload.chunks_exact(4).map(|c| {
+                let re = i16::from_be_bytes([c[0], c[1]]);
+                let im = i16::from_be_bytes([c[2], c[3]]);
+                Complex32::new(re as f32 / 32768.0, im as f32 / 32768.0)
+            })),
+            Format::Cf32 => out.extend(payload.chunks_exact(8).map(|c| {
+                let re = f32::from_be_bytes(c[0..4].try_into().unwrap());
+                let im = f32::from_be_bytes(c[4..8].try_into().unwrap());
+                Complex32::new(re, im)
+            })),
+        }
+    }
+
+    pub(super) fn encode(&self, samples: &[Complex32], out: &mut Vec<u8>) {
+        match self {
+            Format::Sc16 => {
+                for s in samples {
+                    let re = (s.re * 32768.0).round().clamp(-32768.0, 32767.0) as i16;
+                    let im = (s.im * 32768.0).round().clamp(-32768.0, 32767.0) as i16;
+                    out.extend_from_slice(&re.to_be_bytes());
+                    out.extend_from_slice(&im.to_be_bytes());
+                }
+            }
+            Format::Cf32 => {
+                for s in samples {
+                    out.extend_from_slice(&s.re.to_be_bytes());
+                    out.extend_from_slice(&s.im.to_be_bytes());
+                }
+            }
+        }
+    }
+}
+
+/// Parsed VITA-49 packet
+#[derive(Debug)]
+pub(super) enum Packet<'a> {
+    Data {
+        stream_id: Option<u32>,
+        timestamp: Timestamp,
+        payload: &'a [u8],
+    },
+    Context(Context),
+}
+
+const IF_DATA: u32 = 0x0;
+const IF_DATA_SID: u32 = 0x1;
+const EXT_DATA: u32 = 0x2;
+const EXT_DATA_SID: u32 = 0x3;
+const IF_CONTEXT: u32 = 0x4;
+const EXT_CONTEXT: u32 = 0x5;
+
+/// Context indicator bit of the context field change indicator.
+const CIF_CHANGE: u32 = 1 << 31;
+/// Context indicator bits and field sizes (in words) of the supported fields, in packet order.
+const CIF_FIELDS: [(u32, usize); 10] = [
+    (30, 1),
+    (29, 2),
+    (28, 2),
+    (27, 2),
+    (26, 2),
+    (25, 2),
+    (24, 1),
+    (23, 1),
+    (22, 1),
+    (21, 2),
+];
+
+/// Size of a packet in bytes, as given by the first header word.
+pub(super) fn packet_bytes(header: [u8; 4]) -> usize {
+    (u32::from_be_bytes(header) & 0xffff) as usize * 4
+}
+
+fn word(buf: &[u8], i: usize) -> Result<u32, anyhow::Error> {
+    let b = buf.get(i * 4..i * 4 + 4).context("vita packet truncated")?;
+    Ok(u32::from_be_bytes(b.try_into().unwrap()))
+}
+
+fn double_word(buf: &[u8], i: usize) -> Result<u64, anyhow::Error> {
+    Ok(((word(buf, i)? as u64) << 32) | word(buf, i + 1)? as u64)
+}
+
+fn from_radix20(v: u64) -> f64 {
+    v as i64 as f64 / (1u64 << 20) as f64
+}
+
+fn to_radix20(v: f64) -> u64 {
+    (v * (1u64 << 20) as f64).round() as i64 as u64
+}
+
+fn from_radix7(v: u32) -> f32 {
+    v as u16 as i16 as f32 / 128.0
+}
+
+fn to_radix7(v: f32) -> u32 {
+    (v * 128.0).round() as i16 as u16 as u32
+}
+
+/// Parse a VITA-49 packet.
+pub(super) fn parse(buf: &[u8]) -> Result<Packet<'_>, anyhow::Error> {
+    let header = word(buf, 0)?;
+    let size = (header & 0xffff) as usize;
+    if size == 0 || buf.len() < size * 4 {
+        bail!("vita packet size mismatch");
+    }
+    let buf = &buf[..size * 4];
+    let packet_type = header >> 28;
+    let class_id = header & (1 << 27) != 0;
+    let mut i = 1;
+
+    let stream_id = match packet_type {
+        IF_DATA_SID | EXT_DATA_SID | IF_CONTEXT | EXT_CONTEXT => {
+            i += 1;
+            Some(word(buf, 1)?)
+        }
+        IF_DATA | EXT_DATA => None,
+        t => bail!("unsupported vita packet type {t}"),
+    };
+    if class_id {
+        i += 2;
+    }
+
+    let mut timestamp = Timestamp {
+        tsi: match (header >> 22) & 0x3 {
+            0 => Tsi::None,
+            1 => Tsi::Utc,
+            2 => Tsi::Gps,
+            _ => Tsi::Other,
+        },
+        tsf: match (header >> 20) & 0x3 {
+            0 => Tsf::None,
+            1 => Tsf::SampleCount,
+            2 => Tsf::RealTime,
+            _ => Tsf::FreeRunning,
+        },
+        ..Default::default()
+    };
+    if timestamp.tsi != Tsi::None {
+        timestamp.integer = word(buf, i)?;
+        i += 1;
+    }
+    if timestamp.tsf != Tsf::None {
+        timestamp.fractional = double_word(buf, i)?;
+        i += 2;
+    }
+
+    if packet_type >= IF_CONTEXT {
+        let mut context = Context {
+            stream_id: stream_id.unwrap(),
+            timestamp,
+            ..Default::default()
+        };
+        let cif = word(buf, i)?;
+        i += 1;
+        for (bit, words) in CIF_FIELDS {
+            if cif & (1 << bit) == 0 {
+                continue;
+            }
+            match bit {
+                30 => context.reference_point = Some(word(buf, i)?),
+                29 => context.bandwidth = Some(from_radix20(double_word(buf, i)?)),
+                28 => context.if_reference_frequency = Some(from_radix20(double_word(buf, i)?)),
+                27 => context.rf_reference_frequency = Some(from_radix20(double_word(buf, i)?)),
+                26 => {
+                    context.rf_reference_frequency_offset = Some(from_radix20(double_word(buf, i)?))
+                }
+                25 => context.if_band_offset = Some(from_radix20(double_word(buf, i)?)),
+                24 => context.reference_level = Some(from_radix7(word(buf, i)?)),
+                23 => {
+                    let g = word(buf, i)?;
+                    context.gain = Some(from_radix7(g) + from_radix7(g >> 16));
+                }
+                22 => context.over_range_count = Some(word(buf, i)?),
+                21 => context.sample_rate = Some(from_radix20(double_word(buf, i)?)),
+                _ => unreachable!(),
+            }
+            i += words;
+        }
+        return Ok(Packet::Context(context));
+    }
+
+    let trailer = header & (1 << 26) != 0;
+    let end = if trailer { size - 1 } else { size };
+    if end < i {
+        bail!("vita packet truncated");
+    }
+    Ok(Packet::Data {
+        stream_id,
+        timestamp,
+        payload: &buf[i * 4..end * 4],
+    })
+}
+
+fn header(packet_type: u32, timestamp: &Timestamp, count: u8, size: usize) -> u32 {
+    let tsi = match timestamp.tsi {
+        Tsi::None => 0,
+        Tsi::Utc => 1,
+        Tsi::Gps => 2,
+        Tsi::Other => 3,
+    };
+    let tsf = match timestamp.tsf {
+        Tsf::None => 0,
+        Tsf::SampleCount => 1,
+        Tsf::RealTime => 2,
+        Tsf::FreeRunning => 3,
+    };
+    (packet_type << 28) | (tsi << 22) | (tsf << 20) | ((count as u32 & 0xf) << 16) | size as u32
+}
+
+fn timestamp_words(timestamp: &Timestamp) -> usize {
+    let mut n = 0;
+    if timestamp.tsi != Tsi::None {
+        n += 1;
+    }
+    if timestamp.tsf != Tsf::None {
+        n += 2;
+    }
+    n
+}
+
+fn put_timestamp(timestamp: &Timestamp, out: &mut Vec<u8>) {
+    if timestamp.tsi != Tsi::None {
+        out.extend_from_slice(&timestamp.integer.to_be_bytes());
+    }
+    if timestamp.tsf != Tsf::None {
+        out.extend_from_slice(&timestamp.fractional.to_be_bytes());
+    }
+}
+
+/// Encode an IF data packet with stream ID.
+pub(super) fn encode_data(
+    stream_id: u32,
+    timestamp: &Timestamp,
+    count: u8,
+    format: Format,
+    samples: &[Complex32],
+    out: &mut Vec<u8>,
+) {
+    let payload_words = samples.len() * format.bytes_per_sample() / 4;
+    let size = 2 + timestamp_words(timestamp) + payload_words;
+    out.clear();
+    out.extend_from_slice(&header(IF_DATA_SID, timestamp, count, size).to_be_bytes());
+    out.extend_from_slice(&stream_id.to_be_bytes());
+    put_timestamp(timestamp, out);
+    format.encode(samples, out);
+}
+
+/// Encode an IF context packet.
+pub(super) fn encode_context(context: &Context, count: u8, out: &mut Vec<u8>) {
+    let u32_field = |v: Option<u32>| v.map(|v| v.to_be_bytes().to_vec());
+    let radix7_field = |v: Option<f32>| v.map(|v| to_radix7(v).to_be_bytes().to_vec());
+    let radix20_field = |v: Option<f64>| v.map(|v| to_radix20(v).to_be_bytes().to_vec());
+
+    let mut cif = CIF_CHANGE;
+    let mut fields = Vec::new();
+    for (bit, _) in CIF_FIELDS {
+        let field = match bit {
+            30 => u32_field(context.reference_point),
+            29 => radix20_field(context.bandwidth),
+            28 => radix20_field(context.if_reference_frequency),
+            27 => radix20_field(context.rf_reference_frequency),
+            26 => radix20_field(context.rf_reference_frequency_offset),
+            25 => radix20_field(context.if_band_offset),
+            24 => radix7_field(context.reference_level),
+            23 => radix7_field(context.gain),
+            22 => u32_field(context.over_range_count),
+            21 => radix20_field(context.sample_rate),
+            _ => unreachable!(),
+        };
+        if let Some(f) = field {
+            cif |= 1 << bit;
+            fields.extend_from_slice(&f);
+        }
+    }
+
+    let size = 3 + timestamp_words(&context.timestamp) + fields.len() / 4;
+    out.clear();
+    out.extend_from_slice(&header(IF_CONTEXT, &context.timestamp, count, size).to_be_bytes());
+    out.extend_from_slice(&context.stream_id.to_be_bytes());
+    put_timestamp(&context.timestamp, out);
+    out.extend_from_slice(&cif.to_be_bytes());
+    out.extend_from_slice(&fields);
+}
@@ -0,0 +1,274 @@
+use anyhow::Context as _;
+use async_net::SocketAddr;
+use async_net::TcpListener;
+use async_net::TcpStream;
+use async_net::UdpSocket;
+use futures::AsyncWriteExt;
+use std::net::ToSocketAddrs;
+
+use super::CONTEXT_TAG;
+use super::Context;
+use super::Format;
+use super::TIME_TAG;
+use super::Timestamp;
+use super::packet;
+use crate::prelude::*;
+
+enum Transport {
+    Udp(SocketAddr),
+    Tcp(String),
+}
+
+enum Socket {
+    Udp(UdpSocket),
+    Tcp(TcpListener, Option<TcpStream>),
+}
+
+/// Send samples as VITA-49 signal data packets.
+///
+/// Samples are sent in IF data packets with stream ID and up to `samples_per_packet` samples.
+/// Tags are mapped to packets:
+///
+/// - `Tag::NamedAny("vita_time", Box<Timestamp>)` starts a new data packet with this timestamp,
+/// - `Tag::NamedAny("vita_context", Box<Context>)` sends a context packet before the data packet
+///   that starts with the tagged sample. The stream ID of the context is replaced with the stream
+///   ID of the sink.
+///
+/// Tags of a [`VitaSource`](super::VitaSource) are, therefore, forwarded.
+///
+/// # Inputs
+///
+/// `input`: Samples to send
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::vita::Format;
+/// use futuresdr::blocks::vita::VitaSink;
+///
+/// let udp: VitaSink = VitaSink::udp("127.0.0.1:4991", 1, Format::Sc16, 360);
+/// let tcp: VitaSink = VitaSink::tcp("0.0.0.0:4991", 1, Format::Cf32, 180);
+/// ```
+#[derive(Block)]
+pub struct VitaSink<I = DefaultCpuReader<Complex32>>
+where
+    I: CpuBufferReader<Item = Complex32>,
+{
+    #[input]
+    input: I,
+    transport: Transport,
+    socket: Option<Socket>,
+    stream_id: u32,
+    format: Format,
+    samples_per_packet: usize,
+    data_count: u8,
+    context_count: u8,
+    buffer: Vec<u8>,
+}
+
+impl<I> VitaSink<I>
+where
+    I: CpuBufferReader<Item = Complex32>,
+{
+    fn new(
+        transport: Transport,
+        stream_id: u32,
+        format: Format,
+        samples_per_packet: usize,
+    ) -> Self {
+        assert!(samples_per_packet > 0, "VitaSink: empty packets");
+        assert!(
+            samples_per_packet * format.bytes_per_sample() / 4 < 65535 - 5,
+            "VitaSink: packets too large"
+        );
+        let mut input = I::default();
+        input.set_min_items(samples_per_packet);
+        Self {
+            input,
+            transport,
+            socket: None,
+            stream_id,
+            format,
+            samples_per_packet,
+            data_count: 0,
+            context_count: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Create VITA-49 Sink block, sending one packet per UDP datagram
+    ///
+    /// ## Parameter
+    /// - `remote`: UDP socket address, e.g., `localhost:4991`
+    /// - `stream_id`: stream ID of the packets
+    /// - `format`: sample format of the data packets
+    /// - `samples_per_packet`: maximum number of samples per data packet
+    pub fn udp(
+        remote: impl AsRef<str>,
+        stream_id: u32,
+        format: Format,
+        samples_per_packet: usize,
+    ) -> Self {
+        let remote = remote
+            .as_ref()
+            .to_socket_addrs()
+            .expect("could not resolve socket address")
+            .next()
+            .unwrap();
+        Self::new(
+            Transport::Udp(remote),
+            stream_id,
+            format,
+            samples_per_packet,
+        )
+    }
+
+    /// Create VITA-49 Sink block, serving the packet stream to one TCP client
+    ///
+    /// ## Parameter
+    /// - `bind`: local TCP socket address, e.g., `0.0.0.0:4991`
+    /// - `stream_id`: stream ID of the packets
+    /// - `format`: sample format of the data packets
+    /// - `samples_per_packet`: maximum number of samples per data packet
+    pub fn tcp(
+        bind: impl Into<String>,
+        stream_id: u32,
+        format: Format,
+        samples_per_packet: usize,
+    ) -> Self {
+        Self::new(
+            Transport::Tcp(bind.into()),
+            stream_id,
+            format,
+            samples_per_packet,
+        )
+    }
+}
+
+async fn send(socket: &mut Socket, transport: &Transport, packet: &[u8]) -> Result<()> {
+    match (socket, transport) {
+        (Socket::Udp(socket), Transport::Udp(remote)) => {
+            let s = socket.send_to(packet, remote).await?;
+            assert_eq!(s, packet.len());
+        }
+        (Socket::Tcp(_, Some(stream)), _) => {
+            stream.write_all(packet).await?;
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+
+#[doc(hidden)]
+impl<I> Kernel for VitaSink<I>
+where
+    I: CpuBufferReader<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let socket = self.socket.as_mut().context("no socket")?;
+        if let Socket::Tcp(listener, stream @ None) = socket {
+            let (s, _) = listener.accept().await?;
+            *stream = Some(s);
+            debug!("vita sink accepted connection");
+        }
+
+        // check before getting the slice, so that no samples arrive in between
+        let finished = self.input.finished();
+        let (i, tags) = self.input.slice_with_tags();
+        let i_len = i.len();
+
+        let mut consumed = 0;
+        while consumed < i_len {
+            // packets start at tagged samples
+            let cut = tags
+                .iter()
+                .filter(|t| {
+                    t.index > consumed
+                        && t.index < i_len
+                        && matches!(&t.tag, Tag::NamedAny(n, _) if n == TIME_TAG || n == CONTEXT_TAG)
+                })
+                .map(|t| t.index)
+                .min();
+            let mut end = consumed + self.samples_per_packet;
+            if let Some(c) = cut {
+                end = std::cmp::min(end, c);
+            }
+            if end > i_len {
+                if finished {
+                    end = i_len;
+                } else {
+                    break;
+                }
+            }
+
+            let mut timestamp = Timestamp::default();
+            for t in tags.iter().filter(|t| t.index == consumed) {
+                match &t.tag {
+                    Tag::NamedAny(n, a) if n == TIME_TAG => {
+                        if let Some(ts) = a.downcast_ref::<Timestamp>() {
+                            timestamp = *ts;
+                        }
+                    }
+                    Tag::NamedAny(n, a) if n == CONTEXT_TAG => {
+                        if let Some(c) = a.downcast_ref::<Context>() {
+                            let c = Context {
+                                stream_id: self.stream_id,
+                                ..c.clone()
+                            };
+                            packet::encode_context(&c, self.context_count, &mut self.buffer);
+                            self.context_count = self.context_count.wrapping_add(1);
+                            send(socket, &self.transport, &self.buffer).await?;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            packet::encode_data(
+                self.stream_id,
+                &timestamp,
+                self.data_count,
+                self.format,
+                &i[consumed..end],
+                &mut self.buffer,
+            );
+            self.data_count = self.data_count.wrapping_add(1);
+            send(socket, &self.transport, &self.buffer).await?;
+            consumed = end;
+        }
+
+        self.input.consume(consumed);
+
+        if finished && consumed == i_len {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        self.socket = Some(match &self.transport {
+            Transport::Udp(remote) => {
+                let bind = if remote.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                Socket::Udp(UdpSocket::bind(bind).await?)
+            }
+            Transport::Tcp(bind) => Socket::Tcp(TcpListener::bind(bind.clone()).await?, None),
+        });
+        Ok(())
+    }
+
+    async fn deinit(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        if let Some(Socket::Tcp(_, Some(stream))) = self.socket.as_mut() {
+            stream.close().await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+use anyhow::Context as _;
+use anyhow::bail;
+use async_net::TcpStream;
+use async_net::UdpSocket;
+use futures::AsyncReadExt;
+use futures::FutureExt;
+
+use super::CONTEXT_TAG;
+use super::Context;
+use super::Format;
+use super::STREAM_ID_TAG;
+use super::TIME_TAG;
+use super::packet;
+use super::packet::Packet;
+use crate::prelude::*;
+
+/// Maximum size of a VITA-49 packet.
+const MAX_PACKET_BYTES: usize = 65535 * 4;
+/// Size of a single read from a TCP socket.
+const READ_BYTES: usize = 1 << 16;
+
+enum Transport {
+    Udp(String),
+    Tcp(String),
+}
+
+enum Socket {
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+}
+
+/// Receive VITA-49 signal data and context packets.
+///
+/// The payload of signal data packets is output as [`Complex32`] samples. Stream information is
+/// mapped to tags, which are added to the first sample of the following data packet:
+///
+/// - `Tag::NamedUsize("vita_stream_id", id)`, when the stream ID of data packets changes,
+/// - `Tag::NamedAny("vita_time", Box<Timestamp>)`, for data packets with timestamp,
+/// - `Tag::NamedAny("vita_context", Box<Context>)`, for each context packet.
+///
+/// # Outputs
+///
+/// `output`: Received samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::vita::Format;
+/// use futuresdr::blocks::vita::VitaSource;
+///
+/// let udp: VitaSource = VitaSource::udp("0.0.0.0:4991", Format::Sc16);
+/// let tcp: VitaSource = VitaSource::tcp("192.168.10.2:4991", Format::Cf32);
+/// ```
+#[derive(Block)]
+pub struct VitaSource<O = DefaultCpuWriter<Complex32>>
+where
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    #[output]
+    output: O,
+    transport: Transport,
+    socket: Option<Socket>,
+    format: Format,
+    buffer: Vec<u8>,
+    chunk: Vec<u8>,
+    received: Vec<u8>,
+    samples: Vec<Complex32>,
+    offset: usize,
+    tags: Vec<Tag>,
+    stream_id: Option<u32>,
+}
+
+impl<O> VitaSource<O>
+where
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    fn new(transport: Transport, format: Format) -> Self {
+        Self {
+            output: O::default(),
+            transport,
+            socket: None,
+            format,
+            buffer: vec![0; MAX_PACKET_BYTES],
+            chunk: Vec::new(),
+            received: Vec::new(),
+            samples: Vec::new(),
+            offset: 0,
+            tags: Vec::new(),
+            stream_id: None,
+        }
+    }
+
+    /// Create VITA-49 Source block, receiving one packet per UDP datagram
+    ///
+    /// ## Parameter
+    /// - `bind`: local UDP socket address, e.g., `0.0.0.0:4991`
+    /// - `format`: sample format of the data packets
+    pub fn udp(bind: impl Into<String>, format: Format) -> Self {
+        Self::new(Transport::Udp(bind.into()), format)
+    }
+
+    /// Create VITA-49 Source block, connecting to a TCP server that sends a packet stream
+    ///
+    /// ## Parameter
+    /// - `remote`: TCP socket address of the server, e.g., `192.168.10.2:4991`
+    /// - `format`: sample format of the data packets
+    pub fn tcp(remote: impl Into<String>, format: Format) -> Self {
+        Self::new(Transport::Tcp(remote.into()), format)
+    }
+
+    /// Move the next complete packet from the TCP stream to the packet buffer.
+    fn next_tcp_packet(&mut self) -> Result<Option<usize>> {
+        if self.received.len() < 4 {
+            return Ok(None);
+        }
+        let header: [u8; 4] = self.received[..4].try_into().unwrap();
+        let len = packet::packet_bytes(header);
+        if len < 4 {
+            bail!("invalid packet size");
+        }
+        if self.received.len() < len {
+            return Ok(None);
+        }
+        self.buffer[..len].copy_from_slice(&self.received[..len]);
+        self.received.drain(..len);
+        Ok(Some(len))
+    }
+
+    fn handle(&mut self, len: usize) -> Result<()> {
+        match packet::parse(&self.buffer[..len])? {
+            Packet::Data {
+                stream_id,
+                timestamp,
+                payload,
+            } => {
+                if let Some(id) = stream_id
+                    && self.stream_id != stream_id
+                {
+                    self.stream_id = stream_id;
+                    self.tags
+                        .push(Tag::NamedUsize(STREAM_ID_TAG.to_string(), id as usize));
+                }
+                if timestamp.is_some() {
+                    self.tags
+                        .push(Tag::NamedAny(TIME_TAG.to_string(), Box::new(timestamp)));
+                }
+                self.samples.clear();
+                self.offset = 0;
+                self.format.decode(payload, &mut self.samples);
+            }
+            Packet::Context(context) => {
+                self.tags.push(Tag::NamedAny(
+                    CONTEXT_TAG.to_string(),
+                    Box::<Context>::new(context),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+impl<O> Kernel for VitaSource<O>
+where
+    O: CpuBufferWriter<Item = Complex32>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        if self.offset < self.samples.len() {
+            let (out, mut tags) = self.output.slice_with_tags();
+            if out.is_empty() {
+                return Ok(());
+            }
+            let n = std::cmp::min(out.len(), self.samples.len() - self.offset);
+            out[..n].copy_from_slice(&self.samples[self.offset..self.offset + n]);
+            for tag in self.tags.drain(..) {
+                tags.add_tag(0, tag);
+            }
+            self.offset += n;
+            self.output.produce(n);
+            io.call_again = true;
+            return Ok(());
+        }
+
+        let len = match self.socket.as_mut().context("no socket")? {
+            Socket::Udp(socket) => {
+                let socket = socket.clone();
+                // do not block, so that the block can still react to messages
+                match socket.recv_from(&mut self.buffer).now_or_never() {
+                    Some(Ok((s, _))) => s,
+                    Some(Err(_)) => {
+                        debug!("vita source socket closed");
+                        io.finished = true;
+                        return Ok(());
+                    }
+                    None => {
+                        io.block_on(async move {
+                            let mut b = [0u8; 1];
+                            let _ = socket.peek_from(&mut b).await;
+                        });
+                        return Ok(());
+                    }
+                }
+            }
+            Socket::Tcp(socket) => {
+                let mut socket = socket.clone();
+                match self.next_tcp_packet() {
+                    Ok(Some(len)) => len,
+                    Ok(None) => {
+                        // do not block, so that the block can still react to messages
+                        match socket.read(&mut self.chunk).now_or_never() {
+                            Some(Ok(0)) | Some(Err(_)) => {
+                                debug!("vita source socket closed");
+                                io.finished = true;
+                            }
+                            Some(Ok(n)) => {
+                                self.received.extend_from_slice(&self.chunk[..n]);
+                                io.call_again = true;
+                            }
+                            None => {
+                                io.block_on(async move {
+                                    let mut b = [0u8; 1];
+                                    let _ = socket.peek(&mut b).await;
+                                });
+                            }
+                        }
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        warn!("vita source: {e}, closing connection");
+                        io.finished = true;
+                        return Ok(());
+                    }
+                }
+            }
+        };
+
+        if let Err(e) = self.handle(len) {
+            warn!("vita source: dropping packet ({e})");
+        }
+        io.call_again = true;
+
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        self.socket = Some(match &self.transport {
+            Transport::Udp(bind) => Socket::Udp(UdpSocket::bind(bind.clone()).await?),
+            Transport::Tcp(remote) => {
+                self.chunk = vec![0; READ_BYTES];
+                Socket::Tcp(TcpStream::connect(remote.clone()).await?)
+            }
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+//! Receive path shared by [`WebsocketSource`](super::WebsocketSource) and
+//! [`WebsocketPmtSource`](super::WebsocketPmtSource).
+use async_io::Async;
+use async_tungstenite::WebSocketStream;
+use async_tungstenite::tungstenite::Message;
+use futures::StreamExt;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::prelude::*;
+
+type Connection = WebSocketStream<Async<TcpStream>>;
+
+/// Accepts WebSocket clients and receives their data messages.
+///
+/// Receiving happens in the future that the block waits on, so that the block can still react to
+/// messages. The future owns the connection and hands it back together with the next message.
+/// When a client disconnects, the next client is accepted.
+pub(super) struct Receiver {
+    port: u32,
+    listener: Option<Arc<Async<TcpListener>>>,
+    conn: Option<Connection>,
+    received: Arc<Mutex<Option<(Connection, Message)>>>,
+    in_flight: bool,
+}
+
+impl Receiver {
+    pub(super) fn new(port: u32) -> Self {
+        Self {
+            port,
+            listener: None,
+            conn: None,
+            received: Arc::new(Mutex::new(None)),
+            in_flight: false,
+        }
+    }
+
+    pub(super) fn init(&mut self) -> Result<()> {
+        self.listener = Some(Arc::new(Async::<TcpListener>::bind(
+            format!("0.0.0.0:{}", self.port).parse::<SocketAddr>()?,
+        )?));
+        Ok(())
+    }
+
+    /// Take the last received message, if any.
+    pub(super) fn try_recv(&mut self) -> Option<Message> {
+        let (conn, msg) = self.received.lock().unwrap().take()?;
+        self.conn = Some(conn);
+        self.in_flight = false;
+        Some(msg)
+    }
+
+    /// Wait for the next message, unless this is already in progress.
+    pub(super) fn receive(&mut self, io: &mut WorkIo) -> Result<()> {
+        if self.in_flight {
+            return Ok(());
+        }
+        let listener = self
+            .listener
+            .clone()
+            .ok_or_else(|| Error::RuntimeError("no listener".to_string()))?;
+        let conn = self.conn.take();
+        let received = self.received.clone();
+        self.in_flight = true;
+        io.block_on(async move {
+            let r = next_message(listener, conn).await;
+            *received.lock().unwrap() = Some(r);
+        });
+        Ok(())
+    }
+}
+
+async fn accept(listener: &Async<TcpListener>) -> Connection {
+    loop {
+        match listener.accept().await {
+            Ok((stream, addr)) => match async_tungstenite::accept_async(stream).await {
+                Ok(conn) => {
+                    debug!("Websocket Accepted client: {}", addr);
+                    return conn;
+                }
+                Err(e) => warn!("websocket: handshake failed ({})", e),
+            },
+            Err(e) => warn!("websocket: accept failed ({})", e),
+        }
+    }
+}
+
+async fn next_message(
+    listener: Arc<Async<TcpListener>>,
+    conn: Option<Connection>,
+) -> (Connection, Message) {
+    let mut conn = match conn {
+        Some(c) => c,
+        None => accept(&listener).await,
+    };
+    loop {
+        match conn.next().await {
+            Some(Ok(m @ (Message::Binary(_) | Message::Text(_)))) => return (conn, m),
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                debug!("websocket: client disconnected");
+                conn = accept(&listener).await;
+            }
+            Some(Ok(_)) => {}
+        }
+    }
+}
@@ -16,8 +16,13 @@ use std::task::Context;
 use std::task::Poll;
 
 use crate::prelude::*;
+use crate::runtime::PmtEncoding;
 
 /// Push Samples from PMTs in a WebSocket.
+///
+/// By default, vectors and blobs are sent as raw little-endian samples and strings as text. With
+/// an encoding (see [`WebsocketPmtSink::with_encoding`]), all PMTs are sent encoded, JSON as text
+/// and the binary formats as binary messages.
 #[derive(Block)]
 #[message_inputs(r#in)]
 pub struct WebsocketPmtSink {
@@ -25,6 +30,7 @@ pub struct WebsocketPmtSink {
     listener: Option<Arc<Async<TcpListener>>>,
     conn: Option<WsStream>,
     pmts: VecDeque<Pmt>,
+    encoding: Option<PmtEncoding>,
 }
 
 impl WebsocketPmtSink {
@@ -35,6 +41,28 @@ impl WebsocketPmtSink {
             listener: None,
             conn: None,
             pmts: VecDeque::new(),
+            encoding: None,
+        }
+    }
+
+    /// Create WebsocketPmtSink block that sends encoded PMTs
+    pub fn with_encoding(port: u32, encoding: PmtEncoding) -> Self {
+        Self {
+            encoding: Some(encoding),
+            ..Self::new(port)
+        }
+    }
+
+    fn encode(encoding: PmtEncoding, p: &Pmt) -> Option<Message> {
+        match encoding.encode(p) {
+            Ok(b) if encoding == PmtEncoding::Json => {
+                Some(Message::Text(String::from_utf8(b).ok()?.into()))
+            }
+            Ok(b) => Some(Message::Binary(b.into())),
+            Err(_) => {
+                warn!("WebsocketPmtSink: cannot encode {:?}", p);
+                None
+            }
         }
     }
 
@@ -66,8 +94,9 @@ impl Kernel for WebsocketPmtSink {
         _meta: &mut BlockMeta,
     ) -> Result<()> {
         if let Some(ref mut conn) = self.conn {
-            let msg = match self.pmts.pop_front() {
-                Some(Pmt::VecCF32(v)) => {
+            let msg = match (self.encoding, self.pmts.pop_front()) {
+                (Some(e), Some(p)) => Self::encode(e, &p),
+                (_, Some(Pmt::VecCF32(v))) => {
                     let v: Vec<u8> = v
                         .into_iter()
                         .flat_map(|f| {
@@ -83,7 +112,7 @@ impl Kernel for WebsocketPmtSink {
                         None
                     }
                 }
-                Some(Pmt::VecF32(v)) => {
+                (_, Some(Pmt::VecF32(v))) => {
                     let v: Vec<u8> = v
                         .into_iter()
                         .flat_map(|f| {
@@ -98,7 +127,7 @@ impl Kernel for WebsocketPmtSink {
                         None
                     }
                 }
-                Some(Pmt::VecU64(v)) => {
+                (_, Some(Pmt::VecU64(v))) => {
                     let v: Vec<u8> = v
                         .into_iter()
                         .flat_map(|f| {
@@ -113,13 +142,13 @@ impl Kernel for WebsocketPmtSink {
                         None
                     }
                 }
-                Some(Pmt::Blob(b)) => Some(Message::Binary(b.into())),
-                Some(Pmt::String(s)) => Some(Message::Text(s.into())),
-                Some(p) => {
+                (_, Some(Pmt::Blob(b))) => Some(Message::Binary(b.into())),
+                (_, Some(Pmt::String(s))) => Some(Message::Text(s.into())),
+                (_, Some(p)) => {
                     warn!("WebsocketPmtSink: unsupported PMT type {:?}", p);
                     None
                 }
-                None => None,
+                (_, None) => None,
             };
 
             if let Some(msg) = msg {
@@ -0,0 +1,75 @@
+use async_tungstenite::tungstenite::Message;
+
+use crate::blocks::websocket_connection::Receiver;
+use crate::prelude::*;
+use crate::runtime::PmtEncoding;
+
+/// Receive [`Pmt`]s from a WebSocket.
+///
+/// Listens on `0.0.0.0:port` and decodes each message of a client into a [`Pmt`]. Text messages
+/// are parsed as JSON in the serde representation of [`Pmt`], e.g., `{"F32": 1.0}` or `"Null"`.
+/// Binary messages are decoded with the configured [`PmtEncoding`], by default
+/// [`PmtEncoding::Cbor`]. Messages that cannot be decoded are dropped. When the client
+/// disconnects, the block waits for the next one.
+///
+/// # Outputs
+///
+/// **Message**: `out`: Received [`Pmt`]s
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::WebsocketPmtSource;
+/// use futuresdr::runtime::PmtEncoding;
+///
+/// let src = WebsocketPmtSource::new(9002);
+/// let gr = WebsocketPmtSource::with_encoding(9003, PmtEncoding::GnuRadio);
+/// ```
+#[derive(Block)]
+#[message_outputs(out)]
+pub struct WebsocketPmtSource {
+    receiver: Receiver,
+    encoding: PmtEncoding,
+}
+
+impl WebsocketPmtSource {
+    /// Create WebsocketPmtSource block
+    pub fn new(port: u32) -> Self {
+        Self::with_encoding(port, PmtEncoding::Cbor)
+    }
+
+    /// Create WebsocketPmtSource block, decoding binary messages with `encoding`
+    pub fn with_encoding(port: u32, encoding: PmtEncoding) -> Self {
+        Self {
+            receiver: Receiver::new(port),
+            encoding,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl Kernel for WebsocketPmtSource {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let pmt = match self.receiver.try_recv() {
+            Some(Message::Text(s)) => Some(PmtEncoding::Json.decode(s.as_bytes())),
+            Some(Message::Binary(b)) => Some(self.encoding.decode(&b)),
+            _ => None,
+        };
+        match pmt {
+            Some(Ok(p)) => mio.post("out", p).await?,
+            Some(Err(e)) => warn!("WebsocketPmtSource: dropping message ({})", e),
+            None => {}
+        }
+
+        self.receiver.receive(io)?;
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        self.receiver.init()
+    }
+}
@@ -0,0 +1,93 @@
+use async_tungstenite::tungstenite::Message;
+use std::marker::PhantomData;
+
+use crate::blocks::websocket_connection::Receiver;
+use crate::prelude::*;
+
+/// Maximum number of received bytes that are buffered in the block.
+const MAX_BUFFER_BYTES: usize = 1 << 20;
+
+/// Read samples from a WebSocket.
+///
+/// Listens on `0.0.0.0:port` and reads samples from binary messages of a client. Samples are
+/// expected in native byte order, i.e., the format of [`WebsocketSink`](crate::blocks::WebsocketSink).
+/// Samples may span message boundaries. When the client disconnects, the block waits for the
+/// next one.
+///
+/// # Outputs
+///
+/// `output`: Received samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::WebsocketSource;
+///
+/// let src = WebsocketSource::<f32>::new(9001);
+/// ```
+#[derive(Block)]
+pub struct WebsocketSource<T: CpuSample, O: CpuBufferWriter<Item = T> = DefaultCpuWriter<T>> {
+    #[output]
+    output: O,
+    receiver: Receiver,
+    buffer: Vec<u8>,
+    _p: PhantomData<T>,
+}
+
+impl<T, O> WebsocketSource<T, O>
+where
+    T: CpuSample,
+    O: CpuBufferWriter<Item = T>,
+{
+    /// Create WebsocketSource block
+    pub fn new(port: u32) -> Self {
+        Self {
+            output: O::default(),
+            receiver: Receiver::new(port),
+            buffer: Vec::new(),
+            _p: PhantomData,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, O> Kernel for WebsocketSource<T, O>
+where
+    T: CpuSample,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        match self.receiver.try_recv() {
+            Some(Message::Binary(b)) => self.buffer.extend_from_slice(&b),
+            Some(_) => warn!("WebsocketSource: ignoring non-binary message"),
+            None => {}
+        }
+
+        let item_size = std::mem::size_of::<T>();
+        let out = self.output.slice();
+        let n = std::cmp::min(out.len(), self.buffer.len() / item_size);
+        if n > 0 {
+            let out_bytes = unsafe {
+                std::slice::from_raw_parts_mut(out.as_mut_ptr() as *mut u8, n * item_size)
+            };
+            out_bytes.copy_from_slice(&self.buffer[..n * item_size]);
+            self.buffer.drain(..n * item_size);
+            self.output.produce(n);
+            io.call_again = true;
+        }
+
+        if self.buffer.len() < MAX_BUFFER_BYTES {
+            self.receiver.receive(io)?;
+        }
+
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        self.receiver.init()
+    }
+}
@@ -7,7 +7,14 @@ use futuredsp::prelude::*;
 use crate::prelude::*;
 
 /// Frequency Xlating FIR filter.
+///
+/// # Message Inputs
+///
+/// `taps`: Replace the low-pass taps (`Pmt::VecF32`) or return them for `Pmt::Null`
 #[derive(Block)]
+#[message_inputs(taps)]
+#[message_input_schema(taps, description = "Filter taps", kind = VecF32, getter = current_taps)]
+#[tag_propagation(one_to_one)]
 pub struct XlatingFir<I = DefaultCpuReader<Complex32>, O = DefaultCpuWriter<Complex32>>
 where
     I: CpuBufferReader<Item = Complex32>,
@@ -19,6 +26,10 @@ where
     output: O,
     filter: DecimatingFirFilter<Complex32, Complex32, Vec<Complex32>>,
     rotator: Rotator,
+    taps: Vec<f32>,
+    decimation: usize,
+    offset: f32,
+    sample_rate: f32,
 }
 
 impl<I, O> XlatingFir<I, O>
@@ -39,6 +50,27 @@ where
     pub fn with_taps(taps: Vec<f32>, decimation: usize, offset: f32, sample_rate: f32) -> Self {
         assert!(decimation != 0);
 
+        Self {
+            input: I::default(),
+            output: O::default(),
+            filter: Self::bandpass(&taps, decimation, offset, sample_rate),
+            rotator: Rotator::new(
+                -std::f32::consts::TAU * offset * decimation as f32 / sample_rate,
+            ),
+            taps,
+            decimation,
+            offset,
+            sample_rate,
+        }
+    }
+
+    /// Shift the low-pass taps to the offset frequency.
+    fn bandpass(
+        taps: &[f32],
+        decimation: usize,
+        offset: f32,
+        sample_rate: f32,
+    ) -> DecimatingFirFilter<Complex32, Complex32, Vec<Complex32>> {
         let mut bpf_taps = Vec::new();
         for (i, tap) in taps.iter().enumerate() {
             bpf_taps.push(
@@ -46,14 +78,28 @@ where
                     * tap,
             );
         }
+        DecimatingFirFilter::new(decimation, bpf_taps)
+    }
 
-        Self {
-            input: I::default(),
-            output: O::default(),
-            filter: DecimatingFirFilter::new(decimation, bpf_taps),
-            rotator: Rotator::new(
-                -std::f32::consts::TAU * offset * decimation as f32 / sample_rate,
-            ),
+    fn current_taps(&self) -> Pmt {
+        Pmt::VecF32(self.taps.clone())
+    }
+
+    async fn taps(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::VecF32(taps) if !taps.is_empty() => {
+                self.filter = Self::bandpass(&taps, self.decimation, self.offset, self.sample_rate);
+                self.taps = taps;
+                Ok(Pmt::Ok)
+            }
+            Pmt::Null => Ok(self.current_taps()),
+            _ => Ok(Pmt::InvalidValue),
         }
     }
 }
@@ -70,12 +116,13 @@ where
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        let i = self.input.slice();
-        let o = self.output.slice();
+        let (i, i_tags) = self.input.slice_with_tags();
+        let (o, mut o_tags) = self.output.slice_with_tags();
 
         let (consumed, produced, status) = self.filter.filter(i, o);
 
         self.rotator.rotate_inplace(&mut o[0..produced]);
+        Self::tag_propagation().forward(0, 0, i_tags, consumed, &mut o_tags, produced);
 
         self.input.consume(consumed);
         self.output.produce(produced);
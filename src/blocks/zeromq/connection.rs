@@ -0,0 +1,142 @@
+//! Socket setup and sample framing shared by the ZeroMQ blocks.
+use crate::blocks::zeromq::tag_header;
+use crate::prelude::*;
+
+/// Timeout of blocking socket operations, so that blocks can still react to messages.
+const TIMEOUT_MS: i32 = 100;
+
+/// Create a socket that binds or connects to `address`.
+pub(super) fn socket(kind: zmq::SocketType, address: &str, bind: bool) -> Result<zmq::Socket> {
+    let context = zmq::Context::new();
+    let socket = context.socket(kind)?;
+    socket.set_rcvtimeo(TIMEOUT_MS)?;
+    socket.set_sndtimeo(TIMEOUT_MS)?;
+    socket.set_linger(0)?;
+    if bind {
+        info!("ZeroMQ {:?} binding to {:?}", kind, address);
+        socket.bind(address)?;
+    } else {
+        info!("ZeroMQ {:?} connecting to {:?}", kind, address);
+        socket.connect(address)?;
+    }
+    Ok(socket)
+}
+
+/// Turn a timeout into `None`.
+pub(super) fn timeout<T>(r: zmq::Result<T>) -> Result<Option<T>> {
+    match r {
+        Ok(v) => Ok(Some(v)),
+        Err(zmq::Error::EAGAIN) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Builds messages from samples, optionally with a GNU Radio tag header.
+pub(super) struct Outgoing {
+    pass_tags: bool,
+    offset: u64,
+    buffer: Vec<u8>,
+}
+
+impl Outgoing {
+    pub(super) fn new(pass_tags: bool) -> Self {
+        Self {
+            pass_tags,
+            offset: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Message for the samples `items` with their `tags`.
+    pub(super) fn message<T>(&mut self, items: &[T], tags: &[ItemTag]) -> &[u8] {
+        let bytes = unsafe {
+            std::slice::from_raw_parts(items.as_ptr() as *const u8, std::mem::size_of_val(items))
+        };
+        if !self.pass_tags {
+            return bytes;
+        }
+        self.buffer.clear();
+        tag_header::encode(self.offset, items.len(), tags, &mut self.buffer);
+        self.buffer.extend_from_slice(bytes);
+        &self.buffer
+    }
+
+    /// The samples of the last message were sent.
+    pub(super) fn sent(&mut self, n_items: usize) {
+        self.offset += n_items as u64;
+    }
+}
+
+/// Buffers received samples that do not fit in the output buffer.
+pub(super) struct Incoming {
+    pass_tags: bool,
+    buffer: Vec<u8>,
+    tags: Vec<ItemTag>,
+}
+
+impl Incoming {
+    pub(super) fn new(pass_tags: bool) -> Self {
+        Self {
+            pass_tags,
+            buffer: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Add a received message.
+    pub(super) fn push<T>(&mut self, msg: &[u8]) {
+        let item_size = std::mem::size_of::<T>();
+        let mut data = msg;
+        if self.pass_tags {
+            match tag_header::decode(msg) {
+                Ok((len, tags)) => {
+                    let base = self.buffer.len() / item_size;
+                    self.tags.extend(tags.into_iter().map(|t| ItemTag {
+                        index: base + t.index,
+                        tag: t.tag,
+                    }));
+                    data = &msg[len..];
+                }
+                Err(e) => {
+                    warn!("ZeroMQ: dropping message ({})", e);
+                    return;
+                }
+            }
+        }
+        if data.len() % item_size != 0 {
+            warn!("ZeroMQ: message is not a multiple of the item size, truncating");
+        }
+        self.buffer
+            .extend_from_slice(&data[..data.len() / item_size * item_size]);
+    }
+
+    /// Copy buffered samples and tags to the output, returning the number of samples.
+    pub(super) fn write<T, O>(&mut self, output: &mut O) -> usize
+    where
+        T: Send + 'static,
+        O: CpuBufferWriter<Item = T>,
+    {
+        let item_size = std::mem::size_of::<T>();
+        let (o, mut tags) = output.slice_with_tags();
+        let n = std::cmp::min(o.len(), self.buffer.len() / item_size);
+        let out =
+            unsafe { std::slice::from_raw_parts_mut(o.as_mut_ptr() as *mut u8, n * item_size) };
+        out.copy_from_slice(&self.buffer[..n * item_size]);
+        self.buffer.drain(..n * item_size);
+
+        for t in self.tags.iter().filter(|t| t.index < n) {
+            tags.add_tag(t.index, t.tag.clone());
+        }
+        self.tags.retain(|t| t.index >= n);
+        for t in self.tags.iter_mut() {
+            t.index -= n;
+        }
+
+        output.produce(n);
+        n
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+
+use crate::blocks::zeromq::connection::socket;
+use crate::blocks::zeromq::connection::timeout;
+use crate::prelude::*;
+use crate::runtime::PmtEncoding;
+
+/// Socket type of a ZeroMQ [MessageSink].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageSinkSocket {
+    /// Publish to all subscribers (GNU Radio ZMQ PUB Message Sink)
+    Pub,
+    /// Push to the next peer (GNU Radio ZMQ PUSH Message Sink)
+    Push,
+    /// Send one message per request (GNU Radio ZMQ REP Message Sink)
+    Rep,
+}
+
+/// Send [`Pmt`]s to a [ZeroMQ](https://zeromq.org/) socket.
+///
+/// Binds to the address. By default, messages are serialized in GNU Radio's PMT format and are,
+/// therefore, compatible with GNU Radio's ZMQ message blocks. Other encodings can be selected with
+/// [`MessageSink::with_encoding`]. `Pmt`s that cannot be encoded, e.g., `Pmt::Any`, are rejected
+/// with `Pmt::InvalidValue`.
+///
+/// # Inputs
+///
+/// **Message**: `in`: [`Pmt`]s to send
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::zeromq::MessageSink;
+/// use futuresdr::blocks::zeromq::MessageSinkSocket;
+/// use futuresdr::runtime::PmtEncoding;
+///
+/// let snk = MessageSink::new("tcp://*:5558", MessageSinkSocket::Pub);
+/// let cbor = MessageSink::with_encoding("tcp://*:5559", MessageSinkSocket::Push, PmtEncoding::Cbor);
+/// ```
+#[derive(Block)]
+#[blocking]
+#[message_inputs(r#in)]
+pub struct MessageSink {
+    address: String,
+    kind: MessageSinkSocket,
+    encoding: PmtEncoding,
+    socket: Option<zmq::Socket>,
+    queue: VecDeque<Vec<u8>>,
+    requested: bool,
+    finished: bool,
+}
+
+impl MessageSink {
+    /// Create MessageSink block, using GNU Radio's PMT format
+    pub fn new(address: impl Into<String>, kind: MessageSinkSocket) -> Self {
+        Self::with_encoding(address, kind, PmtEncoding::GnuRadio)
+    }
+
+    /// Create MessageSink block, using the given encoding
+    pub fn with_encoding(
+        address: impl Into<String>,
+        kind: MessageSinkSocket,
+        encoding: PmtEncoding,
+    ) -> Self {
+        Self {
+            address: address.into(),
+            kind,
+            encoding,
+            socket: None,
+            queue: VecDeque::new(),
+            requested: false,
+            finished: false,
+        }
+    }
+
+    async fn r#in(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Finished => {
+                self.finished = true;
+                Ok(Pmt::Ok)
+            }
+            p => match self.encoding.encode(&p) {
+                Ok(b) => {
+                    self.queue.push_back(b);
+                    Ok(Pmt::Ok)
+                }
+                Err(_) => {
+                    warn!("ZeroMQ MessageSink: cannot encode {:?}", p);
+                    Ok(Pmt::InvalidValue)
+                }
+            },
+        }
+    }
+}
+
+#[doc(hidden)]
+impl Kernel for MessageSink {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let socket = self.socket.as_ref().unwrap();
+        while let Some(msg) = self.queue.front() {
+            if self.kind == MessageSinkSocket::Rep && !self.requested {
+                if timeout(socket.recv_bytes(0))?.is_none() {
+                    io.call_again = true;
+                    return Ok(());
+                }
+                self.requested = true;
+            }
+            if timeout(socket.send(&msg[..], 0))?.is_none() {
+                io.call_again = true;
+                return Ok(());
+            }
+            self.requested = false;
+            self.queue.pop_front();
+        }
+
+        if self.finished {
+            io.finished = true;
+        }
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        let kind = match self.kind {
+            MessageSinkSocket::Pub => zmq::PUB,
+            MessageSinkSocket::Push => zmq::PUSH,
+            MessageSinkSocket::Rep => zmq::REP,
+        };
+        self.socket = Some(socket(kind, &self.address, true)?);
+        Ok(())
+    }
+}
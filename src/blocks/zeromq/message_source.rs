@@ -0,0 +1,111 @@
+use crate::blocks::zeromq::connection::socket;
+use crate::blocks::zeromq::connection::timeout;
+use crate::prelude::*;
+use crate::runtime::PmtEncoding;
+
+/// Socket type of a ZeroMQ [MessageSource].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageSourceSocket {
+    /// Subscribe to all messages (GNU Radio ZMQ SUB Message Source)
+    Sub,
+    /// Pull from a PUSH socket (GNU Radio ZMQ PULL Message Source)
+    Pull,
+    /// Request one message at a time (GNU Radio ZMQ REQ Message Source)
+    Req,
+}
+
+/// Receive [`Pmt`]s from a [ZeroMQ](https://zeromq.org/) socket.
+///
+/// Connects to the address. By default, messages are expected in GNU Radio's PMT format, as sent
+/// by GNU Radio's ZMQ message blocks or a ZeroMQ [`MessageSink`](super::MessageSink). Other
+/// encodings can be selected with [`MessageSource::with_encoding`]. Messages that cannot be
+/// decoded are dropped.
+///
+/// # Outputs
+///
+/// **Message**: `out`: Received [`Pmt`]s
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::zeromq::MessageSource;
+/// use futuresdr::blocks::zeromq::MessageSourceSocket;
+///
+/// let src = MessageSource::new("tcp://127.0.0.1:5558", MessageSourceSocket::Sub);
+/// ```
+#[derive(Block)]
+#[blocking]
+#[message_outputs(out)]
+pub struct MessageSource {
+    address: String,
+    kind: MessageSourceSocket,
+    encoding: PmtEncoding,
+    socket: Option<zmq::Socket>,
+    requested: bool,
+}
+
+impl MessageSource {
+    /// Create MessageSource block, using GNU Radio's PMT format
+    pub fn new(address: impl Into<String>, kind: MessageSourceSocket) -> Self {
+        Self::with_encoding(address, kind, PmtEncoding::GnuRadio)
+    }
+
+    /// Create MessageSource block, using the given encoding
+    pub fn with_encoding(
+        address: impl Into<String>,
+        kind: MessageSourceSocket,
+        encoding: PmtEncoding,
+    ) -> Self {
+        Self {
+            address: address.into(),
+            kind,
+            encoding,
+            socket: None,
+            requested: false,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl Kernel for MessageSource {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        // called again in any case to poll the socket
+        io.call_again = true;
+        let socket = self.socket.as_ref().unwrap();
+
+        if self.kind == MessageSourceSocket::Req && !self.requested {
+            // ask for one message
+            if timeout(socket.send(&1i32.to_ne_bytes()[..], 0))?.is_none() {
+                return Ok(());
+            }
+            self.requested = true;
+        }
+
+        if let Some(msg) = timeout(socket.recv_bytes(0))? {
+            self.requested = false;
+            match self.encoding.decode(&msg) {
+                Ok(p) => mio.post("out", p).await?,
+                Err(_) => warn!("ZeroMQ MessageSource: dropping invalid message"),
+            }
+        }
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        let kind = match self.kind {
+            MessageSourceSocket::Sub => zmq::SUB,
+            MessageSourceSocket::Pull => zmq::PULL,
+            MessageSourceSocket::Req => zmq::REQ,
+        };
+        let socket = socket(kind, &self.address, false)?;
+        if self.kind == MessageSourceSocket::Sub {
+            socket.set_subscribe(b"")?;
+        }
+        self.socket = Some(socket);
+        Ok(())
+    }
+}
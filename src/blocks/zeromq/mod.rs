@@ -1,8 +1,39 @@
 //! ## [ZeroMQ](https://zeromq.org/) Blocks
+//!
+//! The stream blocks can optionally emit and parse GNU Radio's tag headers (`pass_tags`) and the
+//! message blocks use GNU Radio's PMT serialization, so that they interoperate with GNU Radio's
+//! ZMQ blocks.
+mod connection;
+mod tag_header;
+
+mod message_sink;
+pub use message_sink::MessageSink;
+pub use message_sink::MessageSinkSocket;
+
+mod message_source;
+pub use message_source::MessageSource;
+pub use message_source::MessageSourceSocket;
+
 mod pub_sink;
 pub use pub_sink::PubSink;
 pub use pub_sink::PubSinkBuilder;
 
+mod pull_source;
+pub use pull_source::PullSource;
+pub use pull_source::PullSourceBuilder;
+
+mod push_sink;
+pub use push_sink::PushSink;
+pub use push_sink::PushSinkBuilder;
+
+mod rep_sink;
+pub use rep_sink::RepSink;
+pub use rep_sink::RepSinkBuilder;
+
+mod req_source;
+pub use req_source::ReqSource;
+pub use req_source::ReqSourceBuilder;
+
 mod sub_source;
 pub use sub_source::SubSource;
 pub use sub_source::SubSourceBuilder;
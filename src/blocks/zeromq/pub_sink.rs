@@ -1,6 +1,24 @@
+use crate::blocks::zeromq::connection::Outgoing;
 use crate::prelude::*;
 
 /// Push samples into [ZeroMQ](https://zeromq.org/) socket.
+///
+/// Binds to the address. Compatible with GNU Radio's ZMQ SUB Source. With `pass_tags`, messages
+/// start with a GNU Radio tag header that carries the tags of the samples.
+///
+/// # Inputs
+///
+/// `input`: Samples to publish
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::zeromq::PubSinkBuilder;
+///
+/// let snk = PubSinkBuilder::<f32>::new()
+///     .address("tcp://*:5557")
+///     .pass_tags(true)
+///     .build();
+/// ```
 #[derive(Block)]
 pub struct PubSink<T, I = DefaultCpuReader<T>>
 where
@@ -12,6 +30,7 @@ where
     address: String,
     publisher: Option<zmq::Socket>,
     min_item: usize,
+    outgoing: Outgoing,
 }
 
 impl<T, I> PubSink<T, I>
@@ -19,13 +38,14 @@ where
     T: Send + 'static,
     I: CpuBufferReader<Item = T>,
 {
-    /// Create PubSink
+    /// Create PubSink without tag headers, see [PubSinkBuilder::pass_tags]
     pub fn new(address: impl Into<String>, min_item: usize) -> Self {
         Self {
             input: I::default(),
             address: address.into(),
             publisher: None,
             min_item,
+            outgoing: Outgoing::new(false),
         }
     }
 }
@@ -42,15 +62,13 @@ where
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        let i = self.input.slice();
+        let (i, tags) = self.input.slice_with_tags();
 
         let n = i.len();
         if n > 0 && n > self.min_item {
-            let i = self.input.slice();
-            let ptr = i.as_ptr() as *const u8;
-            let byte_len = std::mem::size_of_val(i);
-            let data = unsafe { std::slice::from_raw_parts(ptr, byte_len) };
-            self.publisher.as_mut().unwrap().send(data, 0).unwrap();
+            let msg = self.outgoing.message(i, tags);
+            self.publisher.as_mut().unwrap().send(msg, 0)?;
+            self.outgoing.sent(n);
             self.input.consume(n);
         }
 
@@ -82,6 +100,7 @@ where
     _type: std::marker::PhantomData<I>,
     /// Minimum number of items per send
     min_item: usize,
+    pass_tags: bool,
 }
 
 impl<T, I> PubSinkBuilder<T, I>
@@ -95,6 +114,7 @@ where
             address: "tcp://*:5555".into(),
             _type: std::marker::PhantomData,
             min_item: 1,
+            pass_tags: false,
         }
     }
 
@@ -111,9 +131,18 @@ where
         self
     }
 
+    /// Prepend messages with a GNU Radio tag header
+    #[must_use]
+    pub fn pass_tags(mut self, pass_tags: bool) -> Self {
+        self.pass_tags = pass_tags;
+        self
+    }
+
     /// Build PubSink
     pub fn build(self) -> PubSink<T, I> {
-        PubSink::<T, I>::new(self.address, self.min_item)
+        let mut snk = PubSink::<T, I>::new(self.address, self.min_item);
+        snk.outgoing = Outgoing::new(self.pass_tags);
+        snk
     }
 }
 
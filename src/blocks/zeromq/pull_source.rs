@@ -0,0 +1,143 @@
+use crate::blocks::zeromq::connection::Incoming;
+use crate::blocks::zeromq::connection::socket;
+use crate::blocks::zeromq::connection::timeout;
+use crate::prelude::*;
+
+/// Read samples from a [ZeroMQ](https://zeromq.org/) PULL socket.
+///
+/// Connects to the address. Compatible with GNU Radio's ZMQ PUSH Sink. With `pass_tags`, messages
+/// are expected to start with a GNU Radio tag header, which is converted to tags.
+///
+/// # Outputs
+///
+/// `output`: Received samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::zeromq::PullSourceBuilder;
+///
+/// let src = PullSourceBuilder::<f32>::new()
+///     .address("tcp://127.0.0.1:5556")
+///     .pass_tags(true)
+///     .build();
+/// ```
+#[derive(Block)]
+#[blocking]
+pub struct PullSource<T, O = DefaultCpuWriter<T>>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    #[output]
+    output: O,
+    address: String,
+    socket: Option<zmq::Socket>,
+    incoming: Incoming,
+}
+
+impl<T, O> PullSource<T, O>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    /// Create PullSource block
+    pub fn new(address: impl Into<String>, pass_tags: bool) -> Self {
+        Self {
+            output: O::default(),
+            address: address.into(),
+            socket: None,
+            incoming: Incoming::new(pass_tags),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, O> Kernel for PullSource<T, O>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        if self.incoming.is_empty() {
+            match timeout(self.socket.as_ref().unwrap().recv_bytes(0))? {
+                Some(msg) => self.incoming.push::<T>(&msg),
+                None => {
+                    io.call_again = true;
+                    return Ok(());
+                }
+            }
+        }
+
+        self.incoming.write(&mut self.output);
+        if self.incoming.is_empty() {
+            io.call_again = true;
+        }
+
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        self.socket = Some(socket(zmq::PULL, &self.address, false)?);
+        Ok(())
+    }
+}
+
+/// Build a ZeroMQ [PullSource].
+pub struct PullSourceBuilder<T, O = DefaultCpuWriter<T>>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    address: String,
+    pass_tags: bool,
+    _type: std::marker::PhantomData<O>,
+}
+
+impl<T, O> PullSourceBuilder<T, O>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    /// Create PullSource builder
+    pub fn new() -> Self {
+        PullSourceBuilder {
+            address: "tcp://127.0.0.1:5555".into(),
+            pass_tags: false,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    /// Remote socket address
+    #[must_use]
+    pub fn address(mut self, address: &str) -> Self {
+        self.address = address.to_string();
+        self
+    }
+
+    /// Parse GNU Radio tag headers
+    #[must_use]
+    pub fn pass_tags(mut self, pass_tags: bool) -> Self {
+        self.pass_tags = pass_tags;
+        self
+    }
+
+    /// Build PullSource
+    pub fn build(self) -> PullSource<T, O> {
+        PullSource::<T, O>::new(self.address, self.pass_tags)
+    }
+}
+
+impl<T, O> Default for PullSourceBuilder<T, O>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
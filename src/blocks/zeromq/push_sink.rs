@@ -0,0 +1,147 @@
+use crate::blocks::zeromq::connection::Outgoing;
+use crate::blocks::zeromq::connection::socket;
+use crate::blocks::zeromq::connection::timeout;
+use crate::prelude::*;
+
+/// Push samples into a [ZeroMQ](https://zeromq.org/) PUSH socket.
+///
+/// Binds to the address. Compatible with GNU Radio's ZMQ PULL Source. With `pass_tags`, messages
+/// start with a GNU Radio tag header that carries the tags of the samples.
+///
+/// # Inputs
+///
+/// `input`: Samples to send
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::zeromq::PushSinkBuilder;
+///
+/// let snk = PushSinkBuilder::<f32>::new()
+///     .address("tcp://*:5556")
+///     .pass_tags(true)
+///     .build();
+/// ```
+#[derive(Block)]
+#[blocking]
+pub struct PushSink<T, I = DefaultCpuReader<T>>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    #[input]
+    input: I,
+    address: String,
+    socket: Option<zmq::Socket>,
+    outgoing: Outgoing,
+}
+
+impl<T, I> PushSink<T, I>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    /// Create PushSink
+    pub fn new(address: impl Into<String>, pass_tags: bool) -> Self {
+        Self {
+            input: I::default(),
+            address: address.into(),
+            socket: None,
+            outgoing: Outgoing::new(pass_tags),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, I> Kernel for PushSink<T, I>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let finished = self.input.finished();
+        let (i, tags) = self.input.slice_with_tags();
+        let n = i.len();
+
+        if n > 0 {
+            let msg = self.outgoing.message(i, tags);
+            if timeout(self.socket.as_ref().unwrap().send(msg, 0))?.is_none() {
+                // no peer, try again
+                io.call_again = true;
+                return Ok(());
+            }
+            self.outgoing.sent(n);
+            self.input.consume(n);
+        }
+
+        if finished {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        self.socket = Some(socket(zmq::PUSH, &self.address, true)?);
+        Ok(())
+    }
+}
+
+/// Build a ZeroMQ [PushSink].
+pub struct PushSinkBuilder<T, I = DefaultCpuReader<T>>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    address: String,
+    pass_tags: bool,
+    _type: std::marker::PhantomData<I>,
+}
+
+impl<T, I> PushSinkBuilder<T, I>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    /// Create PushSink builder
+    pub fn new() -> Self {
+        PushSinkBuilder {
+            address: "tcp://*:5555".into(),
+            pass_tags: false,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    /// Local socket address
+    #[must_use]
+    pub fn address(mut self, address: &str) -> Self {
+        self.address = address.to_string();
+        self
+    }
+
+    /// Prepend messages with a GNU Radio tag header
+    #[must_use]
+    pub fn pass_tags(mut self, pass_tags: bool) -> Self {
+        self.pass_tags = pass_tags;
+        self
+    }
+
+    /// Build PushSink
+    pub fn build(self) -> PushSink<T, I> {
+        PushSink::<T, I>::new(self.address, self.pass_tags)
+    }
+}
+
+impl<T, I> Default for PushSinkBuilder<T, I>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
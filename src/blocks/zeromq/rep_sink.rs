@@ -0,0 +1,171 @@
+use anyhow::bail;
+
+use crate::blocks::zeromq::connection::Outgoing;
+use crate::blocks::zeromq::connection::socket;
+use crate::blocks::zeromq::connection::timeout;
+use crate::prelude::*;
+
+/// Serve samples on a [ZeroMQ](https://zeromq.org/) REP socket.
+///
+/// Binds to the address and answers each request with at most the requested number of samples.
+/// A request is the number of samples as native-endian `u32`. Compatible with GNU Radio's ZMQ REQ
+/// Source. With `pass_tags`, replies start with a GNU Radio tag header that carries the tags of
+/// the samples.
+///
+/// # Inputs
+///
+/// `input`: Samples to send
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::zeromq::RepSinkBuilder;
+///
+/// let snk = RepSinkBuilder::<f32>::new()
+///     .address("tcp://*:5557")
+///     .build();
+/// ```
+#[derive(Block)]
+#[blocking]
+pub struct RepSink<T, I = DefaultCpuReader<T>>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    #[input]
+    input: I,
+    address: String,
+    socket: Option<zmq::Socket>,
+    outgoing: Outgoing,
+    requested: Option<usize>,
+}
+
+impl<T, I> RepSink<T, I>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    /// Create RepSink
+    pub fn new(address: impl Into<String>, pass_tags: bool) -> Self {
+        Self {
+            input: I::default(),
+            address: address.into(),
+            socket: None,
+            outgoing: Outgoing::new(pass_tags),
+            requested: None,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, I> Kernel for RepSink<T, I>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let socket = self.socket.as_ref().unwrap();
+        let finished = self.input.finished();
+        let (i, tags) = self.input.slice_with_tags();
+
+        if i.is_empty() {
+            if finished {
+                io.finished = true;
+            }
+            return Ok(());
+        }
+
+        let requested = match self.requested {
+            Some(r) => r,
+            None => match timeout(socket.recv_bytes(0))? {
+                Some(req) if req.len() >= 4 => {
+                    u32::from_ne_bytes(req[0..4].try_into().unwrap()) as usize
+                }
+                Some(_) => bail!("ZeroMQ RepSink: invalid request"),
+                None => {
+                    io.call_again = true;
+                    return Ok(());
+                }
+            },
+        };
+
+        // a request must be answered before the next one can be received
+        let n = std::cmp::min(i.len(), requested);
+        let msg = self.outgoing.message(&i[..n], tags);
+        if timeout(socket.send(msg, 0))?.is_none() {
+            self.requested = Some(requested);
+            io.call_again = true;
+            return Ok(());
+        }
+        self.requested = None;
+        self.outgoing.sent(n);
+        self.input.consume(n);
+        io.call_again = true;
+
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        self.socket = Some(socket(zmq::REP, &self.address, true)?);
+        Ok(())
+    }
+}
+
+/// Build a ZeroMQ [RepSink].
+pub struct RepSinkBuilder<T, I = DefaultCpuReader<T>>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    address: String,
+    pass_tags: bool,
+    _type: std::marker::PhantomData<I>,
+}
+
+impl<T, I> RepSinkBuilder<T, I>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    /// Create RepSink builder
+    pub fn new() -> Self {
+        RepSinkBuilder {
+            address: "tcp://*:5555".into(),
+            pass_tags: false,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    /// Local socket address
+    #[must_use]
+    pub fn address(mut self, address: &str) -> Self {
+        self.address = address.to_string();
+        self
+    }
+
+    /// Prepend replies with a GNU Radio tag header
+    #[must_use]
+    pub fn pass_tags(mut self, pass_tags: bool) -> Self {
+        self.pass_tags = pass_tags;
+        self
+    }
+
+    /// Build RepSink
+    pub fn build(self) -> RepSink<T, I> {
+        RepSink::<T, I>::new(self.address, self.pass_tags)
+    }
+}
+
+impl<T, I> Default for RepSinkBuilder<T, I>
+where
+    T: Send + 'static,
+    I: CpuBufferReader<Item = T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
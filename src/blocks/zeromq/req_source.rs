@@ -0,0 +1,159 @@
+use crate::blocks::zeromq::connection::Incoming;
+use crate::blocks::zeromq::connection::socket;
+use crate::blocks::zeromq::connection::timeout;
+use crate::prelude::*;
+
+/// Request samples from a [ZeroMQ](https://zeromq.org/) REP socket.
+///
+/// Connects to the address and requests as many samples as fit in the output buffer. A request
+/// is the number of samples as native-endian `u32`. Compatible with GNU Radio's ZMQ REP Sink. With
+/// `pass_tags`, replies are expected to start with a GNU Radio tag header, which is converted to
+/// tags.
+///
+/// # Outputs
+///
+/// `output`: Received samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::zeromq::ReqSourceBuilder;
+///
+/// let src = ReqSourceBuilder::<f32>::new()
+///     .address("tcp://127.0.0.1:5557")
+///     .build();
+/// ```
+#[derive(Block)]
+#[blocking]
+pub struct ReqSource<T, O = DefaultCpuWriter<T>>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    #[output]
+    output: O,
+    address: String,
+    socket: Option<zmq::Socket>,
+    incoming: Incoming,
+    requested: bool,
+}
+
+impl<T, O> ReqSource<T, O>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    /// Create ReqSource block
+    pub fn new(address: impl Into<String>, pass_tags: bool) -> Self {
+        Self {
+            output: O::default(),
+            address: address.into(),
+            socket: None,
+            incoming: Incoming::new(pass_tags),
+            requested: false,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, O> Kernel for ReqSource<T, O>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        if !self.incoming.is_empty() {
+            self.incoming.write(&mut self.output);
+            if !self.incoming.is_empty() {
+                return Ok(());
+            }
+        }
+
+        let socket = self.socket.as_ref().unwrap();
+        if !self.requested {
+            let space = self.output.slice().len();
+            if space == 0 {
+                return Ok(());
+            }
+            let req = u32::try_from(space).unwrap_or(u32::MAX).to_ne_bytes();
+            if timeout(socket.send(&req[..], 0))?.is_none() {
+                io.call_again = true;
+                return Ok(());
+            }
+            self.requested = true;
+        }
+
+        if let Some(msg) = timeout(socket.recv_bytes(0))? {
+            self.requested = false;
+            self.incoming.push::<T>(&msg);
+            self.incoming.write(&mut self.output);
+        }
+        io.call_again = true;
+
+        Ok(())
+    }
+
+    async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
+        self.socket = Some(socket(zmq::REQ, &self.address, false)?);
+        Ok(())
+    }
+}
+
+/// Build a ZeroMQ [ReqSource].
+pub struct ReqSourceBuilder<T, O = DefaultCpuWriter<T>>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    address: String,
+    pass_tags: bool,
+    _type: std::marker::PhantomData<O>,
+}
+
+impl<T, O> ReqSourceBuilder<T, O>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    /// Create ReqSource builder
+    pub fn new() -> Self {
+        ReqSourceBuilder {
+            address: "tcp://127.0.0.1:5555".into(),
+            pass_tags: false,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    /// Remote socket address
+    #[must_use]
+    pub fn address(mut self, address: &str) -> Self {
+        self.address = address.to_string();
+        self
+    }
+
+    /// Parse GNU Radio tag headers
+    #[must_use]
+    pub fn pass_tags(mut self, pass_tags: bool) -> Self {
+        self.pass_tags = pass_tags;
+        self
+    }
+
+    /// Build ReqSource
+    pub fn build(self) -> ReqSource<T, O> {
+        ReqSource::<T, O>::new(self.address, self.pass_tags)
+    }
+}
+
+impl<T, O> Default for ReqSourceBuilder<T, O>
+where
+    T: Send + 'static,
+    O: CpuBufferWriter<Item = T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,7 +1,29 @@
+use crate::blocks::zeromq::connection::Incoming;
+use crate::blocks::zeromq::connection::socket;
+use crate::blocks::zeromq::connection::timeout;
 use crate::prelude::*;
 
 /// Read samples from [ZeroMQ](https://zeromq.org/) socket.
+///
+/// Connects to the address and subscribes to all messages. Compatible with GNU Radio's ZMQ PUB
+/// Sink. With `pass_tags`, messages are expected to start with a GNU Radio tag header, which is
+/// converted to tags.
+///
+/// # Outputs
+///
+/// `output`: Received samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::zeromq::SubSourceBuilder;
+///
+/// let src = SubSourceBuilder::<f32>::new()
+///     .address("tcp://127.0.0.1:5557")
+///     .pass_tags(true)
+///     .build();
+/// ```
 #[derive(Block)]
+#[blocking]
 pub struct SubSource<T, O = DefaultCpuWriter<T>>
 where
     T: Send + 'static,
@@ -11,6 +33,7 @@ where
     output: O,
     address: String,
     receiver: Option<zmq::Socket>,
+    incoming: Incoming,
 }
 
 impl<T, O> SubSource<T, O>
@@ -18,12 +41,13 @@ where
     T: Send + 'static,
     O: CpuBufferWriter<Item = T>,
 {
-    /// Create SubSource block
+    /// Create SubSource block without tag headers, see [SubSourceBuilder::pass_tags]
     pub fn new(address: impl Into<String>) -> Self {
         Self {
             output: O::default(),
             address: address.into(),
             receiver: None,
+            incoming: Incoming::new(false),
         }
     }
 }
@@ -36,31 +60,31 @@ where
 {
     async fn work(
         &mut self,
-        _io: &mut WorkIo,
+        io: &mut WorkIo,
         _mio: &mut MessageOutputs,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        let o = self.output.slice();
-        let ptr = o.as_ptr() as *mut u8;
-        let byte_len = std::mem::size_of_val(o);
-        let buffer = unsafe { std::slice::from_raw_parts_mut(ptr, byte_len) };
+        if self.incoming.is_empty() {
+            match timeout(self.receiver.as_ref().unwrap().recv_bytes(0))? {
+                Some(msg) => self.incoming.push::<T>(&msg),
+                None => {
+                    io.call_again = true;
+                    return Ok(());
+                }
+            }
+        }
 
-        let n_bytes = self.receiver.as_mut().unwrap().recv_into(buffer, 0)?;
-        debug_assert_eq!(o.len() % std::mem::size_of::<T>(), 0);
-        let n = n_bytes / std::mem::size_of::<T>();
+        let n = self.incoming.write(&mut self.output);
         debug!("SubSource received {}", n);
-        self.output.produce(n);
+        if self.incoming.is_empty() {
+            io.call_again = true;
+        }
 
         Ok(())
     }
 
     async fn init(&mut self, _mio: &mut MessageOutputs, _meta: &mut BlockMeta) -> Result<()> {
-        debug!("SubSource Init");
-
-        let context = zmq::Context::new();
-        let receiver = context.socket(zmq::SUB).unwrap();
-        info!("SubSource Connecting to {:?}", self.address);
-        receiver.connect(&self.address)?;
+        let receiver = socket(zmq::SUB, &self.address, false)?;
         receiver.set_subscribe(b"")?;
         self.receiver = Some(receiver);
         Ok(())
@@ -74,6 +98,7 @@ where
     O: CpuBufferWriter<Item = T>,
 {
     address: String,
+    pass_tags: bool,
     _type: std::marker::PhantomData<O>,
 }
 
@@ -86,6 +111,7 @@ where
     pub fn new() -> Self {
         SubSourceBuilder {
             address: "tcp://*:5555".into(),
+            pass_tags: false,
             _type: std::marker::PhantomData,
         }
     }
@@ -97,9 +123,18 @@ where
         self
     }
 
+    /// Parse GNU Radio tag headers
+    #[must_use]
+    pub fn pass_tags(mut self, pass_tags: bool) -> Self {
+        self.pass_tags = pass_tags;
+        self
+    }
+
     /// Build ZMQ source
     pub fn build(self) -> SubSource<T, O> {
-        SubSource::<T, O>::new(self.address)
+        let mut src = SubSource::<T, O>::new(self.address);
+        src.incoming = Incoming::new(self.pass_tags);
+        src
     }
 }
 
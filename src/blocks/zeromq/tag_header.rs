@@ -0,0 +1,102 @@
+//! GNU Radio ZeroMQ tag header.
+//!
+//! With `pass_tags` enabled, GNU Radio's ZeroMQ stream blocks prepend each message with a header
+//! that carries the tags of the samples in the message. The header uses native byte order:
+//! magic (`u16`), version (`u8`), offset of the first sample (`u64`), number of tags (`u64`), and,
+//! for each tag, its absolute offset (`u64`) followed by key, value, and source id as serialized
+//! PMTs.
+use anyhow::bail;
+use anyhow::ensure;
+
+use crate::prelude::*;
+
+const MAGIC: u16 = 0x5FF0;
+const VERSION: u8 = 0x01;
+/// Size of the fixed part of the header.
+const HEADER_BYTES: usize = 19;
+
+/// Convert a tag into GNU Radio's key/value representation.
+fn to_key_value(tag: &Tag) -> Option<(String, Pmt)> {
+    let kv = match tag {
+        Tag::Id(v) => ("id".to_string(), Pmt::U64(*v)),
+        Tag::String(s) => ("string".to_string(), Pmt::String(s.clone())),
+        Tag::Data(p) => ("data".to_string(), p.clone()),
+        Tag::NamedUsize(n, v) => (n.clone(), Pmt::Usize(*v)),
+        Tag::NamedF32(n, v) => (n.clone(), Pmt::F32(*v)),
+        Tag::NamedAny(n, v) => (n.clone(), v.downcast_ref::<Pmt>()?.clone()),
+    };
+    Some(kv)
+}
+
+/// Convert GNU Radio's key/value representation into a tag.
+fn from_key_value(key: String, value: Pmt) -> Tag {
+    match (key.as_str(), value) {
+        ("id", Pmt::U64(v)) => Tag::Id(v),
+        ("string", Pmt::String(s)) => Tag::String(s),
+        ("data", p) => Tag::Data(p),
+        (_, Pmt::U64(v)) => Tag::NamedUsize(key, v as usize),
+        (_, Pmt::Isize(v)) if v >= 0 => Tag::NamedUsize(key, v as usize),
+        (_, Pmt::F64(v)) => Tag::NamedF32(key, v as f32),
+        (_, p) => Tag::NamedAny(key, Box::new(p)),
+    }
+}
+
+/// Append a tag header for `n_items` samples, starting at absolute sample `offset`, to `out`.
+///
+/// Tags that cannot be represented as PMTs are dropped.
+pub(super) fn encode(offset: u64, n_items: usize, tags: &[ItemTag], out: &mut Vec<u8>) {
+    let start = out.len();
+    out.extend_from_slice(&MAGIC.to_ne_bytes());
+    out.push(VERSION);
+    out.extend_from_slice(&offset.to_ne_bytes());
+    out.extend_from_slice(&0u64.to_ne_bytes());
+
+    let mut n_tags = 0u64;
+    for t in tags.iter().filter(|t| t.index < n_items) {
+        let Some((key, value)) = to_key_value(&t.tag) else {
+            continue;
+        };
+        let len = out.len();
+        out.extend_from_slice(&(offset + t.index as u64).to_ne_bytes());
+        let res = Pmt::String(key)
+            .write_gr(out)
+            .and_then(|_| value.write_gr(out))
+            .and_then(|_| Pmt::Bool(false).write_gr(out));
+        match res {
+            Ok(_) => n_tags += 1,
+            Err(_) => out.truncate(len),
+        }
+    }
+    out[start + 11..start + HEADER_BYTES].copy_from_slice(&n_tags.to_ne_bytes());
+}
+
+/// Parse a tag header, returning the header length and the tags, indexed relative to the first
+/// sample of the message.
+pub(super) fn decode(msg: &[u8]) -> Result<(usize, Vec<ItemTag>)> {
+    ensure!(msg.len() >= HEADER_BYTES, "tag header too short");
+    let magic = u16::from_ne_bytes(msg[0..2].try_into().unwrap());
+    if magic != MAGIC || msg[2] != VERSION {
+        bail!("invalid tag header (magic {magic:#x}, version {})", msg[2]);
+    }
+    let offset = u64::from_ne_bytes(msg[3..11].try_into().unwrap());
+    let n_tags = u64::from_ne_bytes(msg[11..19].try_into().unwrap());
+
+    let mut rest = &msg[HEADER_BYTES..];
+    let mut tags = Vec::new();
+    for _ in 0..n_tags {
+        ensure!(rest.len() >= 8, "tag header too short");
+        let tag_offset = u64::from_ne_bytes(rest[0..8].try_into().unwrap());
+        rest = &rest[8..];
+        let key = Pmt::read_gr(&mut rest)?;
+        let value = Pmt::read_gr(&mut rest)?;
+        let _src_id = Pmt::read_gr(&mut rest)?;
+        let Pmt::String(key) = key else {
+            bail!("tag key is not a symbol");
+        };
+        tags.push(ItemTag {
+            index: tag_offset.saturating_sub(offset) as usize,
+            tag: from_key_value(key, value),
+        });
+    }
+    Ok((msg.len() - rest.len(), tags))
+}
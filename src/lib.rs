@@ -90,13 +90,16 @@ pub mod prelude {
     pub use futuresdr::runtime::FlowgraphId;
     pub use futuresdr::runtime::ItemTag;
     pub use futuresdr::runtime::Kernel;
+    pub use futuresdr::runtime::KernelInterface;
     pub use futuresdr::runtime::MessageOutputs;
     pub use futuresdr::runtime::Pmt;
     pub use futuresdr::runtime::PortId;
     pub use futuresdr::runtime::Result;
     pub use futuresdr::runtime::Runtime;
     pub use futuresdr::runtime::RuntimeHandle;
+    pub use futuresdr::runtime::Serde;
     pub use futuresdr::runtime::Tag;
+    pub use futuresdr::runtime::TagPropagation;
     pub use futuresdr::runtime::WorkIo;
     pub use futuresdr::runtime::buffer::BufferReader;
     pub use futuresdr::runtime::buffer::BufferWriter;
@@ -113,6 +116,10 @@ pub mod prelude {
     pub use futuresdr::runtime::buffer::circuit;
     #[cfg(not(target_arch = "wasm32"))]
     pub use futuresdr::runtime::buffer::circular;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use futuresdr::runtime::buffer::ring;
+    #[cfg(all(unix, not(target_arch = "wasm32")))]
+    pub use futuresdr::runtime::buffer::shm;
     pub use futuresdr::runtime::buffer::slab;
     pub use futuresdr::tracing::debug;
     pub use futuresdr::tracing::error;
@@ -157,6 +157,13 @@ impl<K: KernelInterface + Kernel + Send + 'static> WrappedKernel<K> {
                     }
                     break;
                 }
+                // buffers that connect to other processes can report their peer before init
+                BlockMessage::StreamInputDone { input_id } => {
+                    kernel.stream_input_finish(input_id)?;
+                }
+                BlockMessage::StreamOutputDone { .. } => {
+                    work_io.finished = true;
+                }
                 t => warn!("{} unhandled message during init {:?}", instance_name, t),
             }
         }
@@ -185,6 +192,8 @@ impl<K: KernelInterface + Kernel + Send + 'static> WrappedKernel<K> {
                             stream_inputs,
                             stream_outputs,
                             message_inputs,
+                            message_input_signatures: K::message_input_signatures(),
+                            message_input_schemas: kernel.message_input_schemas(),
                             message_outputs,
                             blocking: K::is_blocking(),
                         };
@@ -17,6 +17,7 @@ use crate::runtime::buffer::CpuBufferReader;
 use crate::runtime::buffer::CpuBufferWriter;
 use crate::runtime::buffer::CpuSample;
 use crate::runtime::buffer::Tags;
+use crate::runtime::latency;
 
 struct MyNotifier {
     sender: Sender<BlockMessage>,
@@ -362,6 +363,7 @@ where
         match self.reader.as_mut().unwrap().slice(false) {
             Some((s, tags)) => {
                 self.tags = tags;
+                latency::observe(self.block_id, &self.tags);
                 (s, &self.tags)
             }
             _ => {
@@ -11,6 +11,14 @@ pub mod circuit;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod circular;
 
+/// Lock-free multi-reader circular buffer with overflow policy
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ring;
+
+/// Shared-memory double-mapped circular buffer
+#[cfg(all(unix, not(target_arch = "wasm32")))]
+pub mod shm;
+
 // ===================== SLAB ========================
 /// Slab buffer
 pub mod slab;
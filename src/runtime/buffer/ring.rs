@@ -0,0 +1,604 @@
+//! Like the [circular](super::circular) buffer, the ring is double-mapped,
+//! presenting available items and space as consecutive slices. In addition,
+//! each connection can select an [`OverflowPolicy`]. Readers with the
+//! [`Block`](OverflowPolicy::Block) policy apply back-pressure like in the
+//! circular buffer. Readers with the [`Overwrite`](OverflowPolicy::Overwrite)
+//! policy never throttle the writer. If they lag behind, their oldest items
+//! are dropped and the reader receives an [`OVERFLOW_TAG`] tag with the number
+//! of lost items at the position of the gap.
+//!
+//! ```
+//! use anyhow::Result;
+//! use futuresdr::blocks::NullSink;
+//! use futuresdr::blocks::NullSource;
+//! use futuresdr::prelude::*;
+//! use futuresdr::runtime::buffer::ring;
+//!
+//! fn main() -> Result<()> {
+//!     let mut fg = Flowgraph::new();
+//!     let src = NullSource::<f32, ring::Writer<f32>>::new();
+//!     let decoder = NullSink::<f32, ring::Reader<f32>>::new();
+//!     let mut gui = NullSink::<f32, ring::Reader<f32>>::new();
+//!     gui.input().set_overflow_policy(ring::OverflowPolicy::Overwrite);
+//!
+//!     connect!(fg, src > decoder; src > gui);
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Items returned by `slice()` stay valid until the reader calls `slice()`
+//! again. If the writer needs these items, it does not overwrite them but asks
+//! the reader to release them. With its next `slice()`, the reader drops them,
+//! even if it did not consume them, and reports them as lost. The writer,
+//! therefore, only waits for an overwriting reader until that reader slices
+//! again. To make room, the writer drops only the oldest items that it needs
+//! for `min_items` new ones, so the writer's `min_items` sets the granularity of
+//! overwrites.
+use futures::prelude::*;
+use std::any::Any;
+use std::fmt;
+use std::mem::size_of;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use vmcircbuffer::double_mapped_buffer::DoubleMappedBuffer;
+
+use crate::channel::mpsc::Sender;
+use crate::channel::mpsc::channel;
+use crate::runtime::BlockId;
+use crate::runtime::BlockMessage;
+use crate::runtime::Error;
+use crate::runtime::ItemTag;
+use crate::runtime::PortId;
+use crate::runtime::Tag;
+use crate::runtime::buffer::BufferReader;
+use crate::runtime::buffer::BufferWriter;
+use crate::runtime::buffer::CpuBufferReader;
+use crate::runtime::buffer::CpuBufferWriter;
+use crate::runtime::buffer::CpuSample;
+use crate::runtime::buffer::Tags;
+use crate::runtime::latency;
+
+/// Name of the [`Tag::NamedUsize`] that marks lost items.
+pub const OVERFLOW_TAG: &str = "overflow";
+
+const BUSY: u64 = 1 << 63;
+const POS: u64 = BUSY - 1;
+
+/// Behavior of a connection when the reader lags behind the writer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Apply back-pressure, i.e., the writer waits for the reader.
+    #[default]
+    Block,
+    /// Never throttle the writer but drop the oldest items of the reader.
+    Overwrite,
+}
+
+/// Statistics of a connection
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReaderStats {
+    /// Block of the reader
+    pub block_id: BlockId,
+    /// Stream input of the reader
+    pub port_id: PortId,
+    /// Overflow policy of the connection
+    pub policy: OverflowPolicy,
+    /// Items that are produced but not yet consumed
+    pub lag: usize,
+    /// Maximum lag observed by the reader
+    pub max_lag: usize,
+    /// Items that were dropped
+    pub lost: u64,
+}
+
+struct ReaderState {
+    block_id: BlockId,
+    port_id: PortId,
+    policy: OverflowPolicy,
+    // read position and busy flag, set while the reader holds a slice
+    state: AtomicU64,
+    // position requested by the writer, while the reader was busy
+    release: AtomicU64,
+    lost: AtomicU64,
+    max_lag: AtomicU64,
+    tags: Mutex<Vec<(u64, Tag)>>,
+}
+
+impl ReaderState {
+    fn stats(&self, write_pos: u64) -> ReaderStats {
+        let r = self.state.load(Ordering::Acquire) & POS;
+        ReaderStats {
+            block_id: self.block_id,
+            port_id: self.port_id.clone(),
+            policy: self.policy,
+            lag: (write_pos - r) as usize,
+            max_lag: self.max_lag.load(Ordering::Relaxed) as usize,
+            lost: self.lost.load(Ordering::Relaxed),
+        }
+    }
+}
+
+struct Shared<D> {
+    buffer: DoubleMappedBuffer<D>,
+    write_pos: AtomicU64,
+}
+
+/// Ring writer
+pub struct Writer<D>
+where
+    D: CpuSample,
+{
+    inbox: Sender<BlockMessage>,
+    block_id: BlockId,
+    port_id: PortId,
+    shared: Option<Arc<Shared<D>>>,
+    readers: Vec<(Arc<ReaderState>, Sender<BlockMessage>)>,
+    tags: Vec<ItemTag>,
+    min_items: Option<usize>,
+    min_buffer_size_in_items: Option<usize>,
+}
+
+impl<D> Writer<D>
+where
+    D: CpuSample,
+{
+    fn new() -> Self {
+        let (rx, _) = channel(0);
+        Self {
+            inbox: rx,
+            block_id: BlockId::default(),
+            port_id: PortId::default(),
+            shared: None,
+            readers: vec![],
+            tags: vec![],
+            min_items: None,
+            min_buffer_size_in_items: None,
+        }
+    }
+
+    /// Statistics of all connected readers
+    pub fn reader_stats(&self) -> Vec<ReaderStats> {
+        let w = self
+            .shared
+            .as_ref()
+            .map(|s| s.write_pos.load(Ordering::Acquire))
+            .unwrap_or(0);
+        self.readers.iter().map(|(r, _)| r.stats(w)).collect()
+    }
+}
+
+impl<D> Default for Writer<D>
+where
+    D: CpuSample,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D> BufferWriter for Writer<D>
+where
+    D: CpuSample,
+{
+    type Reader = Reader<D>;
+
+    fn init(&mut self, block_id: BlockId, port_id: PortId, inbox: Sender<BlockMessage>) {
+        self.block_id = block_id;
+        self.port_id = port_id;
+        self.inbox = inbox;
+    }
+    fn validate(&self) -> Result<(), Error> {
+        if self.shared.is_some() {
+            Ok(())
+        } else {
+            Err(Error::ValidationError(format!(
+                "{:?}:{:?} not connected",
+                self.block_id, self.port_id
+            )))
+        }
+    }
+    fn connect(&mut self, dest: &mut Self::Reader) {
+        if self.shared.is_none() {
+            let min_self = self.min_items.unwrap_or(1);
+            let min_reader = dest.min_items.unwrap_or(1);
+            let mut min_items = min_self + min_reader - 1;
+
+            let buffer_size_configured =
+                self.min_buffer_size_in_items.is_some() || dest.min_buffer_size_in_items.is_some();
+
+            min_items = if buffer_size_configured {
+                let min_self = self.min_buffer_size_in_items.unwrap_or(0);
+                let min_reader = dest.min_buffer_size_in_items.unwrap_or(0);
+                std::cmp::max(min_items, std::cmp::max(min_self, min_reader))
+            } else {
+                std::cmp::max(
+                    min_items,
+                    futuresdr::runtime::config::config().buffer_size / size_of::<D>(),
+                )
+            };
+
+            let buffer = DoubleMappedBuffer::new(min_items).unwrap();
+            self.min_buffer_size_in_items = Some(buffer.capacity());
+            self.shared = Some(Arc::new(Shared {
+                buffer,
+                write_pos: AtomicU64::new(0),
+            }));
+        } else if self.min_buffer_size_in_items.unwrap_or(0)
+            < dest.min_buffer_size_in_items.unwrap_or(0)
+        {
+            warn!("ring buffer is already created, size constraints of reader are not considered.");
+        }
+
+        let shared = self.shared.as_ref().unwrap().clone();
+        let state = Arc::new(ReaderState {
+            block_id: dest.block_id,
+            port_id: dest.port_id.clone(),
+            policy: dest.policy,
+            state: AtomicU64::new(shared.write_pos.load(Ordering::Acquire)),
+            release: AtomicU64::new(0),
+            lost: AtomicU64::new(0),
+            max_lag: AtomicU64::new(0),
+            tags: Mutex::new(vec![]),
+        });
+
+        self.readers.push((state.clone(), dest.inbox.clone()));
+
+        dest.min_buffer_size_in_items = self.min_buffer_size_in_items;
+        dest.shared = Some(shared);
+        dest.state = Some(state);
+        dest.writer_output_id = self.port_id.clone();
+        dest.writer_inbox = self.inbox.clone();
+    }
+    async fn notify_finished(&mut self) {
+        for (r, inbox) in self.readers.iter_mut() {
+            let _ = inbox
+                .send(BlockMessage::StreamInputDone {
+                    input_id: r.port_id.clone(),
+                })
+                .await;
+        }
+    }
+    fn block_id(&self) -> BlockId {
+        self.block_id
+    }
+    fn port_id(&self) -> PortId {
+        self.port_id.clone()
+    }
+}
+
+impl<D> CpuBufferWriter for Writer<D>
+where
+    D: CpuSample,
+{
+    type Item = D;
+
+    fn produce(&mut self, items: usize) {
+        if items == 0 {
+            self.tags.clear();
+            return;
+        }
+        let shared = self.shared.as_ref().unwrap();
+        let w = shared.write_pos.load(Ordering::Relaxed);
+        let tags = std::mem::take(&mut self.tags);
+        if !tags.is_empty() {
+            for (r, _) in self.readers.iter() {
+                let mut q = r.tags.lock().unwrap();
+                for t in tags.iter().filter(|t| t.index < items) {
+                    q.push((w + t.index as u64, t.tag.clone()));
+                }
+            }
+        }
+        shared.write_pos.store(w + items as u64, Ordering::Release);
+        for (_, inbox) in self.readers.iter_mut() {
+            let _ = inbox.try_send(BlockMessage::Notify);
+        }
+    }
+    fn slice_with_tags(&mut self) -> (&mut [Self::Item], Tags<'_>) {
+        let shared = self.shared.as_ref().unwrap();
+        let cap = shared.buffer.capacity() as u64;
+        let w = shared.write_pos.load(Ordering::Relaxed);
+        let reserve = std::cmp::min(cap, self.min_items.unwrap_or(1) as u64);
+
+        let mut min_r = w;
+        for (r, inbox) in self.readers.iter_mut() {
+            loop {
+                let s = r.state.load(Ordering::Acquire);
+                let pos = s & POS;
+                if r.policy == OverflowPolicy::Block || cap - (w - pos) >= reserve {
+                    min_r = std::cmp::min(min_r, pos);
+                    break;
+                }
+                let new_pos = w + reserve - cap;
+                if s & BUSY != 0 {
+                    // the reader holds the items, it drops them with its next slice
+                    r.release.fetch_max(new_pos, Ordering::AcqRel);
+                    let _ = inbox.try_send(BlockMessage::Notify);
+                    min_r = std::cmp::min(min_r, pos);
+                    break;
+                }
+                // drop the oldest items of an idle, overwriting reader
+                if r.state
+                    .compare_exchange(s, new_pos, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok()
+                {
+                    r.lost.fetch_add(new_pos - pos, Ordering::Relaxed);
+                    min_r = std::cmp::min(min_r, new_pos);
+                    break;
+                }
+            }
+        }
+
+        let space = (cap - (w - min_r)) as usize;
+        let offset = (w % cap) as usize;
+        let s = unsafe { &mut shared.buffer.slice_with_offset_mut(offset)[0..space] };
+        (s, Tags::new(&mut self.tags, 0))
+    }
+
+    fn set_min_items(&mut self, n: usize) {
+        if self.shared.is_some() {
+            warn!("buffer size configured after buffer is connected. This has no effect");
+        }
+        self.min_items = Some(n);
+    }
+
+    fn set_min_buffer_size_in_items(&mut self, n: usize) {
+        if self.shared.is_some() {
+            warn!("buffer size configured after buffer is connected. This has no effect");
+        }
+        self.min_buffer_size_in_items = Some(n);
+    }
+    fn max_items(&self) -> usize {
+        self.min_buffer_size_in_items.unwrap_or(usize::MAX)
+    }
+}
+
+impl<D> fmt::Debug for Writer<D>
+where
+    D: CpuSample,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ring::Writer")
+            .field("output_id", &self.port_id)
+            .field("readers", &self.reader_stats())
+            .finish()
+    }
+}
+
+/// Ring reader
+pub struct Reader<D>
+where
+    D: CpuSample,
+{
+    shared: Option<Arc<Shared<D>>>,
+    state: Option<Arc<ReaderState>>,
+    policy: OverflowPolicy,
+    finished: bool,
+    writer_inbox: Sender<BlockMessage>,
+    writer_output_id: PortId,
+    block_id: BlockId,
+    port_id: PortId,
+    inbox: Sender<BlockMessage>,
+    reported_lost: u64,
+    pending: Vec<(u64, Tag)>,
+    tags: Vec<ItemTag>,
+    min_items: Option<usize>,
+    min_buffer_size_in_items: Option<usize>,
+}
+
+impl<D> Reader<D>
+where
+    D: CpuSample,
+{
+    /// Set the overflow policy of the connection
+    ///
+    /// Has to be configured before the reader is connected.
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        if self.state.is_some() {
+            warn!("overflow policy configured after buffer is connected. This has no effect");
+        }
+        self.policy = policy;
+    }
+
+    /// Overflow policy of the connection
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Statistics of the connection
+    pub fn stats(&self) -> Option<ReaderStats> {
+        let w = self.shared.as_ref()?.write_pos.load(Ordering::Acquire);
+        Some(self.state.as_ref()?.stats(w))
+    }
+}
+
+impl<D> Default for Reader<D>
+where
+    D: CpuSample,
+{
+    fn default() -> Self {
+        let (rx, _) = channel(0);
+        Self {
+            shared: None,
+            state: None,
+            policy: OverflowPolicy::default(),
+            finished: false,
+            writer_inbox: rx.clone(),
+            writer_output_id: PortId::default(),
+            block_id: BlockId::default(),
+            port_id: PortId::default(),
+            inbox: rx,
+            reported_lost: 0,
+            pending: vec![],
+            tags: vec![],
+            min_items: None,
+            min_buffer_size_in_items: None,
+        }
+    }
+}
+
+#[async_trait]
+impl<D> BufferReader for Reader<D>
+where
+    D: CpuSample,
+{
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn init(&mut self, block_id: BlockId, port_id: PortId, inbox: Sender<BlockMessage>) {
+        self.block_id = block_id;
+        self.port_id = port_id;
+        self.inbox = inbox;
+    }
+    fn validate(&self) -> Result<(), Error> {
+        if self.state.is_some() {
+            Ok(())
+        } else {
+            Err(Error::ValidationError(format!(
+                "{:?}:{:?} not connected",
+                self.block_id, self.port_id
+            )))
+        }
+    }
+    async fn notify_finished(&mut self) {
+        let _ = self
+            .writer_inbox
+            .send(BlockMessage::StreamOutputDone {
+                output_id: self.writer_output_id.clone(),
+            })
+            .await;
+    }
+    fn finish(&mut self) {
+        self.finished = true;
+    }
+    fn finished(&self) -> bool {
+        self.finished
+    }
+    fn block_id(&self) -> BlockId {
+        self.block_id
+    }
+    fn port_id(&self) -> PortId {
+        self.port_id.clone()
+    }
+}
+
+impl<D> CpuBufferReader for Reader<D>
+where
+    D: CpuSample,
+{
+    type Item = D;
+
+    fn slice_with_tags(&mut self) -> (&[Self::Item], &Vec<ItemTag>) {
+        let shared = self.shared.as_ref().unwrap();
+        let state = self.state.as_ref().unwrap();
+
+        // pin the read position, so that the writer does not drop held items
+        let mut r = loop {
+            let s = state.state.load(Ordering::Acquire);
+            if s & BUSY != 0 {
+                break s & POS;
+            }
+            if state
+                .state
+                .compare_exchange(s, s | BUSY, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                break s;
+            }
+        };
+        // items of the previous slice are no longer held
+        let release = state.release.swap(0, Ordering::AcqRel);
+        if release > r {
+            state.lost.fetch_add(release - r, Ordering::Relaxed);
+            state.state.store(release | BUSY, Ordering::Release);
+            r = release;
+            let _ = self.writer_inbox.try_send(BlockMessage::Notify);
+        }
+        let w = shared.write_pos.load(Ordering::Acquire);
+        let lag = w - r;
+        state.max_lag.fetch_max(lag, Ordering::Relaxed);
+
+        self.pending.append(&mut state.tags.lock().unwrap());
+        let lost = state.lost.load(Ordering::Relaxed);
+        if lost != self.reported_lost {
+            self.pending.retain(|(i, _)| *i >= r);
+            self.pending.insert(
+                0,
+                (
+                    r,
+                    Tag::NamedUsize(
+                        OVERFLOW_TAG.to_string(),
+                        (lost - self.reported_lost) as usize,
+                    ),
+                ),
+            );
+            self.reported_lost = lost;
+        }
+
+        self.tags.clear();
+        for (i, t) in self.pending.iter() {
+            if *i >= r && *i < w {
+                self.tags.push(ItemTag {
+                    index: (*i - r) as usize,
+                    tag: t.clone(),
+                });
+            }
+        }
+
+        latency::observe(self.block_id, &self.tags);
+
+        let cap = shared.buffer.capacity() as u64;
+        let offset = (r % cap) as usize;
+        let s = unsafe { &shared.buffer.slice_with_offset(offset)[0..lag as usize] };
+        (s, &self.tags)
+    }
+    fn consume(&mut self, amount: usize) {
+        let state = self.state.as_ref().unwrap();
+        let r = (state.state.load(Ordering::Acquire) & POS) + amount as u64;
+        debug_assert!(
+            r <= self
+                .shared
+                .as_ref()
+                .unwrap()
+                .write_pos
+                .load(Ordering::Acquire)
+        );
+        // the writer does not modify the position while the reader is busy
+        state.state.store(r, Ordering::Release);
+        self.pending.retain(|(i, _)| *i >= r);
+        let _ = self.writer_inbox.try_send(BlockMessage::Notify);
+    }
+
+    fn set_min_items(&mut self, n: usize) {
+        if self.state.is_some() {
+            warn!("buffer size configured after buffer is connected. This has no effect");
+        }
+        self.min_items = Some(n);
+    }
+
+    fn set_min_buffer_size_in_items(&mut self, n: usize) {
+        if self.state.is_some() {
+            warn!("buffer size configured after buffer is connected. This has no effect");
+        }
+        self.min_buffer_size_in_items = Some(n);
+    }
+    fn max_items(&self) -> usize {
+        self.min_buffer_size_in_items.unwrap_or(usize::MAX)
+    }
+}
+
+impl<D> fmt::Debug for Reader<D>
+where
+    D: CpuSample,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ring::Reader")
+            .field("writer_output_id", &self.writer_output_id)
+            .field("policy", &self.policy)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
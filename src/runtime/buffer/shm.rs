@@ -0,0 +1,977 @@
+//! The buffer is backed by a named POSIX shared-memory segment, which allows
+//! connecting flowgraphs that run in different processes without copying or
+//! serializing samples. The writer creates the segment with
+//! [`Writer::create`], the reader attaches to it with [`Reader::open`].
+//!
+//! ```no_run
+//! use futuresdr::blocks::NullSink;
+//! use futuresdr::blocks::NullSource;
+//! use futuresdr::runtime::buffer::shm;
+//!
+//! // capture process
+//! let mut src = NullSource::<f32, shm::Writer<f32>>::new();
+//! src.output().create("/capture").unwrap();
+//!
+//! // decoder process
+//! let mut snk = NullSink::<f32, shm::Reader<f32>>::new();
+//! snk.input().open("/capture").unwrap();
+//! ```
+//!
+//! Ports that are attached to a segment do not have to be connected in the
+//! flowgraph. The buffer supports a single reader at a time. A reader detaches
+//! when it is dropped. The next reader that opens the segment also replaces a
+//! reader whose process terminated without detaching. A writer that is already
+//! added to a flowgraph, however, finishes when its reader terminates (see
+//! below). Tags are transferred
+//! through a side channel in the segment header. [`Tag::NamedAny`] cannot be
+//! transferred across process boundaries and is dropped. This includes latency
+//! probes, i.e., [latency tracing](crate::runtime::latency) stops at the
+//! segment.
+//!
+//! Items have to be plain data without pointers, since they are shared
+//! between address spaces.
+//!
+//! If the process on the other side of the segment terminates without
+//! finishing its port (e.g., because it crashed), the local port is
+//! finished, as if the remote side had finished regularly.
+use serde::Deserialize;
+use serde::Serialize;
+use std::any::Any;
+use std::ffi::CString;
+use std::fmt;
+use std::mem::size_of;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::channel::mpsc::Sender;
+use crate::channel::mpsc::channel;
+use crate::runtime::BlockId;
+use crate::runtime::BlockMessage;
+use crate::runtime::Error;
+use crate::runtime::ItemTag;
+use crate::runtime::Pmt;
+use crate::runtime::PortId;
+use crate::runtime::Tag;
+use crate::runtime::buffer::BufferReader;
+use crate::runtime::buffer::BufferWriter;
+use crate::runtime::buffer::CpuBufferReader;
+use crate::runtime::buffer::CpuBufferWriter;
+use crate::runtime::buffer::CpuSample;
+use crate::runtime::buffer::Tags;
+
+const MAGIC: u64 = 0x4655_5455_5245_5348;
+const VERSION: u32 = 2;
+const TAG_SLOTS: usize = 64;
+const TAG_SLOT_BYTES: usize = 256;
+const TAG_SLOT_HEADER: usize = 12;
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_micros(500);
+
+static SEGMENT_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[repr(C)]
+struct Header {
+    magic: u64,
+    version: u32,
+    item_size: u32,
+    capacity: u64,
+    write_pos: AtomicU64,
+    read_pos: AtomicU64,
+    tag_write: AtomicU64,
+    tag_read: AtomicU64,
+    writer_done: AtomicU32,
+    reader_done: AtomicU32,
+    reader_attached: AtomicU32,
+    writer_pid: AtomicU32,
+    reader_pid: AtomicU32,
+}
+
+#[derive(Serialize, Deserialize)]
+enum SharedTag {
+    Id(u64),
+    String(String),
+    Data(Pmt),
+    NamedUsize(String, usize),
+    NamedF32(String, f32),
+}
+
+impl SharedTag {
+    fn from_tag(tag: &Tag) -> Option<Self> {
+        match tag {
+            Tag::Id(v) => Some(SharedTag::Id(*v)),
+            Tag::String(v) => Some(SharedTag::String(v.clone())),
+            Tag::Data(v) => Some(SharedTag::Data(v.clone())),
+            Tag::NamedUsize(n, v) => Some(SharedTag::NamedUsize(n.clone(), *v)),
+            Tag::NamedF32(n, v) => Some(SharedTag::NamedF32(n.clone(), *v)),
+            _ => None,
+        }
+    }
+    fn into_tag(self) -> Tag {
+        match self {
+            SharedTag::Id(v) => Tag::Id(v),
+            SharedTag::String(v) => Tag::String(v),
+            SharedTag::Data(v) => Tag::Data(v),
+            SharedTag::NamedUsize(n, v) => Tag::NamedUsize(n, v),
+            SharedTag::NamedF32(n, v) => Tag::NamedF32(n, v),
+        }
+    }
+}
+
+/// Mapping of a shared-memory segment
+///
+/// The segment consists of a header region (ring indices and tag slots),
+/// followed by the data region, which is mapped twice back-to-back.
+struct Segment {
+    name: String,
+    base: *mut u8,
+    header_bytes: usize,
+    data_bytes: usize,
+    owner: bool,
+}
+
+// The segment is only accessed through atomics in the header and the
+// disjoint regions handed out to the single writer and reader.
+unsafe impl Send for Segment {}
+unsafe impl Sync for Segment {}
+
+fn pagesize() -> usize {
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+fn header_bytes() -> usize {
+    let ps = pagesize();
+    let min = size_of::<Header>() + TAG_SLOTS * TAG_SLOT_BYTES;
+    min.div_ceil(ps) * ps
+}
+
+fn os_error(what: &str, name: &str) -> Error {
+    Error::RuntimeError(format!(
+        "shm buffer {name}: {what} failed ({})",
+        std::io::Error::last_os_error()
+    ))
+}
+
+impl Segment {
+    fn create(name: &str, item_size: usize, min_bytes: usize) -> Result<Self, Error> {
+        let ps = pagesize();
+        let mut data_bytes = ps;
+        while data_bytes < min_bytes || (data_bytes % item_size != 0) {
+            data_bytes += ps;
+        }
+        let header_bytes = header_bytes();
+        let cname = CString::new(name).map_err(|_| Error::InvalidParameter)?;
+
+        unsafe {
+            let fd = libc::shm_open(
+                cname.as_ptr(),
+                libc::O_CREAT | libc::O_EXCL | libc::O_RDWR,
+                0o600,
+            );
+            if fd < 0 {
+                return Err(os_error("shm_open", name));
+            }
+            if libc::ftruncate(fd, (header_bytes + data_bytes) as libc::off_t) < 0 {
+                let e = os_error("ftruncate", name);
+                libc::close(fd);
+                libc::shm_unlink(cname.as_ptr());
+                return Err(e);
+            }
+            let segment = Self::map(fd, name, header_bytes, data_bytes, true);
+            libc::close(fd);
+            let segment = match segment {
+                Ok(s) => s,
+                Err(e) => {
+                    libc::shm_unlink(cname.as_ptr());
+                    return Err(e);
+                }
+            };
+
+            let header = segment.base as *mut Header;
+            header.write(Header {
+                magic: 0,
+                version: VERSION,
+                item_size: item_size as u32,
+                capacity: (data_bytes / item_size) as u64,
+                write_pos: AtomicU64::new(0),
+                read_pos: AtomicU64::new(0),
+                tag_write: AtomicU64::new(0),
+                tag_read: AtomicU64::new(0),
+                writer_done: AtomicU32::new(0),
+                reader_done: AtomicU32::new(0),
+                reader_attached: AtomicU32::new(0),
+                writer_pid: AtomicU32::new(std::process::id()),
+                reader_pid: AtomicU32::new(0),
+            });
+            // publish the magic last, so readers never see a half-initialized header
+            (*(header as *const AtomicU64)).store(MAGIC, Ordering::Release);
+            Ok(segment)
+        }
+    }
+
+    fn open(name: &str, item_size: usize) -> Result<Self, Error> {
+        let header_bytes = header_bytes();
+        let cname = CString::new(name).map_err(|_| Error::InvalidParameter)?;
+
+        unsafe {
+            let fd = libc::shm_open(cname.as_ptr(), libc::O_RDWR, 0o600);
+            if fd < 0 {
+                return Err(os_error("shm_open", name));
+            }
+            let mut stat: libc::stat = std::mem::zeroed();
+            if libc::fstat(fd, &mut stat) < 0 {
+                let e = os_error("fstat", name);
+                libc::close(fd);
+                return Err(e);
+            }
+            let total = stat.st_size as usize;
+            if total <= header_bytes {
+                libc::close(fd);
+                return Err(Error::RuntimeError(format!(
+                    "shm buffer {name}: segment not initialized"
+                )));
+            }
+            let segment = Self::map(fd, name, header_bytes, total - header_bytes, false);
+            libc::close(fd);
+            let segment = segment?;
+
+            let header = segment.header();
+            if (*(header as *const Header as *const AtomicU64)).load(Ordering::Acquire) != MAGIC
+                || header.version != VERSION
+            {
+                return Err(Error::RuntimeError(format!(
+                    "shm buffer {name}: not a FutureSDR buffer or incompatible version"
+                )));
+            }
+            if header.item_size as usize != item_size {
+                return Err(Error::RuntimeError(format!(
+                    "shm buffer {name}: item size mismatch (segment {}, reader {})",
+                    header.item_size, item_size
+                )));
+            }
+            if header.reader_attached.swap(1, Ordering::AcqRel) != 0 {
+                // take over the segment from a reader that terminated without detaching
+                let pid = header.reader_pid.load(Ordering::Acquire);
+                if peer_alive(&header.reader_pid)
+                    || header
+                        .reader_pid
+                        .compare_exchange(
+                            pid,
+                            std::process::id(),
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                        )
+                        .is_err()
+                {
+                    return Err(Error::RuntimeError(format!(
+                        "shm buffer {name}: reader already attached"
+                    )));
+                }
+                warn!("shm buffer {name}: reader {pid} terminated, taking over");
+            }
+            header
+                .reader_pid
+                .store(std::process::id(), Ordering::Release);
+            Ok(segment)
+        }
+    }
+
+    unsafe fn map(
+        fd: libc::c_int,
+        name: &str,
+        header_bytes: usize,
+        data_bytes: usize,
+        owner: bool,
+    ) -> Result<Self, Error> {
+        unsafe {
+            let total = header_bytes + 2 * data_bytes;
+            // reserve address space for header + two copies of the data region
+            let base = libc::mmap(
+                std::ptr::null_mut(),
+                total,
+                libc::PROT_NONE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            );
+            if base == libc::MAP_FAILED {
+                return Err(os_error("mmap", name));
+            }
+            let first = libc::mmap(
+                base,
+                header_bytes + data_bytes,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_FIXED,
+                fd,
+                0,
+            );
+            if first != base {
+                let e = os_error("mmap", name);
+                libc::munmap(base, total);
+                return Err(e);
+            }
+            let second_addr = (base as *mut u8).add(header_bytes + data_bytes) as *mut libc::c_void;
+            let second = libc::mmap(
+                second_addr,
+                data_bytes,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_FIXED,
+                fd,
+                header_bytes as libc::off_t,
+            );
+            if second != second_addr {
+                let e = os_error("mmap", name);
+                libc::munmap(base, total);
+                return Err(e);
+            }
+
+            Ok(Segment {
+                name: name.to_string(),
+                base: base as *mut u8,
+                header_bytes,
+                data_bytes,
+                owner,
+            })
+        }
+    }
+
+    fn header(&self) -> &Header {
+        unsafe { &*(self.base as *const Header) }
+    }
+
+    fn capacity(&self) -> usize {
+        self.header().capacity as usize
+    }
+
+    fn data(&self) -> *mut u8 {
+        unsafe { self.base.add(self.header_bytes) }
+    }
+
+    fn tag_slot(&self, i: u64) -> *mut u8 {
+        let i = (i % TAG_SLOTS as u64) as usize;
+        unsafe { self.base.add(size_of::<Header>() + i * TAG_SLOT_BYTES) }
+    }
+
+    fn push_tag(&self, index: u64, tag: &Tag) {
+        let Some(shared) = SharedTag::from_tag(tag) else {
+            warn!("shm buffer {}: tag cannot be shared, dropping", self.name);
+            return;
+        };
+        let Ok(payload) = serde_json::to_vec(&shared) else {
+            warn!("shm buffer {}: failed to serialize tag", self.name);
+            return;
+        };
+        if payload.len() > TAG_SLOT_BYTES - TAG_SLOT_HEADER {
+            warn!("shm buffer {}: tag too large, dropping", self.name);
+            return;
+        }
+        let h = self.header();
+        let w = h.tag_write.load(Ordering::Relaxed);
+        if w - h.tag_read.load(Ordering::Acquire) >= TAG_SLOTS as u64 {
+            warn!("shm buffer {}: tag channel full, dropping tag", self.name);
+            return;
+        }
+        unsafe {
+            let slot = self.tag_slot(w);
+            std::ptr::copy_nonoverlapping(index.to_le_bytes().as_ptr(), slot, 8);
+            std::ptr::copy_nonoverlapping(
+                (payload.len() as u32).to_le_bytes().as_ptr(),
+                slot.add(8),
+                4,
+            );
+            std::ptr::copy_nonoverlapping(
+                payload.as_ptr(),
+                slot.add(TAG_SLOT_HEADER),
+                payload.len(),
+            );
+        }
+        h.tag_write.store(w + 1, Ordering::Release);
+    }
+
+    fn pop_tags(&self, tags: &mut Vec<(u64, Tag)>) {
+        let h = self.header();
+        let w = h.tag_write.load(Ordering::Acquire);
+        let mut r = h.tag_read.load(Ordering::Relaxed);
+        while r < w {
+            unsafe {
+                let slot = self.tag_slot(r);
+                let mut index = [0u8; 8];
+                let mut len = [0u8; 4];
+                std::ptr::copy_nonoverlapping(slot, index.as_mut_ptr(), 8);
+                std::ptr::copy_nonoverlapping(slot.add(8), len.as_mut_ptr(), 4);
+                let len = u32::from_le_bytes(len) as usize;
+                let payload = std::slice::from_raw_parts(slot.add(TAG_SLOT_HEADER), len);
+                match serde_json::from_slice::<SharedTag>(payload) {
+                    Ok(t) => tags.push((u64::from_le_bytes(index), t.into_tag())),
+                    Err(_) => warn!("shm buffer {}: failed to parse tag", self.name),
+                }
+            }
+            r += 1;
+        }
+        h.tag_read.store(r, Ordering::Release);
+    }
+}
+
+impl Drop for Segment {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(
+                self.base as *mut libc::c_void,
+                self.header_bytes + 2 * self.data_bytes,
+            );
+            if self.owner
+                && let Ok(name) = CString::new(self.name.clone())
+            {
+                libc::shm_unlink(name.as_ptr());
+            }
+        }
+    }
+}
+
+/// Check if the process on the other side of the segment is still alive
+///
+/// A pid of zero means that the peer did not attach yet.
+fn peer_alive(pid: &AtomicU32) -> bool {
+    let pid = pid.load(Ordering::Acquire);
+    if pid == 0 || pid == std::process::id() {
+        return true;
+    }
+    unsafe {
+        libc::kill(pid as libc::pid_t, 0) == 0
+            || std::io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH)
+    }
+}
+
+/// Send a message that finishes a port
+///
+/// Returns whether the poller has to retry, since the inbox of the block is full.
+fn send_done(inbox: &mut Sender<BlockMessage>, message: BlockMessage) -> bool {
+    match inbox.try_send(message) {
+        Ok(()) => false,
+        Err(e) => e.is_full(),
+    }
+}
+
+/// Watches the segment from a helper thread and translates changes of the
+/// remote side into messages for the local block.
+struct Poller {
+    stop: Arc<AtomicBool>,
+}
+
+impl Poller {
+    fn spawn<F>(interval: Duration, mut poll: F) -> Self
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let s = stop.clone();
+        std::thread::spawn(move || {
+            while !s.load(Ordering::Relaxed) {
+                if !poll() {
+                    break;
+                }
+                std::thread::sleep(interval);
+            }
+        });
+        Self { stop }
+    }
+}
+
+impl Drop for Poller {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Shared-memory writer
+pub struct Writer<D>
+where
+    D: CpuSample + Copy,
+{
+    inbox: Sender<BlockMessage>,
+    block_id: BlockId,
+    port_id: PortId,
+    segment: Option<Arc<Segment>>,
+    poller: Option<Poller>,
+    poll_interval: Duration,
+    initialized: bool,
+    tags: Vec<ItemTag>,
+    min_items: Option<usize>,
+    min_buffer_size_in_items: Option<usize>,
+    _p: std::marker::PhantomData<D>,
+}
+
+impl<D> Writer<D>
+where
+    D: CpuSample + Copy,
+{
+    fn new() -> Self {
+        let (rx, _) = channel(0);
+        Self {
+            inbox: rx,
+            block_id: BlockId::default(),
+            port_id: PortId::default(),
+            segment: None,
+            poller: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            initialized: false,
+            tags: vec![],
+            min_items: None,
+            min_buffer_size_in_items: None,
+            _p: std::marker::PhantomData,
+        }
+    }
+
+    /// Create a named shared-memory segment for this writer
+    ///
+    /// The name has to be a valid POSIX shared-memory name, e.g.,
+    /// `/capture`. The segment is removed when the writer is dropped.
+    pub fn create(&mut self, name: impl AsRef<str>) -> Result<(), Error> {
+        if self.segment.is_some() {
+            return Err(Error::ValidationError(format!(
+                "{:?}:{:?} shm buffer already created",
+                self.block_id, self.port_id
+            )));
+        }
+
+        let min_self = self.min_items.unwrap_or(1);
+        let min_bytes = match self.min_buffer_size_in_items {
+            Some(n) => std::cmp::max(n, min_self) * size_of::<D>(),
+            None => std::cmp::max(
+                min_self * size_of::<D>(),
+                futuresdr::runtime::config::config().buffer_size,
+            ),
+        };
+
+        let segment = Segment::create(name.as_ref(), size_of::<D>(), min_bytes)?;
+        self.min_buffer_size_in_items = Some(segment.capacity());
+        self.segment = Some(Arc::new(segment));
+        self.start_poller();
+        Ok(())
+    }
+
+    /// Name of the shared-memory segment
+    pub fn name(&self) -> Option<&str> {
+        self.segment.as_ref().map(|s| s.name.as_str())
+    }
+
+    /// Interval in which the reader state is polled
+    pub fn set_poll_interval(&mut self, interval: Duration) {
+        self.poll_interval = interval;
+    }
+
+    /// Number of items that are written but not yet consumed by the reader
+    pub fn lag(&self) -> usize {
+        self.segment
+            .as_ref()
+            .map(|s| {
+                let h = s.header();
+                (h.write_pos.load(Ordering::Acquire) - h.read_pos.load(Ordering::Acquire)) as usize
+            })
+            .unwrap_or(0)
+    }
+
+    fn start_poller(&mut self) {
+        if !self.initialized || self.poller.is_some() {
+            return;
+        }
+        let Some(segment) = self.segment.clone() else {
+            return;
+        };
+        let mut inbox = self.inbox.clone();
+        let output_id = self.port_id.clone();
+        let mut last = segment.header().read_pos.load(Ordering::Acquire);
+        self.poller = Some(Poller::spawn(self.poll_interval, move || {
+            let h = segment.header();
+            if h.reader_done.load(Ordering::Acquire) != 0 || !peer_alive(&h.reader_pid) {
+                return send_done(
+                    &mut inbox,
+                    BlockMessage::StreamOutputDone {
+                        output_id: output_id.clone(),
+                    },
+                );
+            }
+            let r = h.read_pos.load(Ordering::Acquire);
+            if r != last {
+                last = r;
+                let _ = inbox.try_send(BlockMessage::Notify);
+            }
+            !inbox.is_closed()
+        }));
+    }
+}
+
+impl<D> Default for Writer<D>
+where
+    D: CpuSample + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D> BufferWriter for Writer<D>
+where
+    D: CpuSample + Copy,
+{
+    type Reader = Reader<D>;
+
+    fn init(&mut self, block_id: BlockId, port_id: PortId, inbox: Sender<BlockMessage>) {
+        self.block_id = block_id;
+        self.port_id = port_id;
+        self.inbox = inbox;
+        self.initialized = true;
+        self.start_poller();
+    }
+    fn validate(&self) -> Result<(), Error> {
+        if self.segment.is_some() {
+            Ok(())
+        } else {
+            Err(Error::ValidationError(format!(
+                "{:?}:{:?} shm buffer not created",
+                self.block_id, self.port_id
+            )))
+        }
+    }
+    fn connect(&mut self, dest: &mut Self::Reader) {
+        if self.segment.is_none() {
+            if let Some(n) = dest.min_buffer_size_in_items {
+                let n = std::cmp::max(n, self.min_buffer_size_in_items.unwrap_or(0));
+                self.min_buffer_size_in_items = Some(n);
+            }
+            let name = format!(
+                "/futuresdr-{}-{}",
+                std::process::id(),
+                SEGMENT_COUNTER.fetch_add(1, Ordering::Relaxed)
+            );
+            if let Err(e) = self.create(&name) {
+                error!("shm buffer: could not create segment {name} ({e})");
+                return;
+            }
+        }
+        let name = self.segment.as_ref().unwrap().name.clone();
+        if let Err(e) = dest.open(&name) {
+            error!("shm buffer: could not open segment {name} ({e})");
+        }
+    }
+    async fn notify_finished(&mut self) {
+        if let Some(s) = self.segment.as_ref() {
+            s.header().writer_done.store(1, Ordering::Release);
+        }
+        self.poller = None;
+    }
+    fn block_id(&self) -> BlockId {
+        self.block_id
+    }
+    fn port_id(&self) -> PortId {
+        self.port_id.clone()
+    }
+}
+
+impl<D> CpuBufferWriter for Writer<D>
+where
+    D: CpuSample + Copy,
+{
+    type Item = D;
+
+    fn produce(&mut self, items: usize) {
+        let s = self.segment.as_ref().unwrap();
+        let h = s.header();
+        let w = h.write_pos.load(Ordering::Relaxed);
+        for t in std::mem::take(&mut self.tags) {
+            if t.index < items {
+                s.push_tag(w + t.index as u64, &t.tag);
+            }
+        }
+        h.write_pos.store(w + items as u64, Ordering::Release);
+    }
+    fn slice_with_tags(&mut self) -> (&mut [Self::Item], Tags<'_>) {
+        let s = self.segment.as_ref().unwrap();
+        let h = s.header();
+        let w = h.write_pos.load(Ordering::Relaxed);
+        let r = h.read_pos.load(Ordering::Acquire);
+        let cap = s.capacity();
+        let space = cap - (w - r) as usize;
+        let slice = unsafe {
+            let ptr = s.data().add((w as usize % cap) * size_of::<D>()) as *mut D;
+            std::slice::from_raw_parts_mut(ptr, space)
+        };
+        (slice, Tags::new(&mut self.tags, 0))
+    }
+
+    fn set_min_items(&mut self, n: usize) {
+        if self.segment.is_some() {
+            warn!("buffer size configured after buffer is created. This has no effect");
+        }
+        self.min_items = Some(n);
+    }
+
+    fn set_min_buffer_size_in_items(&mut self, n: usize) {
+        if self.segment.is_some() {
+            warn!("buffer size configured after buffer is created. This has no effect");
+        }
+        self.min_buffer_size_in_items = Some(n);
+    }
+    fn max_items(&self) -> usize {
+        self.min_buffer_size_in_items.unwrap_or(usize::MAX)
+    }
+}
+
+impl<D> fmt::Debug for Writer<D>
+where
+    D: CpuSample + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("shm::Writer")
+            .field("output_id", &self.port_id)
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
+/// Shared-memory reader
+pub struct Reader<D>
+where
+    D: CpuSample + Copy,
+{
+    segment: Option<Arc<Segment>>,
+    poller: Option<Poller>,
+    poll_interval: Duration,
+    initialized: bool,
+    finished: bool,
+    block_id: BlockId,
+    port_id: PortId,
+    inbox: Sender<BlockMessage>,
+    pending: Vec<(u64, Tag)>,
+    tags: Vec<ItemTag>,
+    min_items: Option<usize>,
+    min_buffer_size_in_items: Option<usize>,
+    _p: std::marker::PhantomData<D>,
+}
+
+impl<D> Reader<D>
+where
+    D: CpuSample + Copy,
+{
+    /// Attach to a shared-memory segment that was created by a [`Writer`]
+    pub fn open(&mut self, name: impl AsRef<str>) -> Result<(), Error> {
+        if self.segment.is_some() {
+            return Err(Error::ValidationError(format!(
+                "{:?}:{:?} shm buffer already opened",
+                self.block_id, self.port_id
+            )));
+        }
+        let segment = Segment::open(name.as_ref(), size_of::<D>())?;
+        if let Some(n) = self.min_items
+            && n > segment.capacity()
+        {
+            warn!(
+                "shm buffer {}: capacity {} smaller than min items of reader {}",
+                segment.name,
+                segment.capacity(),
+                n
+            );
+        }
+        self.min_buffer_size_in_items = Some(segment.capacity());
+        self.segment = Some(Arc::new(segment));
+        self.start_poller();
+        Ok(())
+    }
+
+    /// Name of the shared-memory segment
+    pub fn name(&self) -> Option<&str> {
+        self.segment.as_ref().map(|s| s.name.as_str())
+    }
+
+    /// Interval in which the writer state is polled
+    pub fn set_poll_interval(&mut self, interval: Duration) {
+        self.poll_interval = interval;
+    }
+
+    fn start_poller(&mut self) {
+        if !self.initialized || self.poller.is_some() {
+            return;
+        }
+        let Some(segment) = self.segment.clone() else {
+            return;
+        };
+        let mut inbox = self.inbox.clone();
+        let input_id = self.port_id.clone();
+        let mut last = segment.header().write_pos.load(Ordering::Acquire);
+        self.poller = Some(Poller::spawn(self.poll_interval, move || {
+            let h = segment.header();
+            let done = h.writer_done.load(Ordering::Acquire) != 0 || !peer_alive(&h.writer_pid);
+            let w = h.write_pos.load(Ordering::Acquire);
+            if w != last {
+                last = w;
+                let _ = inbox.try_send(BlockMessage::Notify);
+            }
+            if done {
+                return send_done(
+                    &mut inbox,
+                    BlockMessage::StreamInputDone {
+                        input_id: input_id.clone(),
+                    },
+                );
+            }
+            !inbox.is_closed()
+        }));
+    }
+}
+
+impl<D> Default for Reader<D>
+where
+    D: CpuSample + Copy,
+{
+    fn default() -> Self {
+        let (rx, _) = channel(0);
+        Self {
+            segment: None,
+            poller: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            initialized: false,
+            finished: false,
+            block_id: BlockId::default(),
+            port_id: PortId::default(),
+            inbox: rx,
+            pending: vec![],
+            tags: vec![],
+            min_items: None,
+            min_buffer_size_in_items: None,
+            _p: std::marker::PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<D> BufferReader for Reader<D>
+where
+    D: CpuSample + Copy,
+{
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn init(&mut self, block_id: BlockId, port_id: PortId, inbox: Sender<BlockMessage>) {
+        self.block_id = block_id;
+        self.port_id = port_id;
+        self.inbox = inbox;
+        self.initialized = true;
+        self.start_poller();
+    }
+    fn validate(&self) -> Result<(), Error> {
+        if self.segment.is_some() {
+            Ok(())
+        } else {
+            Err(Error::ValidationError(format!(
+                "{:?}:{:?} shm buffer not opened",
+                self.block_id, self.port_id
+            )))
+        }
+    }
+    async fn notify_finished(&mut self) {
+        if let Some(s) = self.segment.as_ref() {
+            s.header().reader_done.store(1, Ordering::Release);
+        }
+        self.poller = None;
+    }
+    fn finish(&mut self) {
+        self.finished = true;
+    }
+    fn finished(&self) -> bool {
+        self.finished
+    }
+    fn block_id(&self) -> BlockId {
+        self.block_id
+    }
+    fn port_id(&self) -> PortId {
+        self.port_id.clone()
+    }
+}
+
+impl<D> CpuBufferReader for Reader<D>
+where
+    D: CpuSample + Copy,
+{
+    type Item = D;
+
+    fn slice_with_tags(&mut self) -> (&[Self::Item], &Vec<ItemTag>) {
+        let s = self.segment.as_ref().unwrap();
+        let h = s.header();
+        let w = h.write_pos.load(Ordering::Acquire);
+        let r = h.read_pos.load(Ordering::Relaxed);
+        let available = (w - r) as usize;
+        s.pop_tags(&mut self.pending);
+
+        self.tags.clear();
+        for (index, tag) in self.pending.iter() {
+            if *index >= r && *index < w {
+                self.tags.push(ItemTag {
+                    index: (*index - r) as usize,
+                    tag: tag.clone(),
+                });
+            }
+        }
+
+        let cap = s.capacity();
+        let slice = unsafe {
+            let ptr = s.data().add((r as usize % cap) * size_of::<D>()) as *const D;
+            std::slice::from_raw_parts(ptr, available)
+        };
+        (slice, &self.tags)
+    }
+    fn consume(&mut self, amount: usize) {
+        let s = self.segment.as_ref().unwrap();
+        let h = s.header();
+        let r = h.read_pos.load(Ordering::Relaxed) + amount as u64;
+        debug_assert!(r <= h.write_pos.load(Ordering::Acquire));
+        self.pending.retain(|(index, _)| *index >= r);
+        h.read_pos.store(r, Ordering::Release);
+    }
+
+    fn set_min_items(&mut self, n: usize) {
+        if self.segment.is_some() {
+            warn!("buffer size configured after buffer is opened. This has no effect");
+        }
+        self.min_items = Some(n);
+    }
+
+    fn set_min_buffer_size_in_items(&mut self, n: usize) {
+        if self.segment.is_some() {
+            warn!("buffer size configured after buffer is opened. This has no effect");
+        }
+        self.min_buffer_size_in_items = Some(n);
+    }
+    fn max_items(&self) -> usize {
+        self.min_buffer_size_in_items.unwrap_or(usize::MAX)
+    }
+}
+
+impl<D> Drop for Reader<D>
+where
+    D: CpuSample + Copy,
+{
+    fn drop(&mut self) {
+        // detach, so that another reader can open the segment
+        if let Some(s) = self.segment.as_ref() {
+            let h = s.header();
+            h.reader_pid.store(0, Ordering::Release);
+            h.reader_attached.store(0, Ordering::Release);
+        }
+    }
+}
+
+impl<D> fmt::Debug for Reader<D>
+where
+    D: CpuSample + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("shm::Reader")
+            .field("name", &self.name())
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
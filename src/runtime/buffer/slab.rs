@@ -18,6 +18,7 @@ use crate::runtime::buffer::CpuBufferWriter;
 use crate::runtime::buffer::CpuSample;
 use crate::runtime::buffer::Tags;
 use crate::runtime::config;
+use crate::runtime::latency;
 
 #[derive(Debug)]
 struct BufferEmpty<D: CpuSample> {
@@ -425,6 +426,7 @@ where
         }
 
         let c = self.current.as_mut().unwrap();
+        latency::observe(self.block_id, &c.tags);
         (&c.buffer[c.offset..c.end_offset], &c.tags)
     }
 
@@ -1,9 +1,16 @@
 //! Remote Control through REST API
 use axum::Json;
 use axum::Router;
+use axum::body::Bytes;
 use axum::extract::Path;
+#[cfg(feature = "seify")]
+use axum::extract::Query;
 use axum::extract::State;
+use axum::http::HeaderMap;
 use axum::http::StatusCode;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::response::Response;
 use axum::routing::get;
 use axum::routing::get_service;
 use futures::channel::oneshot;
@@ -16,9 +23,12 @@ use tower_http::services::ServeDir;
 
 use crate::runtime::BlockDescription;
 use crate::runtime::BlockId;
+#[cfg(feature = "seify")]
+use crate::runtime::DeviceDescription;
 use crate::runtime::FlowgraphDescription;
 use crate::runtime::FlowgraphId;
 use crate::runtime::Pmt;
+use crate::runtime::PmtEncoding;
 use crate::runtime::PortId;
 use crate::runtime::RuntimeHandle;
 use crate::runtime::config;
@@ -64,14 +74,43 @@ async fn block_description(
     Err(StatusCode::BAD_REQUEST)
 }
 
+/// Encoding of the request body, given by its `Content-Type`. Defaults to JSON.
+fn request_encoding(headers: &HeaderMap) -> Result<PmtEncoding, StatusCode> {
+    match headers.get(header::CONTENT_TYPE) {
+        Some(v) => v
+            .to_str()
+            .ok()
+            .and_then(PmtEncoding::from_media_type)
+            .ok_or(StatusCode::UNSUPPORTED_MEDIA_TYPE),
+        None => Ok(PmtEncoding::Json),
+    }
+}
+
+/// Encoding of the response, i.e., the first supported type in `Accept`. Defaults to JSON.
+fn response_encoding(headers: &HeaderMap) -> PmtEncoding {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').find_map(PmtEncoding::from_media_type))
+        .unwrap_or_default()
+}
+
+fn pmt_response(pmt: &Pmt, encoding: PmtEncoding) -> Result<Response, StatusCode> {
+    let body = encoding
+        .encode(pmt)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(([(header::CONTENT_TYPE, encoding.media_type())], body).into_response())
+}
+
 async fn handler_id(
     Path((fg, blk, handler)): Path<(usize, BlockId, PortId)>,
     State(rt): State<RuntimeHandle>,
-) -> Result<Json<Pmt>, StatusCode> {
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
     let fg = rt.get_flowgraph(FlowgraphId(fg));
     if let Some(mut fg) = fg.await {
         if let Ok(ret) = fg.callback(blk, handler, Pmt::Null).await {
-            return Ok(Json::from(ret));
+            return pmt_response(&ret, response_encoding(&headers));
         }
     }
 
@@ -81,18 +120,39 @@ async fn handler_id(
 async fn handler_id_post(
     Path((fg, blk, handler)): Path<(usize, BlockId, PortId)>,
     State(rt): State<RuntimeHandle>,
-    Json(pmt): Json<Pmt>,
-) -> Result<Json<Pmt>, StatusCode> {
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, StatusCode> {
+    let pmt = request_encoding(&headers)?
+        .decode(&body)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
     let fg = rt.get_flowgraph(FlowgraphId(fg));
     if let Some(mut fg) = fg.await {
         if let Ok(ret) = fg.callback(blk, handler, pmt).await {
-            return Ok(Json::from(ret));
+            return pmt_response(&ret, response_encoding(&headers));
         }
     }
 
     Err(StatusCode::BAD_REQUEST)
 }
 
+/// Query of the device route, i.e., seify `Args` to filter the devices.
+#[cfg(feature = "seify")]
+#[derive(serde::Deserialize)]
+struct DeviceQuery {
+    args: Option<String>,
+}
+
+#[cfg(feature = "seify")]
+async fn devices(Query(q): Query<DeviceQuery>) -> Result<Json<Vec<DeviceDescription>>, StatusCode> {
+    // opening devices blocks
+    tokio::task::spawn_blocking(move || crate::blocks::seify::discover(q.args))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map(Json::from)
+        .map_err(|_| StatusCode::BAD_REQUEST)
+}
+
 pub struct ControlPort {
     thread: Option<(oneshot::Sender<()>, JoinHandle<()>)>,
     handle: RuntimeHandle,
@@ -117,14 +177,17 @@ impl ControlPort {
             return;
         }
 
-        let mut app = Router::new()
+        let app = Router::new()
             .route("/api/fg/", get(flowgraphs))
             .route("/api/fg/{fg}/", get(flowgraph_description))
             .route("/api/fg/{fg}/block/{blk}/", get(block_description))
             .route(
                 "/api/fg/{fg}/block/{blk}/call/{handler}/",
                 get(handler_id).post(handler_id_post),
-            )
+            );
+        #[cfg(feature = "seify")]
+        let app = app.route("/api/devices/", get(devices));
+        let mut app = app
             .layer(CorsLayer::permissive())
             .with_state(self.handle.clone());
 
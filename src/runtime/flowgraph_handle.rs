@@ -2,6 +2,7 @@ use futures::SinkExt;
 use futures::channel::mpsc::Sender;
 use futures::channel::oneshot;
 use std::cmp::PartialEq;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 
 use futuresdr::runtime::BlockDescription;
@@ -9,6 +10,7 @@ use futuresdr::runtime::BlockId;
 use futuresdr::runtime::Error;
 use futuresdr::runtime::FlowgraphDescription;
 use futuresdr::runtime::FlowgraphMessage;
+use futuresdr::runtime::FlowgraphSnapshot;
 use futuresdr::runtime::Pmt;
 use futuresdr::runtime::PortId;
 
@@ -95,6 +97,54 @@ impl FlowgraphHandle {
         Ok(d)
     }
 
+    /// Snapshot the settings of all blocks
+    ///
+    /// Collects the current values of all message handlers that have a
+    /// [`MessageInputSchema`](crate::runtime::MessageInputSchema) with a getter. Blocks are
+    /// identified by their instance name.
+    pub async fn snapshot(&mut self) -> Result<FlowgraphSnapshot, Error> {
+        let desc = self.description().await?;
+        let mut snapshot = FlowgraphSnapshot::new();
+        for b in desc.blocks {
+            let settings: BTreeMap<String, Pmt> = b
+                .message_inputs
+                .into_iter()
+                .zip(b.message_input_schemas)
+                .filter_map(|(handler, schema)| match schema.value {
+                    Some(Pmt::Null) | None => None,
+                    Some(v) => Some((handler, v)),
+                })
+                .collect();
+            if !settings.is_empty() {
+                snapshot.blocks.insert(b.instance_name, settings);
+            }
+        }
+        Ok(snapshot)
+    }
+
+    /// Restore the settings of a [`FlowgraphSnapshot`]
+    ///
+    /// Calls the message handlers of the blocks with the same instance name. Blocks that are not
+    /// part of the [`Flowgraph`] are ignored. Fails if a handler does not accept its value.
+    pub async fn restore(&mut self, snapshot: &FlowgraphSnapshot) -> Result<(), Error> {
+        let desc = self.description().await?;
+        for (name, settings) in &snapshot.blocks {
+            let Some(b) = desc.blocks.iter().find(|b| &b.instance_name == name) else {
+                warn!("restore snapshot: block {name} not found, ignoring");
+                continue;
+            };
+            for (handler, value) in settings {
+                let ret = self.callback(b.id, handler.as_str(), value.clone()).await?;
+                if matches!(ret, Pmt::InvalidValue) {
+                    return Err(Error::HandlerError(format!(
+                        "{name}: cannot restore {handler} to {value}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Send a terminate message to the [`Flowgraph`]
     ///
     /// Does not wait until the [`Flowgraph`] is actually terminated.
@@ -106,6 +156,36 @@ impl FlowgraphHandle {
         Ok(())
     }
 
+    /// Send a drain message to the [`Flowgraph`]
+    ///
+    /// Stops the stream sources, i.e., blocks with stream outputs but no
+    /// connected stream inputs. Downstream blocks process the samples that are
+    /// still in flight and shut down once their inputs are done. Blocks without
+    /// stream connections are terminated once all other blocks finished.
+    ///
+    /// Does not wait until the [`Flowgraph`] is actually terminated.
+    pub async fn drain(&mut self) -> Result<(), Error> {
+        self.inbox
+            .send(FlowgraphMessage::Drain)
+            .await
+            .map_err(|_| Error::FlowgraphTerminated)?;
+        Ok(())
+    }
+
+    /// Drain the [`Flowgraph`]
+    ///
+    /// Send a drain message to the [`Flowgraph`] and wait until it is shutdown.
+    pub async fn drain_and_wait(&mut self) -> Result<(), Error> {
+        self.drain().await?;
+        while !self.inbox.is_closed() {
+            #[cfg(not(target_arch = "wasm32"))]
+            async_io::Timer::after(std::time::Duration::from_millis(200)).await;
+            #[cfg(target_arch = "wasm32")]
+            gloo_timers::future::sleep(std::time::Duration::from_millis(200)).await;
+        }
+        Ok(())
+    }
+
     /// Terminate the [`Flowgraph`]
     ///
     /// Send a terminate message to the [`Flowgraph`] and wait until it is shutdown.
@@ -0,0 +1,361 @@
+//! Typed Message Handlers
+use num_complex::Complex32;
+use num_complex::Complex64;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+
+use crate::runtime::BlockMeta;
+use crate::runtime::HandlerSignature;
+use crate::runtime::MessageOutputs;
+use crate::runtime::Pmt;
+use crate::runtime::Result;
+use crate::runtime::WorkIo;
+use futuresdr_types::PmtConversionError;
+
+/// Argument of a message handler.
+///
+/// Message handlers do not have to take and return a [`Pmt`]. The [`Block`](crate::macros::Block)
+/// macro converts the [`Pmt`] to the argument type of the handler ([`HandlerArg`]) and its return
+/// value back to a [`Pmt`] ([`HandlerReturn`]). If the conversion of the argument fails, the
+/// handler is not called and the caller receives `Pmt::InvalidValue`.
+///
+/// ```
+/// use futuresdr::prelude::*;
+///
+/// #[derive(Block)]
+/// #[message_inputs(freq)]
+/// #[null_kernel]
+/// struct Tuner {
+///     freq: f64,
+/// }
+///
+/// impl Tuner {
+///     async fn freq(
+///         &mut self,
+///         _io: &mut WorkIo,
+///         _mio: &mut MessageOutputs,
+///         _meta: &mut BlockMeta,
+///         freq: f64,
+///     ) -> Result<()> {
+///         self.freq = freq;
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// Structs that implement serde's `Serialize` and `Deserialize` can be passed as [`Serde<T>`].
+pub trait HandlerArg: Sized {
+    /// Convert the [`Pmt`] that was sent to the handler.
+    fn from_pmt(p: Pmt) -> Result<Self, PmtConversionError>;
+    /// Type, as exported in the [`BlockDescription`](crate::runtime::BlockDescription).
+    fn type_description() -> String;
+}
+
+/// Return value of a message handler.
+pub trait HandlerReturn {
+    /// Convert the return value to the [`Pmt`] that is sent back to the caller.
+    fn into_pmt(self) -> Pmt;
+    /// Type, as exported in the [`BlockDescription`](crate::runtime::BlockDescription).
+    fn type_description() -> String;
+}
+
+/// Handler argument or return value that is converted with serde.
+///
+/// Structs are represented as `Pmt::MapStrPmt`, sequences as `Pmt::VecPmt`. Options are `None`
+/// for `Pmt::Null`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Serde<T>(pub T);
+
+impl HandlerArg for Pmt {
+    fn from_pmt(p: Pmt) -> Result<Self, PmtConversionError> {
+        Ok(p)
+    }
+    fn type_description() -> String {
+        "Pmt".to_string()
+    }
+}
+
+impl HandlerReturn for Pmt {
+    fn into_pmt(self) -> Pmt {
+        self
+    }
+    fn type_description() -> String {
+        "Pmt".to_string()
+    }
+}
+
+/// `Pmt::Null` as argument, `Pmt::Ok` as return value.
+impl HandlerArg for () {
+    fn from_pmt(p: Pmt) -> Result<Self, PmtConversionError> {
+        match p {
+            Pmt::Null => Ok(()),
+            _ => Err(PmtConversionError),
+        }
+    }
+    fn type_description() -> String {
+        "()".to_string()
+    }
+}
+
+impl HandlerReturn for () {
+    fn into_pmt(self) -> Pmt {
+        Pmt::Ok
+    }
+    fn type_description() -> String {
+        "()".to_string()
+    }
+}
+
+impl HandlerArg for String {
+    fn from_pmt(p: Pmt) -> Result<Self, PmtConversionError> {
+        match p {
+            Pmt::String(s) => Ok(s),
+            _ => Err(PmtConversionError),
+        }
+    }
+    fn type_description() -> String {
+        "String".to_string()
+    }
+}
+
+impl HandlerReturn for String {
+    fn into_pmt(self) -> Pmt {
+        Pmt::String(self)
+    }
+    fn type_description() -> String {
+        "String".to_string()
+    }
+}
+
+impl HandlerArg for f32 {
+    fn from_pmt(p: Pmt) -> Result<Self, PmtConversionError> {
+        match p {
+            Pmt::F32(v) => Ok(v),
+            p => f64::try_from(p).map(|v| v as f32),
+        }
+    }
+    fn type_description() -> String {
+        "f32".to_string()
+    }
+}
+
+impl HandlerArg for u32 {
+    fn from_pmt(p: Pmt) -> Result<Self, PmtConversionError> {
+        let v = u64::try_from(p)?;
+        u32::try_from(v).map_err(|_| PmtConversionError)
+    }
+    fn type_description() -> String {
+        "u32".to_string()
+    }
+}
+
+/// `None` for `Pmt::Null`, e.g., to query a value instead of setting it.
+impl<T: HandlerArg> HandlerArg for Option<T> {
+    fn from_pmt(p: Pmt) -> Result<Self, PmtConversionError> {
+        match p {
+            Pmt::Null => Ok(None),
+            p => T::from_pmt(p).map(Some),
+        }
+    }
+    fn type_description() -> String {
+        format!("Option<{}>", T::type_description())
+    }
+}
+
+/// `Pmt::Null` for `None`.
+impl<T: HandlerReturn> HandlerReturn for Option<T> {
+    fn into_pmt(self) -> Pmt {
+        match self {
+            Some(v) => v.into_pmt(),
+            None => Pmt::Null,
+        }
+    }
+    fn type_description() -> String {
+        format!("Option<{}>", T::type_description())
+    }
+}
+
+macro_rules! impl_handler_arg {
+    ($($t:ty),*) => {
+        $(
+            impl HandlerArg for $t {
+                fn from_pmt(p: Pmt) -> Result<Self, PmtConversionError> {
+                    p.try_into()
+                }
+                fn type_description() -> String {
+                    stringify!($t).to_string()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_handler_return {
+    ($($t:ty),*) => {
+        $(
+            impl HandlerReturn for $t {
+                fn into_pmt(self) -> Pmt {
+                    self.into()
+                }
+                fn type_description() -> String {
+                    stringify!($t).to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_handler_arg!(
+    bool,
+    f64,
+    u64,
+    usize,
+    isize,
+    Complex32,
+    Vec<f32>,
+    Vec<u64>,
+    Vec<u8>,
+    Vec<i16>,
+    Vec<i32>,
+    Vec<Complex32>,
+    Vec<Complex64>
+);
+
+impl_handler_return!(
+    bool,
+    f32,
+    f64,
+    u32,
+    u64,
+    usize,
+    isize,
+    Complex32,
+    Vec<f32>,
+    Vec<u64>,
+    Vec<u8>,
+    Vec<i16>,
+    Vec<i32>,
+    Vec<Complex32>,
+    Vec<Complex64>
+);
+
+impl<T: DeserializeOwned> HandlerArg for Serde<T> {
+    fn from_pmt(p: Pmt) -> Result<Self, PmtConversionError> {
+        serde_json::from_value(pmt_to_json(p)?)
+            .map(Serde)
+            .map_err(|_| PmtConversionError)
+    }
+    fn type_description() -> String {
+        std::any::type_name::<T>().to_string()
+    }
+}
+
+/// `Pmt::InvalidValue` if serialization fails.
+impl<T: Serialize> HandlerReturn for Serde<T> {
+    fn into_pmt(self) -> Pmt {
+        serde_json::to_value(self.0)
+            .map(json_to_pmt)
+            .unwrap_or(Pmt::InvalidValue)
+    }
+    fn type_description() -> String {
+        std::any::type_name::<T>().to_string()
+    }
+}
+
+fn pmt_to_json(p: Pmt) -> Result<Value, PmtConversionError> {
+    let v = match p {
+        Pmt::Null => Value::Null,
+        Pmt::Bool(v) => Value::Bool(v),
+        Pmt::String(v) => Value::String(v),
+        Pmt::Usize(v) => v.into(),
+        Pmt::Isize(v) => v.into(),
+        Pmt::U32(v) => v.into(),
+        Pmt::U64(v) => v.into(),
+        Pmt::F32(v) => v.into(),
+        Pmt::F64(v) => v.into(),
+        Pmt::CF32(v) => serde_json::to_value(v).map_err(|_| PmtConversionError)?,
+        Pmt::VecF32(v) => v.into(),
+        Pmt::VecU64(v) => v.into(),
+        Pmt::VecU8(v) | Pmt::Blob(v) => v.into(),
+        Pmt::VecI16(v) => v.into(),
+        Pmt::VecI32(v) => v.into(),
+        Pmt::VecCF32(v) => serde_json::to_value(v).map_err(|_| PmtConversionError)?,
+        Pmt::VecCF64(v) => serde_json::to_value(v).map_err(|_| PmtConversionError)?,
+        Pmt::VecPmt(v) | Pmt::Tuple(v) => {
+            Value::Array(v.into_iter().map(pmt_to_json).collect::<Result<_, _>>()?)
+        }
+        Pmt::MapStrPmt(m) => Value::Object(
+            m.into_iter()
+                .map(|(k, v)| Ok((k, pmt_to_json(v)?)))
+                .collect::<Result<_, _>>()?,
+        ),
+        _ => return Err(PmtConversionError),
+    };
+    Ok(v)
+}
+
+fn json_to_pmt(v: Value) -> Pmt {
+    match v {
+        Value::Null => Pmt::Null,
+        Value::Bool(v) => Pmt::Bool(v),
+        Value::Number(n) => {
+            if let Some(v) = n.as_u64() {
+                Pmt::U64(v)
+            } else if let Some(v) = n.as_i64() {
+                Pmt::Isize(v as isize)
+            } else {
+                Pmt::F64(n.as_f64().unwrap_or(f64::NAN))
+            }
+        }
+        Value::String(v) => Pmt::String(v),
+        Value::Array(v) => Pmt::VecPmt(v.into_iter().map(json_to_pmt).collect()),
+        Value::Object(m) => Pmt::MapStrPmt(
+            m.into_iter()
+                .map(|(k, v)| (k, json_to_pmt(v)))
+                .collect::<HashMap<_, _>>(),
+        ),
+    }
+}
+
+/// Convert the argument of a handler, logging a warning if the [`Pmt`] does not match.
+#[doc(hidden)]
+pub fn handler_arg<A: HandlerArg>(block: &str, handler: &str, p: Pmt) -> Option<A> {
+    let kind = p.kind();
+    let finished = matches!(p, Pmt::Finished);
+    match A::from_pmt(p) {
+        Ok(a) => Some(a),
+        Err(_) => {
+            if !finished {
+                warn!(
+                    "{block}: message handler {handler} expects {}, got {kind}",
+                    A::type_description()
+                );
+            }
+            None
+        }
+    }
+}
+
+/// Infer the return type of a handler from its (not awaited) future.
+#[doc(hidden)]
+pub fn handler_return<R, F: Future<Output = Result<R>>>(_f: &F) -> PhantomData<R> {
+    PhantomData
+}
+
+/// Infer the signature of a handler from a closure that is never called.
+#[doc(hidden)]
+pub fn handler_signature<S, A, R, F>(_handler: F) -> HandlerSignature
+where
+    A: HandlerArg,
+    R: HandlerReturn,
+    F: FnOnce(&mut S, &mut WorkIo, &mut MessageOutputs, &mut BlockMeta, A) -> PhantomData<R>,
+{
+    HandlerSignature {
+        argument: A::type_description(),
+        returns: R::type_description(),
+    }
+}
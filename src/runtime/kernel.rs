@@ -5,10 +5,13 @@ use futuresdr::runtime::BlockId;
 use futuresdr::runtime::BlockMessage;
 use futuresdr::runtime::BlockMeta;
 use futuresdr::runtime::Error;
+use futuresdr::runtime::HandlerSignature;
+use futuresdr::runtime::MessageInputSchema;
 use futuresdr::runtime::MessageOutputs;
 use futuresdr::runtime::Pmt;
 use futuresdr::runtime::PortId;
 use futuresdr::runtime::Result;
+use futuresdr::runtime::TagPropagation;
 use futuresdr::runtime::WorkIo;
 use futuresdr::runtime::buffer::BufferReader;
 
@@ -83,6 +86,8 @@ pub trait KernelInterface {
     fn is_blocking() -> bool;
     /// Name of the block
     fn type_name() -> &'static str;
+    /// Tag propagation policy of the block
+    fn tag_propagation() -> TagPropagation;
     /// Input Stream Ports
     fn stream_inputs(&self) -> Vec<String>;
     /// Output Stream Ports.
@@ -108,6 +113,10 @@ pub trait KernelInterface {
 
     /// Input Message Handler Names.
     fn message_inputs() -> &'static [&'static str];
+    /// Input Message Handler Signatures, in the order of [`message_inputs`](Self::message_inputs).
+    fn message_input_signatures() -> Vec<HandlerSignature>;
+    /// Input Message Handler Schemas, in the order of [`message_inputs`](Self::message_inputs).
+    fn message_input_schemas(&self) -> Vec<MessageInputSchema>;
     /// Output Message Handler Names.
     fn message_outputs() -> &'static [&'static str];
     /// Call message handlers of the kernel.
@@ -128,6 +137,8 @@ pub trait KernelInterface {
     fn is_blocking() -> bool;
     /// Name of the block
     fn type_name() -> &'static str;
+    /// Tag propagation policy of the block
+    fn tag_propagation() -> TagPropagation;
     /// Input Stream Ports
     fn stream_inputs(&self) -> Vec<String>;
     /// Output Stream Ports.
@@ -153,6 +164,10 @@ pub trait KernelInterface {
 
     /// Input Message Handler Names.
     fn message_inputs() -> &'static [&'static str];
+    /// Input Message Handler Signatures, in the order of [`message_inputs`](Self::message_inputs).
+    fn message_input_signatures() -> Vec<HandlerSignature>;
+    /// Input Message Handler Schemas, in the order of [`message_inputs`](Self::message_inputs).
+    fn message_input_schemas(&self) -> Vec<MessageInputSchema>;
     /// Output Message Handler Names.
     fn message_outputs() -> &'static [&'static str];
     /// Call message handlers of the kernel.
@@ -0,0 +1,211 @@
+//! Latency Tracing with Probe Tags
+//!
+//! A [`LatencyProbe`](crate::blocks::LatencyProbe) block injects [`Probe`]s as
+//! [`Tag::NamedAny`] tags with the name [`PROBE_TAG`] into the stream. Blocks
+//! that forward tags carry the probes downstream. Every time a probe shows up
+//! in the input buffer of a block, the wall-clock time since the probe was
+//! created is recorded in the [`LatencyRecorder`] that is shared by all probes
+//! of the injecting block.
+//!
+//! This results in one [`LatencyHistogram`] per path, i.e., from the probe
+//! block to each block that the probes reached.
+//!
+//! Probes refer to the recorder in the memory of the process. They are,
+//! therefore, not transferred through [shared-memory](crate::runtime::buffer::shm)
+//! buffers, and paths end at the writer of the segment.
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use web_time::Instant;
+
+use crate::runtime::BlockId;
+use crate::runtime::ItemTag;
+use crate::runtime::Pmt;
+use crate::runtime::Tag;
+
+/// Name of the [`Tag::NamedAny`] that carries a [`Probe`].
+pub const PROBE_TAG: &str = "latency_probe";
+
+/// Number of buckets of a [`LatencyHistogram`]
+pub const HISTOGRAM_BUCKETS: usize = 32;
+
+/// Latency probe, carried through the flowgraph as stream tag
+#[derive(Clone, Debug)]
+pub struct Probe {
+    id: u64,
+    created: Instant,
+    recorder: Arc<LatencyRecorder>,
+}
+
+impl Probe {
+    /// Create a probe, timestamped now.
+    pub fn new(id: u64, recorder: Arc<LatencyRecorder>) -> Self {
+        Self {
+            id,
+            created: Instant::now(),
+            recorder,
+        }
+    }
+    /// Sequence number of the probe
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+    /// Time since the probe was created
+    pub fn elapsed(&self) -> Duration {
+        self.created.elapsed()
+    }
+    /// Wrap the probe in a [`Tag`]
+    pub fn to_tag(&self) -> Tag {
+        Tag::NamedAny(PROBE_TAG.to_string(), Box::new(self.clone()))
+    }
+    /// Extract a probe from a [`Tag`]
+    pub fn from_tag(tag: &Tag) -> Option<&Probe> {
+        match tag {
+            Tag::NamedAny(name, a) if name == PROBE_TAG => a.downcast_ref::<Probe>(),
+            _ => None,
+        }
+    }
+}
+
+/// Histogram of latencies
+///
+/// Bucket `i` counts latencies in `[2^i, 2^(i+1))` microseconds. Bucket 0 also
+/// holds latencies below one microsecond and the last bucket everything above.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LatencyHistogram {
+    count: u64,
+    sum: Duration,
+    min: Duration,
+    max: Duration,
+    buckets: [u64; HISTOGRAM_BUCKETS],
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: Duration::ZERO,
+            min: Duration::MAX,
+            max: Duration::ZERO,
+            buckets: [0; HISTOGRAM_BUCKETS],
+        }
+    }
+}
+
+impl LatencyHistogram {
+    /// Add a latency sample
+    pub fn add(&mut self, latency: Duration) {
+        self.count += 1;
+        self.sum += latency;
+        self.min = self.min.min(latency);
+        self.max = self.max.max(latency);
+        let us = latency.as_micros().max(1);
+        let bucket = (u128::BITS - 1 - us.leading_zeros()) as usize;
+        self.buckets[bucket.min(HISTOGRAM_BUCKETS - 1)] += 1;
+    }
+    /// Number of samples
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Minimum latency
+    pub fn min(&self) -> Option<Duration> {
+        (self.count > 0).then_some(self.min)
+    }
+    /// Maximum latency
+    pub fn max(&self) -> Option<Duration> {
+        (self.count > 0).then_some(self.max)
+    }
+    /// Mean latency
+    pub fn mean(&self) -> Option<Duration> {
+        (self.count > 0).then(|| self.sum.div_f64(self.count as f64))
+    }
+    /// Histogram buckets
+    pub fn buckets(&self) -> &[u64; HISTOGRAM_BUCKETS] {
+        &self.buckets
+    }
+    /// Convert to a [`Pmt::MapStrPmt`] with latencies in microseconds
+    pub fn to_pmt(&self) -> Pmt {
+        let us = |d: Option<Duration>| Pmt::F64(d.map(|d| d.as_secs_f64() * 1e6).unwrap_or(0.0));
+        Pmt::MapStrPmt(HashMap::from([
+            ("count".to_string(), Pmt::U64(self.count)),
+            ("min_us".to_string(), us(self.min())),
+            ("max_us".to_string(), us(self.max())),
+            ("mean_us".to_string(), us(self.mean())),
+            ("buckets".to_string(), Pmt::VecU64(self.buckets.to_vec())),
+        ]))
+    }
+}
+
+#[derive(Debug, Default)]
+struct PathState {
+    last_probe: Option<u64>,
+    histogram: LatencyHistogram,
+}
+
+/// Collects the latencies of the probes of one probe block
+#[derive(Debug, Default)]
+pub struct LatencyRecorder {
+    paths: Mutex<HashMap<BlockId, PathState>>,
+}
+
+impl LatencyRecorder {
+    /// Create recorder
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Record that `probe` reached `block_id`.
+    ///
+    /// Sightings of the same or older probes are ignored, since a block can see
+    /// a tag several times before it consumes the tagged item.
+    pub fn record(&self, block_id: BlockId, probe: &Probe) {
+        let mut paths = self.paths.lock().unwrap();
+        let path = paths.entry(block_id).or_default();
+        if path.last_probe.is_some_and(|l| l >= probe.id) {
+            return;
+        }
+        path.last_probe = Some(probe.id);
+        path.histogram.add(probe.elapsed());
+    }
+    /// Histogram of the path to `block_id`
+    pub fn histogram(&self, block_id: BlockId) -> Option<LatencyHistogram> {
+        self.paths
+            .lock()
+            .unwrap()
+            .get(&block_id)
+            .map(|p| p.histogram.clone())
+    }
+    /// Histograms of all paths
+    pub fn histograms(&self) -> HashMap<BlockId, LatencyHistogram> {
+        self.paths
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (*k, v.histogram.clone()))
+            .collect()
+    }
+    /// Clear all histograms
+    pub fn reset(&self) {
+        for p in self.paths.lock().unwrap().values_mut() {
+            p.histogram = LatencyHistogram::default();
+        }
+    }
+    /// Convert to a [`Pmt::MapStrPmt`], mapping block ids to histograms
+    pub fn to_pmt(&self) -> Pmt {
+        Pmt::MapStrPmt(
+            self.histograms()
+                .into_iter()
+                .map(|(k, v)| (k.0.to_string(), v.to_pmt()))
+                .collect(),
+        )
+    }
+}
+
+/// Record all probes in `tags` that reached the input of `block_id`.
+pub(crate) fn observe(block_id: BlockId, tags: &[ItemTag]) {
+    for t in tags {
+        if let Some(p) = Probe::from_tag(&t.tag) {
+            p.recorder.record(block_id, p);
+        }
+    }
+}
@@ -30,7 +30,9 @@ mod logging;
 
 mod flowgraph;
 mod flowgraph_handle;
+mod handler;
 mod kernel;
+pub mod latency;
 mod message_io;
 #[cfg(not(target_arch = "wasm32"))]
 /// Mocker for unit testing and benchmarking
@@ -47,6 +49,15 @@ pub use block_meta::BlockMeta;
 pub use flowgraph::BlockRef;
 pub use flowgraph::Flowgraph;
 pub use flowgraph_handle::FlowgraphHandle;
+pub use handler::HandlerArg;
+pub use handler::HandlerReturn;
+pub use handler::Serde;
+#[doc(hidden)]
+pub use handler::handler_arg;
+#[doc(hidden)]
+pub use handler::handler_return;
+#[doc(hidden)]
+pub use handler::handler_signature;
 pub use kernel::Kernel;
 pub use kernel::KernelInterface;
 pub use message_io::MessageOutput;
@@ -55,13 +66,20 @@ pub use runtime::Runtime;
 pub use runtime::RuntimeHandle;
 pub use tag::ItemTag;
 pub use tag::Tag;
+pub use tag::TagPropagation;
 pub use work_io::WorkIo;
 
 pub use futuresdr_types::BlockDescription;
 pub use futuresdr_types::BlockId;
+pub use futuresdr_types::ChannelDescription;
+pub use futuresdr_types::DeviceDescription;
 pub use futuresdr_types::FlowgraphDescription;
 pub use futuresdr_types::FlowgraphId;
+pub use futuresdr_types::FlowgraphSnapshot;
+pub use futuresdr_types::HandlerSignature;
+pub use futuresdr_types::MessageInputSchema;
 pub use futuresdr_types::Pmt;
+pub use futuresdr_types::PmtEncoding;
 pub use futuresdr_types::PmtKind;
 pub use futuresdr_types::PortId;
 
@@ -90,6 +108,8 @@ pub fn init() {
 pub enum FlowgraphMessage {
     /// Terminate
     Terminate,
+    /// Stop sources and shut down once the data is processed
+    Drain,
     /// Initialize
     Initialized,
     /// Block is Done
@@ -396,6 +396,7 @@ pub(crate) async fn run_flowgraph<S: Scheduler>(
     debug!("wait for blocks init");
     // wait until all blocks are initialized
     let mut i = active_blocks;
+    let mut done = vec![false; ids.len()];
     let mut queue = Vec::new();
     let mut block_error = false;
     loop {
@@ -408,9 +409,10 @@ pub(crate) async fn run_flowgraph<S: Scheduler>(
         })?;
         match m {
             FlowgraphMessage::Initialized => i -= 1,
-            FlowgraphMessage::BlockError { .. } => {
+            FlowgraphMessage::BlockError { block_id } => {
                 i -= 1;
                 active_blocks -= 1;
+                done[block_id.0] = true;
                 block_error = true;
             }
             x => {
@@ -443,6 +445,7 @@ pub(crate) async fn run_flowgraph<S: Scheduler>(
     }
 
     let mut terminated = false;
+    let mut draining = false;
 
     // main loop
     loop {
@@ -502,12 +505,17 @@ pub(crate) async fn run_flowgraph<S: Scheduler>(
                     let _ = tx.send(Err(Error::InvalidBlock(block_id)));
                 }
             }
-            FlowgraphMessage::BlockDone { .. } => {
+            FlowgraphMessage::BlockDone { block_id } => {
                 active_blocks -= 1;
+                done[block_id.0] = true;
+                if draining && !terminated && stream_drained(&fg, &done) {
+                    let _ = main_channel.send(FlowgraphMessage::Terminate).await;
+                }
             }
-            FlowgraphMessage::BlockError { .. } => {
+            FlowgraphMessage::BlockError { block_id } => {
                 block_error = true;
                 active_blocks -= 1;
+                done[block_id.0] = true;
                 let _ = main_channel.send(FlowgraphMessage::Terminate).await;
             }
             FlowgraphMessage::BlockDescription { block_id, tx } => {
@@ -570,6 +578,25 @@ pub(crate) async fn run_flowgraph<S: Scheduler>(
                     terminated = true;
                 }
             }
+            FlowgraphMessage::Drain => {
+                if !terminated && !draining {
+                    draining = true;
+                    // stop stream sources, the rest of the graph shuts down once its inputs are done
+                    for id in ids.iter() {
+                        let source = fg.stream_edges.iter().any(|e| e.0 == *id)
+                            && !fg.stream_edges.iter().any(|e| e.2 == *id);
+                        if source
+                            && !done[id.0]
+                            && inboxes[id.0].send(BlockMessage::Terminate).await.is_err()
+                        {
+                            debug!("runtime tried to drain block that was already terminated");
+                        }
+                    }
+                    if stream_drained(&fg, &done) {
+                        let _ = main_channel.send(FlowgraphMessage::Terminate).await;
+                    }
+                }
+            }
             _ => warn!("main loop received unhandled message"),
         }
     }
@@ -580,3 +607,12 @@ pub(crate) async fn run_flowgraph<S: Scheduler>(
 
     Ok(fg)
 }
+
+/// All blocks with stream connections are done.
+///
+/// During a drain, the remaining (message-only) blocks are terminated at this point.
+fn stream_drained(fg: &Flowgraph, done: &[bool]) -> bool {
+    fg.stream_edges
+        .iter()
+        .all(|(src, _, dst, _)| done[src.0] && done[dst.0])
+}
@@ -3,6 +3,7 @@ use std::any::Any;
 use std::fmt;
 
 use crate::runtime::Pmt;
+use crate::runtime::buffer::Tags;
 
 pub trait TagAny: Any + DynClone + Send + Sync + 'static {
     fn as_any(&self) -> &dyn Any;
@@ -91,3 +92,94 @@ pub struct ItemTag {
     /// [`Tag`] value
     pub tag: Tag,
 }
+
+/// Tag propagation policy of a block
+///
+/// Declared with the `#[tag_propagation(...)]` attribute of the
+/// [`Block`](crate::macros::Block) derive macro, e.g.,
+/// `#[tag_propagation(one_to_one)]`, and queried through
+/// [`KernelInterface::tag_propagation`](crate::runtime::KernelInterface::tag_propagation).
+/// Blocks without the attribute default to [`TagPropagation::Custom`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TagPropagation {
+    /// Drop all tags
+    None,
+    /// Forward the tags of input `i` to output `i`
+    OneToOne,
+    /// Forward the tags of every input to every output
+    AllToAll,
+    /// The block handles tags itself
+    #[default]
+    Custom,
+}
+
+impl TagPropagation {
+    fn forwards(&self, input: usize, output: usize) -> bool {
+        match self {
+            TagPropagation::OneToOne => input == output,
+            TagPropagation::AllToAll => true,
+            TagPropagation::None | TagPropagation::Custom => false,
+        }
+    }
+
+    /// Forward tags from stream input `input` to stream output `output`, if the policy allows it.
+    ///
+    /// Only tags of the `consumed` input items are forwarded. Their indices are
+    /// rescaled by `produced / consumed` to account for decimation,
+    /// interpolation, and resampling. Nothing is forwarded for
+    /// [`TagPropagation::None`] and [`TagPropagation::Custom`].
+    ///
+    /// If the block consumed items without producing any, their tags are lost.
+    /// Blocks that can do this use [`TagPropagation::forward_held`].
+    pub fn forward(
+        &self,
+        input: usize,
+        output: usize,
+        tags: &[ItemTag],
+        consumed: usize,
+        out: &mut Tags,
+        produced: usize,
+    ) {
+        if !self.forwards(input, output) || consumed == 0 || produced == 0 {
+            return;
+        }
+        for t in tags.iter().filter(|t| t.index < consumed) {
+            out.add_tag(t.index * produced / consumed, t.tag.clone());
+        }
+    }
+
+    /// Forward tags like [`TagPropagation::forward`], but keep the tags of items
+    /// that did not result in output.
+    ///
+    /// If the block consumed items without producing any, their tags are moved
+    /// to `held`. With the next call that produces items, the held tags are
+    /// attached to the first produced item. The block keeps `held` across calls
+    /// to `work()`, one per output.
+    #[allow(clippy::too_many_arguments)]
+    pub fn forward_held(
+        &self,
+        input: usize,
+        output: usize,
+        tags: &[ItemTag],
+        consumed: usize,
+        out: &mut Tags,
+        produced: usize,
+        held: &mut Vec<Tag>,
+    ) {
+        if !self.forwards(input, output) {
+            return;
+        }
+        if produced == 0 {
+            held.extend(
+                tags.iter()
+                    .filter(|t| t.index < consumed)
+                    .map(|t| t.tag.clone()),
+            );
+            return;
+        }
+        for tag in held.drain(..) {
+            out.add_tag(0, tag);
+        }
+        self.forward(input, output, tags, consumed, out, produced);
+    }
+}
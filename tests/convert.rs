@@ -0,0 +1,146 @@
+use anyhow::Result;
+use futuresdr::blocks::ComplexToComplexInt;
+use futuresdr::blocks::ComplexToInt;
+use futuresdr::blocks::ComplexToPacked12;
+use futuresdr::blocks::Conversion;
+use futuresdr::blocks::Convert;
+use futuresdr::blocks::FloatToInt;
+use futuresdr::blocks::IntSample;
+use futuresdr::blocks::IntToComplex;
+use futuresdr::blocks::IntToFloat;
+use futuresdr::blocks::Packed12ToComplex;
+use futuresdr::prelude::*;
+use futuresdr::runtime::mocker::Mocker;
+use futuresdr::runtime::mocker::Reader;
+use futuresdr::runtime::mocker::Writer;
+
+fn run<C: Conversion>(conversion: C, input: Vec<C::In>) -> Vec<C::Out> {
+    let block: Convert<C, Reader<C::In>, Writer<C::Out>> = Convert::new(conversion);
+    let mut mock = Mocker::new(block);
+    mock.input().set(input);
+    mock.output().reserve(64);
+    mock.run();
+    mock.output().get().0
+}
+
+#[test]
+fn rtl_sdr() -> Result<()> {
+    let out = run(IntToComplex::<u8>::new(), vec![0, 255, 127, 128, 1]);
+    assert_eq!(
+        out,
+        vec![
+            Complex32::new(-1.0, 1.0),
+            Complex32::new(-0.5 / 127.5, 0.5 / 127.5)
+        ]
+    );
+
+    let out = run(ComplexToInt::<u8>::new(), out);
+    assert_eq!(out, vec![0, 255, 127, 128]);
+    Ok(())
+}
+
+#[test]
+fn sc16() -> Result<()> {
+    let input = vec![
+        Complex32::new(0.5, -0.5),
+        Complex32::new(2.0, -2.0),
+        Complex32::new(1.0, -1.0),
+    ];
+    let out = run(ComplexToComplexInt::<i16>::new(), input);
+    assert_eq!(
+        out,
+        vec![
+            Complex::new(16384, -16384),
+            Complex::new(i16::MAX, i16::MIN),
+            Complex::new(i16::MAX, i16::MIN),
+        ]
+    );
+
+    let out = run(FloatToInt::<i16>::with_scale(10.0), vec![1.26, -3.0]);
+    assert_eq!(out, vec![13, -30]);
+    let out = run(IntToFloat::<i16>::with_scale(0.5), out);
+    assert_eq!(out, vec![6.5, -15.0]);
+    Ok(())
+}
+
+/// Vectorized conversions have to match the per-sample definition, including the remainder.
+fn check_batch<T: IntSample + PartialEq + std::fmt::Debug>(input: &[f32], scale: f32) {
+    let mut ints = vec![T::from_f32(0.0); input.len()];
+    FloatToInt::<T>::with_scale(scale).convert(input, &mut ints);
+    let expected: Vec<T> = input
+        .iter()
+        .map(|v| T::from_f32(v * scale + T::OFFSET))
+        .collect();
+    assert_eq!(ints, expected);
+
+    let mut floats = vec![0.0; ints.len()];
+    IntToFloat::<T>::with_scale(1.0 / scale).convert(&ints, &mut floats);
+    let expected: Vec<f32> = ints
+        .iter()
+        .map(|v| (v.to_f32() - T::OFFSET) * (1.0 / scale))
+        .collect();
+    assert_eq!(floats, expected);
+}
+
+#[test]
+fn batch() -> Result<()> {
+    // ties, saturation, and values that round wrong when adding 0.5 before truncating
+    let mut input: Vec<f32> = (-1200..1200).map(|k| k as f32 * 0.25).collect();
+    input.extend([
+        0.49999997,
+        -0.49999997,
+        f32::NAN,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+        1e10,
+        -1e10,
+    ]);
+
+    check_batch::<u8>(&input, 1.0);
+    check_batch::<i8>(&input, 1.0);
+    check_batch::<i16>(&input, 1.0);
+    check_batch::<i16>(&input, 100.0);
+    Ok(())
+}
+
+#[test]
+fn packed12() -> Result<()> {
+    let input = vec![
+        Complex32::new(1.0, -1.0),
+        Complex32::new(0.25, -0.001),
+        Complex32::new(-2.0, 0.0),
+    ];
+    let packed = run(ComplexToPacked12::new(), input);
+    assert_eq!(packed.len(), 9);
+    assert_eq!(packed[..3], [0xff, 0x07, 0x80]);
+
+    let out = run(Packed12ToComplex::new(), packed);
+    assert_eq!(
+        out,
+        vec![
+            Complex32::new(2047.0 / 2048.0, -1.0),
+            Complex32::new(0.25, -2.0 / 2048.0),
+            Complex32::new(-1.0, 0.0),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn tags() -> Result<()> {
+    let block: Convert<IntToComplex<i8>, Reader<i8>, Writer<Complex32>> =
+        Convert::new(IntToComplex::new());
+    let mut mock = Mocker::new(block);
+    let tag = ItemTag {
+        index: 4,
+        tag: Tag::Id(1),
+    };
+    mock.input().set_with_tags(vec![0; 8], vec![tag]);
+    mock.output().reserve(4);
+    mock.run();
+    let (out, tags) = mock.output().get();
+    assert_eq!(out.len(), 4);
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].index, 2);
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use anyhow::Result;
+use float_cmp::assert_approx_eq;
+use futuresdr::blocks::DcBlocker;
+use futuresdr::blocks::FrequencyCorrector;
+use futuresdr::blocks::IqCorrector;
+use futuresdr::prelude::*;
+use futuresdr::runtime::mocker::Mocker;
+use futuresdr::runtime::mocker::Reader;
+use futuresdr::runtime::mocker::Writer;
+use std::f32::consts::TAU;
+
+fn tone(freq: f32, n: usize) -> Vec<Complex32> {
+    (0..n)
+        .map(|i| Complex32::from_polar(1.0, TAU * freq * i as f32))
+        .collect()
+}
+
+#[test]
+fn dc_blocker() -> Result<()> {
+    let block: DcBlocker<Reader<Complex32>, Writer<Complex32>> = DcBlocker::new(64);
+    let mut mock = Mocker::new(block);
+
+    let signal = tone(0.25, 1024);
+    let input = signal
+        .iter()
+        .map(|v| v + Complex32::new(0.5, -0.3))
+        .collect();
+    mock.input().set(input);
+    mock.output().reserve(1024);
+    mock.run();
+
+    let (out, _) = mock.output().get();
+    assert_eq!(out.len(), 1024);
+    for (o, s) in out[64..].iter().zip(&signal[64..]) {
+        assert_approx_eq!(f32, o.re, s.re, epsilon = 1e-4);
+        assert_approx_eq!(f32, o.im, s.im, epsilon = 1e-4);
+    }
+
+    assert_eq!(mock.post("length", Pmt::Usize(0)), Ok(Pmt::InvalidValue));
+    assert_eq!(mock.post("length", Pmt::Null), Ok(Pmt::Usize(64)));
+    Ok(())
+}
+
+#[test]
+fn iq_corrector() -> Result<()> {
+    let block: IqCorrector<Reader<Complex32>, Writer<Complex32>> = IqCorrector::new(1e-3);
+    let mut mock = Mocker::new(block);
+
+    let (gain, phase) = (1.2f32, 0.1f32);
+    let n = 20000;
+    let input = (0..n)
+        .map(|i| {
+            let t = TAU * 0.125 * i as f32;
+            Complex32::new(t.cos(), gain * (t + phase).sin())
+        })
+        .collect();
+    mock.input().set(input);
+    mock.output().reserve(n);
+    mock.run();
+
+    let Ok(Pmt::MapStrPmt(m)) = mock.post("imbalance", Pmt::Null) else {
+        panic!("no estimate");
+    };
+    assert_approx_eq!(
+        f64,
+        m["gain"].clone().try_into()?,
+        gain as f64,
+        epsilon = 1e-2
+    );
+    assert_approx_eq!(
+        f64,
+        m["phase"].clone().try_into()?,
+        phase as f64,
+        epsilon = 1e-2
+    );
+
+    // image at -fs/8 is suppressed
+    let (out, _) = mock.output().get();
+    let image: Complex32 = out[n - 1000..]
+        .iter()
+        .zip(tone(-0.125, n)[n - 1000..].iter())
+        .map(|(o, t)| o * t.conj())
+        .sum::<Complex32>()
+        / 1000.0;
+    assert!(image.norm() < 1e-2);
+    Ok(())
+}
+
+#[test]
+fn iq_corrector_zero_input() -> Result<()> {
+    // the estimates follow the input immediately
+    let block: IqCorrector<Reader<Complex32>, Writer<Complex32>> = IqCorrector::new(1.0);
+    let mut mock = Mocker::new(block);
+
+    let n = 1000;
+    let input = vec![Complex32::new(0.0, 0.0); n];
+    mock.input().set(input.clone());
+    mock.output().reserve(n);
+    mock.run();
+
+    let (out, _) = mock.output().get();
+    assert_eq!(out, input);
+    Ok(())
+}
+
+#[test]
+fn frequency_corrector() -> Result<()> {
+    let block: FrequencyCorrector<Reader<Complex32>, Writer<Complex32>> =
+        FrequencyCorrector::new(1000.0, 8000.0);
+    let mut mock = Mocker::new(block);
+    mock.input().set(tone(0.125, 100));
+    mock.output().reserve(100);
+    mock.run();
+    let (out, _) = mock.output().get();
+    for o in &out {
+        assert_approx_eq!(f32, o.re, out[0].re, epsilon = 1e-3);
+        assert_approx_eq!(f32, o.im, out[0].im, epsilon = 1e-3);
+    }
+
+    let block: FrequencyCorrector<Reader<Complex32>, Writer<Complex32>> =
+        FrequencyCorrector::tracking(8000.0, 1e-2);
+    let mut mock = Mocker::new(block);
+    mock.input().set(tone(0.0625, 2000));
+    mock.output().reserve(2000);
+    mock.run();
+    let Ok(Pmt::F64(offset)) = mock.post("offset", Pmt::Null) else {
+        panic!("no offset");
+    };
+    assert_approx_eq!(f64, offset, 500.0, epsilon = 1.0);
+
+    // a configured offset survives until there is a signal to track
+    let block: FrequencyCorrector<Reader<Complex32>, Writer<Complex32>> =
+        FrequencyCorrector::tracking(8000.0, 1e-2);
+    let mut mock = Mocker::new(block);
+    mock.post("offset", Pmt::F64(500.0))?;
+    mock.input().set(vec![Complex32::new(0.0, 0.0); 10]);
+    mock.output().reserve(10);
+    mock.run();
+    let Ok(Pmt::F64(offset)) = mock.post("offset", Pmt::Null) else {
+        panic!("no offset");
+    };
+    assert_approx_eq!(f64, offset, 500.0, epsilon = 1.0);
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::Copy;
+use futuresdr::blocks::MessageSink;
+use futuresdr::blocks::MessageSourceBuilder;
+use futuresdr::blocks::NullSink;
+use futuresdr::blocks::NullSource;
+use futuresdr::prelude::*;
+use std::time::Duration;
+
+#[test]
+fn drain() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = NullSource::<f32>::new();
+    let copy1 = Copy::<f32>::new();
+    let copy2 = Copy::<f32>::new();
+    let snk1 = NullSink::<f32>::new();
+    let snk2 = NullSink::<f32>::new();
+    let msg_src = MessageSourceBuilder::new(Pmt::Null, Duration::from_millis(10)).build();
+    let msg_snk = MessageSink::new();
+
+    connect!(fg, src > copy1 > copy2 > snk1; src > snk2; msg_src | msg_snk);
+
+    let rt = Runtime::new();
+    let (fg, mut handle) = rt.start_sync(fg)?;
+    block_on(async move {
+        futuresdr::async_io::Timer::after(Duration::from_millis(200)).await;
+        handle.drain().await?;
+        fg.await
+    })?;
+
+    // all samples in flight reached the sinks
+    let n1 = snk1.get()?.n_received();
+    let n2 = snk2.get()?.n_received();
+    assert!(n1 > 0);
+    assert_eq!(n1, n2);
+    assert!(msg_snk.get()?.received() > 0);
+
+    Ok(())
+}
+
+#[test]
+fn drain_and_wait() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = NullSource::<u8>::new();
+    let copy = Copy::<u8>::new();
+    let snk = NullSink::<u8>::new();
+
+    connect!(fg, src > copy > snk);
+
+    let rt = Runtime::new();
+    let (_task, mut handle) = rt.start_sync(fg)?;
+    block_on(async move {
+        futuresdr::async_io::Timer::after(Duration::from_millis(100)).await;
+        handle.drain_and_wait().await
+    })?;
+
+    assert!(snk.get()?.n_received() > 0);
+
+    Ok(())
+}
@@ -0,0 +1,302 @@
+use anyhow::Result;
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::FirBuilder;
+use futuresdr::blocks::NullSink;
+use futuresdr::blocks::NullSource;
+use futuresdr::prelude::*;
+use futuresdr::runtime::FlowgraphSnapshot;
+use futuresdr::runtime::HandlerSignature;
+use futuresdr::runtime::MessageInputSchema;
+use futuresdr::runtime::PmtKind;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    freq: f64,
+    antenna: Option<String>,
+}
+
+#[derive(Block)]
+#[message_inputs(freq, name, settings, raw)]
+#[null_kernel]
+struct Typed {
+    freq: f64,
+    settings: Settings,
+}
+
+impl Typed {
+    fn new() -> Self {
+        Self {
+            freq: 0.0,
+            settings: Settings::default(),
+        }
+    }
+
+    async fn freq(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        freq: Option<f64>,
+    ) -> Result<f64> {
+        if let Some(f) = freq {
+            self.freq = f;
+        }
+        Ok(self.freq)
+    }
+
+    async fn name(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        _p: (),
+    ) -> Result<String> {
+        Ok("typed".to_string())
+    }
+
+    async fn settings(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        Serde(settings): Serde<Settings>,
+    ) -> Result<Serde<Settings>> {
+        self.settings = settings;
+        Ok(Serde(self.settings.clone()))
+    }
+
+    async fn raw(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        Ok(p)
+    }
+}
+
+#[test]
+fn typed_handlers() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let typed = fg.add_block(Typed::new());
+    let id: BlockId = typed.clone().into();
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start_sync(fg)?;
+
+    block_on(async move {
+        assert_eq!(
+            handle.callback(id, "freq", Pmt::U64(100)).await?,
+            Pmt::F64(100.0)
+        );
+        assert_eq!(
+            handle.callback(id, "freq", Pmt::Null).await?,
+            Pmt::F64(100.0)
+        );
+        assert_eq!(
+            handle
+                .callback(id, "freq", Pmt::String("foo".to_string()))
+                .await?,
+            Pmt::InvalidValue
+        );
+        assert_eq!(
+            handle.callback(id, "name", Pmt::Null).await?,
+            Pmt::String("typed".to_string())
+        );
+        assert_eq!(
+            handle.callback(id, "name", Pmt::U32(1)).await?,
+            Pmt::InvalidValue
+        );
+
+        let settings = Pmt::MapStrPmt(HashMap::from([
+            ("freq".to_string(), Pmt::F64(2.4e9)),
+            ("antenna".to_string(), Pmt::String("RX2".to_string())),
+        ]));
+        assert_eq!(
+            handle.callback(id, "settings", settings.clone()).await?,
+            settings
+        );
+        assert_eq!(
+            handle.callback(id, "settings", Pmt::F64(1.0)).await?,
+            Pmt::InvalidValue
+        );
+        assert_eq!(handle.callback(id, "raw", Pmt::U32(1)).await?, Pmt::U32(1));
+
+        let desc = handle.description().await?;
+        let signatures = &desc.blocks[0].message_input_signatures;
+        let sig = |argument: &str, returns: &str| HandlerSignature {
+            argument: argument.to_string(),
+            returns: returns.to_string(),
+        };
+        assert_eq!(signatures[0], sig("Option<f64>", "f64"));
+        assert_eq!(signatures[1], sig("()", "String"));
+        assert_eq!(signatures[3], sig("Pmt", "Pmt"));
+        assert!(signatures[2].argument.ends_with("Settings"));
+
+        handle.terminate().await?;
+        task.await?;
+        Ok::<_, anyhow::Error>(())
+    })?;
+
+    let typed = typed.get()?;
+    assert_eq!(typed.freq, 100.0);
+    assert_eq!(typed.settings.freq, 2.4e9);
+    assert_eq!(typed.settings.antenna.as_deref(), Some("RX2"));
+    Ok(())
+}
+
+#[derive(Block)]
+#[message_inputs(gain, mode)]
+#[message_input_schema(gain, description = "Gain", kind = F64, min = -10, max = 30.0, step = 0.5, unit = "dB", getter = current_gain)]
+#[null_kernel]
+struct Amplifier {
+    gain: f64,
+}
+
+impl Amplifier {
+    fn current_gain(&self) -> f64 {
+        self.gain
+    }
+
+    async fn gain(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        gain: f64,
+    ) -> Result<()> {
+        self.gain = gain;
+        Ok(())
+    }
+
+    async fn mode(
+        &mut self,
+        _io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+        _p: Pmt,
+    ) -> Result<Pmt> {
+        Ok(Pmt::Ok)
+    }
+}
+
+#[test]
+fn schemas() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let amp = fg.add_block(Amplifier { gain: 1.0 });
+    let id: BlockId = amp.into();
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start_sync(fg)?;
+
+    block_on(async move {
+        assert_eq!(handle.callback(id, "gain", Pmt::F64(12.5)).await?, Pmt::Ok);
+
+        let desc = handle.block_description(id).await?;
+        let schema = &desc.message_input_schemas[0];
+        assert_eq!(schema.description.as_deref(), Some("Gain"));
+        assert_eq!(schema.kind, Some(PmtKind::F64));
+        assert_eq!(schema.min, Some(-10.0));
+        assert_eq!(schema.max, Some(30.0));
+        assert_eq!(schema.step, Some(0.5));
+        assert_eq!(schema.unit.as_deref(), Some("dB"));
+        assert_eq!(schema.value, Some(Pmt::F64(12.5)));
+        assert_eq!(desc.message_input_schemas[1], MessageInputSchema::default());
+
+        handle.terminate().await?;
+        task.await?;
+        Ok(())
+    })
+}
+
+#[test]
+fn snapshot_restore() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("fg-snapshot-{}.json", std::process::id()));
+    let rt = Runtime::new();
+
+    let mut fg = Flowgraph::new();
+    let amp = fg.add_block(Amplifier { gain: 1.0 });
+    let id: BlockId = amp.into();
+    fg.add_block(Typed::new());
+    let (task, mut handle) = rt.start_sync(fg)?;
+    block_on(async {
+        handle.callback(id, "gain", Pmt::F64(7.5)).await?;
+        let snapshot = handle.snapshot().await?;
+        assert_eq!(snapshot.blocks.len(), 1);
+        assert_eq!(snapshot.blocks["Amplifier-0"]["gain"], Pmt::F64(7.5));
+        snapshot.save(&path)?;
+        handle.terminate().await?;
+        task.await?;
+        Ok::<_, anyhow::Error>(())
+    })?;
+
+    let mut fg = Flowgraph::new();
+    let amp = fg.add_block(Amplifier { gain: 1.0 });
+    let (task, mut handle) = rt.start_sync(fg)?;
+    block_on(async {
+        let snapshot = FlowgraphSnapshot::load(&path)?;
+        std::fs::remove_file(&path)?;
+        handle.restore(&snapshot).await?;
+        handle.terminate().await?;
+        task.await?;
+        Ok::<_, anyhow::Error>(())
+    })?;
+    assert_eq!(amp.get()?.gain, 7.5);
+    Ok(())
+}
+
+#[test]
+fn snapshot_restore_fir() -> Result<()> {
+    let rt = Runtime::new();
+
+    let mut fg = Flowgraph::new();
+    let src = NullSource::<f32>::new();
+    let fir = FirBuilder::fir::<f32, f32, _>(vec![1.0f32, 1.0]);
+    let snk = NullSink::<f32>::new();
+    connect!(fg, src > fir > snk);
+    let id: BlockId = fir.into();
+    let taps = Pmt::VecF32(vec![0.5, 0.25, 0.125]);
+    let (task, mut handle) = rt.start_sync(fg)?;
+    let snapshot = block_on(async {
+        assert_eq!(handle.callback(id, "taps", taps.clone()).await?, Pmt::Ok);
+        let snapshot = handle.snapshot().await?;
+        handle.terminate().await?;
+        task.await?;
+        Ok::<_, anyhow::Error>(snapshot)
+    })?;
+    let (name, settings) = snapshot
+        .blocks
+        .iter()
+        .find(|(name, _)| name.starts_with("Fir"))
+        .unwrap();
+    assert_eq!(settings["taps"], taps);
+
+    let mut fg = Flowgraph::new();
+    let src = NullSource::<f32>::new();
+    let fir = FirBuilder::fir::<f32, f32, _>(vec![1.0f32, 1.0]);
+    let snk = NullSink::<f32>::new();
+    connect!(fg, src > fir > snk);
+    let id: BlockId = fir.into();
+    let (task, mut handle) = rt.start_sync(fg)?;
+    block_on(async {
+        let desc = handle.description().await?;
+        assert!(
+            desc.blocks
+                .iter()
+                .any(|b| b.id == id && &b.instance_name == name)
+        );
+        handle.restore(&snapshot).await?;
+        assert_eq!(handle.callback(id, "taps", Pmt::Null).await?, taps);
+        assert_eq!(
+            handle.callback(id, "taps", Pmt::VecF32(vec![])).await?,
+            Pmt::InvalidValue
+        );
+        handle.terminate().await?;
+        task.await?;
+        Ok(())
+    })
+}
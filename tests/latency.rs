@@ -0,0 +1,83 @@
+use anyhow::Result;
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::ApplyNM;
+use futuresdr::blocks::FirBuilder;
+use futuresdr::blocks::Head;
+use futuresdr::blocks::LatencyProbe;
+use futuresdr::blocks::NullSink;
+use futuresdr::blocks::NullSource;
+use futuresdr::prelude::*;
+
+#[test]
+fn probes_reach_downstream_blocks() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let n_items = 1_000_000;
+    let interval = 1000;
+    let src = NullSource::<f32>::new();
+    let head = Head::<f32>::new(n_items);
+    let probe = LatencyProbe::<f32>::new(interval);
+    let fir = FirBuilder::decimating::<f32, f32, Vec<f32>>(4);
+    let apply = ApplyNM::<_, _, _, 1, 2>::new(|i: &[f32], o: &mut [f32]| {
+        o[0] = i[0];
+        o[1] = i[0];
+    });
+    let snk = NullSink::<f32>::new();
+
+    connect!(fg, src > head > probe > fir > apply > snk);
+    let head_id: BlockId = head.clone().into();
+    let fir_id: BlockId = fir.clone().into();
+    let apply_id: BlockId = apply.clone().into();
+    let snk_id: BlockId = snk.clone().into();
+
+    Runtime::new().run(fg)?;
+
+    let recorder = probe.get()?.recorder();
+    let n_probes = n_items / interval;
+    assert!(recorder.histogram(head_id).is_none());
+    for id in [fir_id, apply_id, snk_id] {
+        let h = recorder.histogram(id).unwrap();
+        // the filter keeps some items at the end of the stream
+        assert!(h.count() >= n_probes - 1);
+        assert!(h.count() <= n_probes);
+        assert!(h.min() <= h.mean() && h.mean() <= h.max());
+        assert_eq!(h.buckets().iter().sum::<u64>(), h.count());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn latency_handler() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = NullSource::<f32>::new();
+    let probe = LatencyProbe::<f32>::new(100);
+    let snk = NullSink::<f32>::new();
+
+    connect!(fg, src > probe > snk);
+    let probe_id: BlockId = probe.into();
+    let snk_id: BlockId = snk.into();
+
+    let rt = Runtime::new();
+    let (fg, mut handle) = rt.start_sync(fg)?;
+    block_on(async move {
+        futuresdr::async_io::Timer::after(std::time::Duration::from_millis(200)).await;
+        let Pmt::MapStrPmt(paths) = handle.callback(probe_id, "latency", Pmt::Null).await? else {
+            panic!("latency handler returned wrong type");
+        };
+        let Some(Pmt::MapStrPmt(h)) = paths.get(&snk_id.0.to_string()) else {
+            panic!("no histogram for sink");
+        };
+        assert!(matches!(h.get("count"), Some(Pmt::U64(n)) if *n > 0));
+        assert!(matches!(h.get("buckets"), Some(Pmt::VecU64(b)) if b.len() == 32));
+
+        assert_eq!(
+            handle.callback(probe_id, "reset", Pmt::Null).await?,
+            Pmt::Ok
+        );
+        handle.terminate().await?;
+        let _ = fg.await;
+        Ok(())
+    })
+}
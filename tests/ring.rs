@@ -0,0 +1,170 @@
+use anyhow::Result;
+use futuresdr::blocks::Head;
+use futuresdr::blocks::NullSource;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSource;
+use futuresdr::prelude::*;
+use futuresdr::runtime::buffer::ring::OVERFLOW_TAG;
+use futuresdr::runtime::buffer::ring::OverflowPolicy;
+use futuresdr::runtime::buffer::ring::Reader;
+use futuresdr::runtime::buffer::ring::Writer;
+use std::time::Duration;
+
+/// Consumes a few items per call and sleeps, to provoke overflows.
+#[derive(Block)]
+struct SlowSink {
+    #[input]
+    input: Reader<u32>,
+    received: u64,
+    lost: u64,
+}
+
+impl SlowSink {
+    fn new() -> Self {
+        let mut input = Reader::default();
+        input.set_overflow_policy(OverflowPolicy::Overwrite);
+        Self {
+            input,
+            received: 0,
+            lost: 0,
+        }
+    }
+}
+
+impl Kernel for SlowSink {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, tags) = self.input.slice_with_tags();
+        let i_len = i.len();
+        let n = std::cmp::min(i_len, 256);
+        for t in tags.iter() {
+            if let Tag::NamedUsize(name, lost) = &t.tag
+                && name == OVERFLOW_TAG
+            {
+                assert_eq!(t.index, 0);
+                self.lost += *lost as u64;
+            }
+        }
+        let finished = self.input.finished() && n == i_len;
+        self.input.consume(n);
+        self.received += n as u64;
+
+        if finished {
+            io.finished = true;
+        } else if n > 0 {
+            std::thread::sleep(Duration::from_millis(1));
+            io.call_again = true;
+        }
+        Ok(())
+    }
+}
+
+/// Looks at the latest items without ever consuming them.
+#[derive(Block)]
+struct Peek {
+    #[input]
+    input: Reader<u32>,
+    held: u64,
+    lost: u64,
+}
+
+impl Peek {
+    fn new() -> Self {
+        let mut input = Reader::default();
+        input.set_overflow_policy(OverflowPolicy::Overwrite);
+        Self {
+            input,
+            held: 0,
+            lost: 0,
+        }
+    }
+}
+
+impl Kernel for Peek {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, _) = self.input.slice_with_tags();
+        self.held = i.len() as u64;
+        self.lost = self.input.stats().unwrap().lost;
+        if self.input.finished() {
+            io.finished = true;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn multi_reader() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let n_items = 1_000_000;
+    let orig: Vec<u32> = (0..n_items as u32).collect();
+
+    let src = VectorSource::<u32, Writer<u32>>::new(orig.clone());
+    let snk1 = VectorSink::<u32, Reader<u32>>::new(n_items);
+    let snk2 = VectorSink::<u32, Reader<u32>>::new(n_items);
+
+    connect!(fg, src > snk1; src > snk2);
+
+    Runtime::new().run(fg)?;
+
+    assert_eq!(snk1.get()?.items(), &orig);
+    assert_eq!(snk2.get()?.items(), &orig);
+
+    Ok(())
+}
+
+#[test]
+fn overwrite() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let n_items = 2_000_000;
+    let src = NullSource::<u32, Writer<u32>>::new();
+    let mut head = Head::<u32, Reader<u32>, Writer<u32>>::new(n_items);
+    // overwrite in chunks of 1024 items
+    head.output().set_min_items(1024);
+    let snk = VectorSink::<u32, Reader<u32>>::new(n_items as usize);
+    let slow = SlowSink::new();
+
+    connect!(fg, src > head > snk; head > slow);
+
+    Runtime::new().run(fg)?;
+
+    assert_eq!(snk.get()?.items().len(), n_items as usize);
+    let slow = slow.get()?;
+    assert!(slow.lost > 0);
+    assert_eq!(slow.received + slow.lost, n_items);
+
+    Ok(())
+}
+
+#[test]
+fn overwrite_without_consume() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let n_items = 2_000_000;
+    let src = NullSource::<u32, Writer<u32>>::new();
+    let mut head = Head::<u32, Reader<u32>, Writer<u32>>::new(n_items);
+    head.output().set_min_items(1024);
+    let snk = VectorSink::<u32, Reader<u32>>::new(n_items as usize);
+    let peek = Peek::new();
+
+    connect!(fg, src > head > snk; head > peek);
+
+    Runtime::new().run(fg)?;
+
+    assert_eq!(snk.get()?.items().len(), n_items as usize);
+    let peek = peek.get()?;
+    assert!(peek.lost > 0);
+    assert_eq!(peek.held + peek.lost, n_items);
+
+    Ok(())
+}
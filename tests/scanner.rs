@@ -0,0 +1,79 @@
+use anyhow::Result;
+use futuresdr::blocks::Scanner;
+use futuresdr::prelude::*;
+use futuresdr::runtime::mocker::Mocker;
+use futuresdr::runtime::mocker::Reader;
+use futuresdr::runtime::mocker::Writer;
+
+fn tag(freq: f64, index: usize) -> Tag {
+    Tag::Data(Pmt::MapStrPmt(
+        [
+            ("freq".to_string(), Pmt::F64(freq)),
+            ("index".to_string(), Pmt::Usize(index)),
+        ]
+        .into(),
+    ))
+}
+
+#[test]
+fn sweep() -> Result<()> {
+    let scanner: Scanner<u32, Reader<u32>, Writer<u32>> = Scanner::range(1.0, 3.0, 1.0, 4, 2);
+
+    let mut mock = Mocker::new(scanner);
+    mock.init();
+    mock.output().reserve(64);
+    mock.input().set((0..20).collect());
+    mock.run();
+
+    let (out, tags) = mock.output().get();
+    assert_eq!(out, vec![2, 3, 4, 5, 8, 9, 10, 11, 14, 15, 16, 17]);
+    let tags: Vec<(usize, Tag)> = tags.into_iter().map(|t| (t.index, t.tag)).collect();
+    assert_eq!(
+        tags,
+        vec![(0, tag(1.0, 0)), (4, tag(2.0, 1)), (8, tag(3.0, 2))]
+    );
+    assert_eq!(
+        mock.take_messages(),
+        vec![vec![
+            Pmt::F64(1.0),
+            Pmt::F64(2.0),
+            Pmt::F64(3.0),
+            Pmt::F64(1.0)
+        ]]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn retune() -> Result<()> {
+    let scanner: Scanner<u32, Reader<u32>, Writer<u32>> = Scanner::new(vec![1.0, 2.0], 3, 1);
+
+    let mut mock = Mocker::new(scanner);
+    mock.init();
+    mock.output().reserve(64);
+
+    let f = Pmt::VecPmt(vec![Pmt::F64(5.0), Pmt::F64(6.0)]);
+    assert_eq!(mock.post("frequencies", f.clone()), Ok(Pmt::Ok));
+    assert_eq!(mock.post("settle", Pmt::Usize(0)), Ok(Pmt::Usize(0)));
+    assert_eq!(mock.post("dwell", Pmt::Usize(0)), Ok(Pmt::InvalidValue));
+    assert_eq!(mock.post("dwell", Pmt::Null), Ok(Pmt::Usize(3)));
+    assert_eq!(
+        mock.post("frequencies", Pmt::VecPmt(vec![])),
+        Ok(Pmt::InvalidValue)
+    );
+
+    mock.input().set((0..6).collect());
+    mock.run();
+
+    let (out, tags) = mock.output().get();
+    assert_eq!(out, vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags[1].tag, tag(6.0, 1));
+    assert_eq!(
+        mock.take_messages(),
+        vec![vec![Pmt::F64(5.0), Pmt::F64(6.0), Pmt::F64(5.0)]]
+    );
+
+    Ok(())
+}
@@ -207,3 +207,12 @@ fn sink_config_cmd_map() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn discover_devices() -> Result<()> {
+    let devs = discover("driver=dummy")?;
+    assert_eq!(devs.len(), 1);
+    assert_eq!(devs[0].driver, "Dummy");
+    assert!(!devs[0].rx.is_empty());
+    Ok(())
+}
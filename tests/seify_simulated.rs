@@ -0,0 +1,270 @@
+use anyhow::Result;
+use float_cmp::assert_approx_eq;
+use futuresdr::blocks::Head;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSource;
+use futuresdr::blocks::seify::*;
+use futuresdr::prelude::*;
+use futuresdr::runtime::mocker::Mocker;
+use futuresdr::runtime::mocker::Reader;
+use futuresdr::runtime::mocker::Writer;
+use futuresdr::seify::Device;
+use futuresdr::seify::Direction::*;
+use futuresdr::seify::RxStreamer;
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+fn read(dev: &Device<Simulated>, n: usize) -> Result<Vec<Complex32>> {
+    let mut s = dev.rx_streamer(&[0])?;
+    s.activate()?;
+    let mut buf = vec![Complex32::new(0.0, 0.0); n];
+    assert_eq!(s.read(&mut [&mut buf], 0)?, n);
+    Ok(buf)
+}
+
+#[test]
+fn tone() -> Result<()> {
+    let dev = Device::from_impl(Simulated::new().realtime(false).tone(100.25e6, 0.5));
+    dev.set_frequency(Rx, 0, 100e6)?;
+    dev.set_sample_rate(Rx, 0, 1e6)?;
+    dev.set_gain(Rx, 0, 20.0)?;
+
+    let s = read(&dev, 8)?;
+    // quarter of the sample rate
+    for (i, v) in s.iter().enumerate() {
+        let expected = Complex32::from_polar(5.0, i as f32 * std::f32::consts::FRAC_PI_2);
+        assert_approx_eq!(f32, v.re, expected.re, epsilon = 1e-4);
+        assert_approx_eq!(f32, v.im, expected.im, epsilon = 1e-4);
+    }
+
+    assert!(dev.set_gain(Rx, 0, 100.0).is_err());
+    assert!(dev.set_frequency(Rx, 1, 100e6).is_err());
+    Ok(())
+}
+
+#[test]
+fn sigmf_resample() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("sigmf-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let meta = r#"{
+        "global": { "core:datatype": "ci16_le", "core:sample_rate": 500000, "core:version": "1.0.0" },
+        "captures": [ { "core:sample_start": 0, "core:frequency": 433.92e6 } ],
+        "annotations": []
+    }"#;
+    std::fs::write(dir.join("rec.sigmf-meta"), meta)?;
+    let data: Vec<u8> = [16384i16, 0, 0, -16384]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    std::fs::write(dir.join("rec.sigmf-data"), data)?;
+
+    let rec = Recording::from_sigmf(dir.join("rec.sigmf-data"))?;
+    std::fs::remove_dir_all(&dir)?;
+
+    let dev = Device::from_impl(Simulated::new().realtime(false).recording(rec));
+    dev.set_frequency(Rx, 0, 433.92e6)?;
+    dev.set_sample_rate(Rx, 0, 1e6)?;
+
+    let s = read(&dev, 5)?;
+    let expected = [
+        Complex32::new(0.5, 0.0),
+        Complex32::new(0.25, -0.25),
+        Complex32::new(0.0, -0.5),
+        Complex32::new(0.25, -0.25),
+        Complex32::new(0.5, 0.0),
+    ];
+    for (v, e) in s.iter().zip(expected) {
+        assert_approx_eq!(f32, v.re, e.re, epsilon = 1e-6);
+        assert_approx_eq!(f32, v.im, e.im, epsilon = 1e-6);
+    }
+    Ok(())
+}
+
+#[test]
+fn overflow() -> Result<()> {
+    let dev = Device::from_impl(Simulated::new().buffer_size(1000));
+    dev.set_sample_rate(Rx, 0, 1e6)?;
+
+    let mut s = dev.rx_streamer(&[0])?;
+    let mut buf = vec![Complex32::new(0.0, 0.0); 100];
+    assert!(matches!(
+        s.read(&mut [&mut buf], 0),
+        Err(futuresdr::seify::Error::Inactive)
+    ));
+    s.activate()?;
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(matches!(
+        s.read(&mut [&mut buf], 0),
+        Err(futuresdr::seify::Error::Overflow)
+    ));
+    assert_eq!(s.read(&mut [&mut buf], 1_000_000)?, 100);
+    Ok(())
+}
+
+#[test]
+fn loopback() -> Result<()> {
+    let sim = Simulated::new().realtime(false).loopback(10, 6.0);
+    let dev = Device::from_impl(sim);
+    let input: Vec<Complex32> = (0..1000).map(|i| Complex32::new(i as f32, 0.0)).collect();
+
+    let mut fg = Flowgraph::new();
+    let src = VectorSource::<Complex32>::new(input.clone());
+    let snk = Builder::from_device(dev.clone())
+        .frequency(2.4e9)
+        .build_sink()?;
+    connect!(fg, src > inputs[0].snk);
+
+    let rx = Builder::from_device(dev).frequency(2.4e9).build_source()?;
+    let head = Head::<Complex32>::new(1010);
+    let vect = VectorSink::<Complex32>::new(1010);
+    connect!(fg, rx.outputs[0] > head > vect);
+
+    Runtime::new().run(fg)?;
+
+    let vect = vect.get()?;
+    let out = vect.items();
+    assert_eq!(out.len(), 1010);
+    assert!(out[..10].iter().all(|v| v.norm() == 0.0));
+    for (o, i) in out[10..].iter().zip(input) {
+        assert_approx_eq!(f32, o.re, i.re * 0.501_187, epsilon = 1e-2);
+    }
+    Ok(())
+}
+
+#[test]
+fn multi_channel() -> Result<()> {
+    let sim = Simulated::new()
+        .channels(2)
+        .realtime(false)
+        .tone(100.25e6, 1.0);
+    let dev = Device::from_impl(sim);
+    let src = Builder::from_device(dev.clone())
+        .channels(vec![0, 1])
+        .frequency(100e6)
+        .start_time(42)
+        .build_source_with_buffer::<Writer<Complex32>>()?;
+
+    let mut mock = Mocker::new(src);
+    assert_eq!(
+        mock.post(
+            "freq",
+            Pmt::VecPmt(vec![Pmt::F64(100e6), Pmt::U64(100_250_000)])
+        ),
+        Ok(Pmt::Ok)
+    );
+    assert_eq!(mock.post("freq", Pmt::Null), Ok(Pmt::F64(100e6)));
+    assert_eq!(
+        mock.post("freq", Pmt::VecF32(vec![1.0, 2.0, 3.0])),
+        Ok(Pmt::InvalidValue)
+    );
+    let cmd = Pmt::MapStrPmt(HashMap::from([
+        ("chan".to_string(), Pmt::U32(1)),
+        ("gain".to_string(), Pmt::F64(20.0)),
+    ]));
+    assert_eq!(mock.post("cmd", cmd), Ok(Pmt::Ok));
+    assert_approx_eq!(f64, dev.gain(Rx, 0)?.unwrap(), 0.0);
+    assert_approx_eq!(f64, dev.gain(Rx, 1)?.unwrap(), 20.0);
+    let Ok(Pmt::MapStrPmt(config)) = mock.post("config", Pmt::Usize(1)) else {
+        panic!("no config");
+    };
+    assert_eq!(config["freq"], Pmt::F64(100.25e6));
+
+    let before = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as usize;
+    mock.init();
+    let after = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as usize;
+    mock.outputs().iter_mut().for_each(|o| o.reserve(16));
+    mock.run();
+    mock.deinit();
+
+    let (out0, tags0) = mock.outputs()[0].get();
+    let (out1, tags1) = mock.outputs()[1].get();
+    assert_eq!(out0.len(), 16);
+    assert_eq!(out1.len(), 16);
+    // tone at +250 kHz on channel 0, at DC with 20 dB gain on channel 1
+    assert_approx_eq!(f32, out0[1].im, 1.0, epsilon = 1e-4);
+    assert!(out1.iter().all(|v| (v.re - 10.0).abs() < 1e-4));
+    for tags in [tags0, tags1] {
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].index, 0);
+        // activation time plus the start time delay
+        let Tag::NamedUsize(ref name, t) = tags[0].tag else {
+            panic!("no rx_time tag");
+        };
+        assert_eq!(name, "rx_time");
+        assert!((before + 42..=after + 42).contains(&t));
+    }
+    Ok(())
+}
+
+#[test]
+fn tx_bursts() -> Result<()> {
+    let dev = Device::from_impl(Simulated::new().realtime(false).loopback(0, 0.0));
+    let snk = Builder::from_device(dev.clone()).build_sink_with_buffer::<Reader<Complex32>>()?;
+
+    let input: Vec<Complex32> = (0..30).map(|i| Complex32::new(i as f32, 0.0)).collect();
+    let tag = |index, name: &str, v| ItemTag {
+        index,
+        tag: Tag::NamedUsize(name.to_string(), v),
+    };
+    let later = SystemTime::now().duration_since(UNIX_EPOCH)? + Duration::from_secs(10);
+    // the last burst is truncated by the end of the input
+    let tags = vec![
+        tag(0, "burst_start", 10),
+        tag(0, "tx_time", 1),
+        tag(10, "burst_start", 0),
+        tag(19, "burst_end", 0),
+        tag(20, "burst_start", 20),
+        tag(20, "tx_time", later.as_nanos() as usize),
+    ];
+
+    let mut mock = Mocker::new(snk);
+    mock.inputs()[0].set_with_tags(input.clone(), tags);
+    mock.init();
+    mock.run();
+
+    let events = mock.take_messages().pop().unwrap();
+    assert_eq!(
+        events,
+        vec![Pmt::MapStrPmt(HashMap::from([
+            ("event".to_string(), Pmt::String("late".to_string())),
+            ("tx_time".to_string(), Pmt::Usize(1)),
+        ]))]
+    );
+    assert_eq!(read(&dev, 30)?, input);
+    mock.deinit();
+
+    let writes = dev.impl_ref::<Simulated>()?.tx_writes();
+    assert_eq!(writes.len(), 3);
+    let burst = TxWrite {
+        samples: 10,
+        at_ns: None,
+        end_burst: true,
+    };
+    assert_eq!(writes[0], burst);
+    assert_eq!(writes[1], burst);
+    assert_eq!(writes[2].samples, 10);
+    assert!(writes[2].at_ns.is_some_and(|t| t > 9_000_000_000));
+    assert!(writes[2].end_burst);
+    Ok(())
+}
+
+#[test]
+fn describe_device() -> Result<()> {
+    let dev = Device::from_impl(Simulated::new().channels(2));
+    let d = describe("driver=simulated", &dev)?;
+    assert_eq!(d.args, "driver=simulated");
+    assert_eq!(d.driver, "Dummy");
+    assert_eq!(d.id.as_deref(), Some("simulated"));
+    assert_eq!(d.rx.len(), 2);
+    assert_eq!(d.tx.len(), 2);
+    assert_eq!(d.rx[1].antennas, vec!["A".to_string()]);
+    assert_eq!(
+        d.rx[0].sample_rate_range,
+        Pmt::from(futuresdr::seify::Range::new(vec![
+            futuresdr::seify::RangeItem::Interval(1e3, 100e6)
+        ]))
+    );
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use anyhow::Result;
+use futuresdr::blocks::Copy;
+use futuresdr::blocks::NullSource;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSource;
+use futuresdr::prelude::*;
+use futuresdr::runtime::buffer::shm::Reader;
+use futuresdr::runtime::buffer::shm::Writer;
+use std::iter::repeat_with;
+
+#[test]
+fn flowgraph() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let n_items = 1_000_000;
+    let orig: Vec<f32> = repeat_with(rand::random::<f32>).take(n_items).collect();
+
+    let src = VectorSource::<f32, Writer<f32>>::new(orig.clone());
+    let copy = Copy::<f32, Reader<f32>, Writer<f32>>::new();
+    let snk = VectorSink::<f32, Reader<f32>>::new(n_items);
+
+    connect!(fg, src > copy > snk);
+
+    Runtime::new().run(fg)?;
+
+    let snk = snk.get()?;
+    let v = snk.items();
+
+    assert_eq!(v, &orig);
+
+    Ok(())
+}
+
+#[test]
+fn separate_flowgraphs() -> Result<()> {
+    let name = format!("/futuresdr-test-{}", std::process::id());
+    let n_items = 1_000_000;
+    let orig: Vec<u32> = (0..n_items as u32).collect();
+
+    let mut src = VectorSource::<u32, Writer<u32>>::new(orig.clone());
+    src.output().create(&name)?;
+    let mut fg_tx = Flowgraph::new();
+    fg_tx.add_block(src);
+
+    let mut snk = VectorSink::<u32, Reader<u32>>::new(n_items);
+    snk.input().open(&name)?;
+    let mut fg_rx = Flowgraph::new();
+    let snk = fg_rx.add_block(snk);
+
+    let rx = std::thread::spawn(move || Runtime::new().run(fg_rx));
+    Runtime::new().run(fg_tx)?;
+    rx.join().unwrap()?;
+
+    let snk = snk.get()?;
+    assert_eq!(snk.items(), &orig);
+
+    Ok(())
+}
+
+const CHILD_ROLE: &str = "FUTURESDR_SHM_CHILD";
+const CHILD_SEGMENT: &str = "FUTURESDR_SHM_SEGMENT";
+
+fn spawn_child(role: &str, name: &str) -> Result<std::process::Child> {
+    Ok(std::process::Command::new(std::env::current_exe()?)
+        .args(["--exact", "child", "--nocapture"])
+        .env(CHILD_ROLE, role)
+        .env(CHILD_SEGMENT, name)
+        .spawn()?)
+}
+
+// Entry point of the processes spawned by the tests below
+#[test]
+fn child() -> Result<()> {
+    let (Ok(role), Ok(name)) = (std::env::var(CHILD_ROLE), std::env::var(CHILD_SEGMENT)) else {
+        return Ok(());
+    };
+
+    let mut snk = VectorSink::<u32, Reader<u32>>::new(1000);
+    snk.input().open(&name)?;
+
+    if role == "dead" {
+        // terminate without finishing the port
+        std::process::exit(0);
+    }
+
+    let mut fg = Flowgraph::new();
+    let snk = fg.add_block(snk);
+    Runtime::new().run(fg)?;
+    let snk = snk.get()?;
+    assert_eq!(snk.items(), &(0..100_000).collect::<Vec<u32>>());
+    Ok(())
+}
+
+#[test]
+fn separate_processes() -> Result<()> {
+    let name = format!("/futuresdr-test-proc-{}", std::process::id());
+
+    let mut src = VectorSource::<u32, Writer<u32>>::new((0..100_000).collect());
+    src.output().create(&name)?;
+    let mut fg = Flowgraph::new();
+    fg.add_block(src);
+
+    let mut child = spawn_child("reader", &name)?;
+    Runtime::new().run(fg)?;
+    assert!(child.wait()?.success());
+
+    Ok(())
+}
+
+#[test]
+fn dead_reader() -> Result<()> {
+    let name = format!("/futuresdr-test-dead-{}", std::process::id());
+
+    let mut src = NullSource::<u32, Writer<u32>>::new();
+    src.output().create(&name)?;
+    let mut fg = Flowgraph::new();
+    fg.add_block(src);
+
+    assert!(spawn_child("dead", &name)?.wait()?.success());
+    // the writer has to terminate, even though the reader never finished
+    Runtime::new().run(fg)?;
+
+    Ok(())
+}
+
+#[test]
+fn reattach() -> Result<()> {
+    let name = format!("/futuresdr-test-reattach-{}", std::process::id());
+    let n_items = 100_000;
+    let orig: Vec<u32> = (0..n_items as u32).collect();
+
+    let mut src = VectorSource::<u32, Writer<u32>>::new(orig.clone());
+    src.output().create(&name)?;
+
+    // a reader that terminated without detaching, before the writer was added to a flowgraph
+    assert!(spawn_child("dead", &name)?.wait()?.success());
+    let mut snk = VectorSink::<u32, Reader<u32>>::new(n_items);
+    snk.input().open(&name)?;
+
+    let mut other = VectorSink::<u32, Reader<u32>>::new(n_items);
+    assert!(other.input().open(&name).is_err());
+
+    // a reader that was dropped
+    drop(snk);
+    let mut snk = VectorSink::<u32, Reader<u32>>::new(n_items);
+    snk.input().open(&name)?;
+    let mut fg_rx = Flowgraph::new();
+    let snk = fg_rx.add_block(snk);
+    let mut fg_tx = Flowgraph::new();
+    fg_tx.add_block(src);
+
+    let rx = std::thread::spawn(move || Runtime::new().run(fg_rx));
+    Runtime::new().run(fg_tx)?;
+    rx.join().unwrap()?;
+
+    assert_eq!(snk.get()?.items(), &orig);
+
+    Ok(())
+}
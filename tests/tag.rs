@@ -1,5 +1,12 @@
 use anyhow::Result;
-use futuresdr::runtime::Tag;
+use futuresdr::blocks::Apply;
+use futuresdr::blocks::ApplyNM;
+use futuresdr::blocks::Fft;
+use futuresdr::blocks::FirBuilder;
+use futuresdr::blocks::NullSink;
+use futuresdr::blocks::PfbChannelizer;
+use futuresdr::blocks::VectorSource;
+use futuresdr::prelude::*;
 
 #[test]
 fn tag_any_is() -> Result<()> {
@@ -11,3 +18,286 @@ fn tag_any_is() -> Result<()> {
     assert!(!value.is::<f32>());
     Ok(())
 }
+
+/// Tags every `interval`-th sample with its index.
+#[derive(Block)]
+#[tag_propagation(custom)]
+struct Tagger {
+    #[input]
+    input: DefaultCpuReader<f32>,
+    #[output]
+    output: DefaultCpuWriter<f32>,
+    interval: usize,
+    n_items: usize,
+}
+
+impl Tagger {
+    fn new(interval: usize) -> Self {
+        Self {
+            input: Default::default(),
+            output: Default::default(),
+            interval,
+            n_items: 0,
+        }
+    }
+}
+
+impl Kernel for Tagger {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = self.input.slice();
+        let (o, mut tags) = self.output.slice_with_tags();
+        let i_len = i.len();
+        let n = std::cmp::min(i_len, o.len());
+        o[..n].copy_from_slice(&i[..n]);
+        for k in 0..n {
+            if (self.n_items + k) % self.interval == 0 {
+                tags.add_tag(k, Tag::Id((self.n_items + k) as u64));
+            }
+        }
+        self.n_items += n;
+        self.input.consume(n);
+        self.output.produce(n);
+        if self.input.finished() && n == i_len {
+            io.finished = true;
+        }
+        Ok(())
+    }
+}
+
+/// Collects `Tag::Id` tags together with the absolute index of the tagged sample.
+#[derive(Block)]
+struct TagCollector<T: CpuSample> {
+    #[input]
+    input: DefaultCpuReader<T>,
+    n_items: usize,
+    tags: Vec<(usize, u64)>,
+}
+
+impl<T: CpuSample> TagCollector<T> {
+    fn new() -> Self {
+        Self {
+            input: Default::default(),
+            n_items: 0,
+            tags: Vec::new(),
+        }
+    }
+}
+
+impl<T: CpuSample> Kernel for TagCollector<T> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, tags) = self.input.slice_with_tags();
+        let n = i.len();
+        for t in tags.iter().filter(|t| t.index < n) {
+            if let Tag::Id(id) = t.tag {
+                self.tags.push((self.n_items + t.index, id));
+            }
+        }
+        self.n_items += n;
+        self.input.consume(n);
+        if self.input.finished() {
+            io.finished = true;
+        }
+        Ok(())
+    }
+}
+
+/// Keeps every `decimation`-th sample, consuming one sample per call to `work()`.
+#[derive(Block)]
+#[tag_propagation(one_to_one)]
+struct SlowDecimator {
+    #[input]
+    input: DefaultCpuReader<f32>,
+    #[output]
+    output: DefaultCpuWriter<f32>,
+    decimation: usize,
+    n_items: usize,
+    held_tags: Vec<Tag>,
+}
+
+impl SlowDecimator {
+    fn new(decimation: usize) -> Self {
+        Self {
+            input: Default::default(),
+            output: Default::default(),
+            decimation,
+            n_items: 0,
+            held_tags: Vec::new(),
+        }
+    }
+}
+
+impl Kernel for SlowDecimator {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, i_tags) = self.input.slice_with_tags();
+        let (o, mut o_tags) = self.output.slice_with_tags();
+        if i.is_empty() || o.is_empty() {
+            if i.is_empty() && self.input.finished() {
+                io.finished = true;
+            }
+            return Ok(());
+        }
+        self.n_items += 1;
+        let produced = if self.n_items % self.decimation == 0 {
+            o[0] = i[0];
+            1
+        } else {
+            0
+        };
+        Self::tag_propagation().forward_held(
+            0,
+            0,
+            i_tags,
+            1,
+            &mut o_tags,
+            produced,
+            &mut self.held_tags,
+        );
+        self.input.consume(1);
+        self.output.produce(produced);
+        io.call_again = true;
+        Ok(())
+    }
+}
+
+#[test]
+fn declared_policies() {
+    assert_eq!(
+        <Apply<fn(&f32) -> f32, f32, f32> as KernelInterface>::tag_propagation(),
+        TagPropagation::OneToOne
+    );
+    assert_eq!(
+        <Fft as KernelInterface>::tag_propagation(),
+        TagPropagation::OneToOne
+    );
+    assert_eq!(
+        <PfbChannelizer as KernelInterface>::tag_propagation(),
+        TagPropagation::AllToAll
+    );
+    assert_eq!(
+        <NullSink<f32> as KernelInterface>::tag_propagation(),
+        TagPropagation::Custom
+    );
+}
+
+#[test]
+fn apply_one_to_one() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = VectorSource::<f32>::new(vec![0.0; 10_000]);
+    let tagger = Tagger::new(100);
+    let apply: Apply<_, _, _> = Apply::new(|x: &f32| x + 1.0);
+    let snk = TagCollector::<f32>::new();
+
+    connect!(fg, src > tagger > apply > snk);
+    Runtime::new().run(fg)?;
+
+    let expected: Vec<(usize, u64)> = (0..100).map(|i| (i * 100, i as u64 * 100)).collect();
+    assert_eq!(snk.get()?.tags, expected);
+    Ok(())
+}
+
+#[test]
+fn fir_decimation_rescales() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = VectorSource::<f32>::new(vec![0.0; 100_000]);
+    let tagger = Tagger::new(400);
+    let fir = FirBuilder::decimating::<f32, f32, Vec<f32>>(4);
+    let snk = TagCollector::<f32>::new();
+
+    connect!(fg, src > tagger > fir > snk);
+    Runtime::new().run(fg)?;
+
+    let tags = snk.get()?.tags.clone();
+    assert!(tags.len() >= 240);
+    for (index, id) in tags {
+        assert_eq!(index as u64, id / 4);
+    }
+    Ok(())
+}
+
+#[test]
+fn decimation_holds_tags_without_output() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = VectorSource::<f32>::new(vec![0.0; 1000]);
+    let tagger = Tagger::new(10);
+    let decimator = SlowDecimator::new(4);
+    let snk = TagCollector::<f32>::new();
+
+    connect!(fg, src > tagger > decimator > snk);
+    Runtime::new().run(fg)?;
+
+    // tags of dropped samples end up at the next output sample
+    let expected: Vec<(usize, u64)> = (0..100).map(|i| (i * 10 / 4, i as u64 * 10)).collect();
+    assert_eq!(snk.get()?.tags, expected);
+    Ok(())
+}
+
+#[test]
+fn applynm_interpolation_rescales() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = VectorSource::<f32>::new(vec![0.0; 10_000]);
+    let tagger = Tagger::new(10);
+    let apply = ApplyNM::<_, _, _, 1, 3>::new(|i: &[f32], o: &mut [f32]| o.fill(i[0]));
+    let snk = TagCollector::<f32>::new();
+
+    connect!(fg, src > tagger > apply > snk);
+    Runtime::new().run(fg)?;
+
+    let expected: Vec<(usize, u64)> = (0..1000).map(|i| (i * 30, i as u64 * 10)).collect();
+    assert_eq!(snk.get()?.tags, expected);
+    Ok(())
+}
+
+#[test]
+fn channelizer_all_to_all() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let channels = 4;
+    let n_items = 40_000;
+    let src = VectorSource::<f32>::new(vec![0.0; n_items]);
+    let tagger = Tagger::new(400);
+    let to_complex: Apply<_, _, _> = Apply::new(|x: &f32| Complex32::new(*x, 0.0));
+    let taps = futuredsp::firdes::kaiser::lowpass::<f32>(0.2, 0.1, 0.001);
+    let channelizer: PfbChannelizer = PfbChannelizer::new(channels, &taps, 1.0);
+    connect!(fg, src > tagger > to_complex > channelizer);
+
+    let mut snks = Vec::new();
+    for c in 0..channels {
+        let snk = fg.add_block(TagCollector::<Complex32>::new());
+        fg.connect_dyn(&channelizer, format!("outputs[{c}]"), &snk, "input")?;
+        snks.push(snk);
+    }
+
+    Runtime::new().run(fg)?;
+
+    let first = snks[0].get()?.tags.clone();
+    // the first samples only fill the filter history
+    assert!(first.len() >= n_items / 400 - 1);
+    // constant offset due to the consumed filter history
+    let offset = first[0].1 - first[0].0 as u64 * channels as u64;
+    for (index, id) in first.iter() {
+        assert_eq!(*index as u64, (*id - offset) / channels as u64);
+    }
+    for snk in snks.iter().skip(1) {
+        assert_eq!(snk.get()?.tags, first);
+    }
+    Ok(())
+}
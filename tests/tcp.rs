@@ -0,0 +1,210 @@
+use anyhow::Result;
+use futuresdr::async_io::Timer;
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::ChannelSource;
+use futuresdr::blocks::Head;
+use futuresdr::blocks::TcpFraming;
+use futuresdr::blocks::TcpSinkBuilder;
+use futuresdr::blocks::TcpSourceBuilder;
+use futuresdr::blocks::VectorSink;
+use futuresdr::futures::SinkExt;
+use futuresdr::prelude::*;
+use std::io::Write;
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// Outputs `n_items` samples and tags every `interval`-th sample with its index.
+#[derive(Block)]
+struct TaggedSource {
+    #[output]
+    output: DefaultCpuWriter<f32>,
+    n_items: usize,
+    interval: usize,
+    produced: usize,
+}
+
+impl TaggedSource {
+    fn new(n_items: usize, interval: usize) -> Self {
+        Self {
+            output: Default::default(),
+            n_items,
+            interval,
+            produced: 0,
+        }
+    }
+}
+
+impl Kernel for TaggedSource {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (o, mut tags) = self.output.slice_with_tags();
+        let n = std::cmp::min(o.len(), self.n_items - self.produced);
+        for (k, v) in o[..n].iter_mut().enumerate() {
+            let index = self.produced + k;
+            *v = index as f32;
+            if index % self.interval == 0 {
+                tags.add_tag(k, Tag::Id(index as u64));
+                tags.add_tag(k, Tag::NamedAny("local".to_string(), Box::new(index)));
+            }
+        }
+        self.produced += n;
+        self.output.produce(n);
+        if self.produced == self.n_items {
+            io.finished = true;
+        }
+        Ok(())
+    }
+}
+
+/// Collects samples and tags together with the absolute index of the tagged sample.
+#[derive(Block)]
+struct TagCollector {
+    #[input]
+    input: DefaultCpuReader<f32>,
+    items: Vec<f32>,
+    tags: Vec<(usize, Tag)>,
+}
+
+impl TagCollector {
+    fn new() -> Self {
+        Self {
+            input: Default::default(),
+            items: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+}
+
+impl Kernel for TagCollector {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, tags) = self.input.slice_with_tags();
+        let n = i.len();
+        for t in tags.iter().filter(|t| t.index < n) {
+            self.tags.push((self.items.len() + t.index, t.tag.clone()));
+        }
+        self.items.extend_from_slice(i);
+        self.input.consume(n);
+        if self.input.finished() {
+            io.finished = true;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn framing_carries_tags() -> Result<()> {
+    let n_items = 100_000;
+    let interval = 1000;
+
+    let mut fg_tx = Flowgraph::new();
+    let src = TaggedSource::new(n_items, interval);
+    let tcp_snk = TcpSinkBuilder::<f32>::server("127.0.0.1:34801")
+        .framing(TcpFraming::LengthPrefixed)
+        .build();
+    connect!(fg_tx, src > tcp_snk);
+
+    let mut fg_rx = Flowgraph::new();
+    let tcp_src = TcpSourceBuilder::<f32>::client("127.0.0.1:34801")
+        .framing(TcpFraming::LengthPrefixed)
+        .build();
+    let snk = TagCollector::new();
+    connect!(fg_rx, tcp_src > snk);
+
+    let rt = Runtime::new();
+    let (tx, _handle) = rt.start_sync(fg_tx)?;
+    rt.run(fg_rx)?;
+    block_on(tx)?;
+
+    let snk = snk.get()?;
+    let expected: Vec<f32> = (0..n_items).map(|i| i as f32).collect();
+    assert_eq!(snk.items, expected);
+    let expected: Vec<(usize, Tag)> = (0..n_items)
+        .step_by(interval)
+        .map(|i| (i, Tag::Id(i as u64)))
+        .collect();
+    assert_eq!(snk.tags, expected);
+    Ok(())
+}
+
+#[test]
+fn fan_out_and_reconnect() -> Result<()> {
+    let n_items = 10_000;
+    let rt = Runtime::new();
+
+    // clients start before the server and retry until it is up
+    let mut clients = Vec::new();
+    let mut sinks = Vec::new();
+    for _ in 0..2 {
+        let mut fg = Flowgraph::new();
+        let tcp_src = TcpSourceBuilder::<u32>::client("127.0.0.1:34802")
+            .reconnect(true)
+            .build();
+        let head = Head::<u32>::new(n_items as u64);
+        let snk = VectorSink::<u32>::new(n_items);
+        connect!(fg, tcp_src > head > snk);
+        clients.push(rt.start_sync(fg)?.0);
+        sinks.push(snk);
+    }
+
+    block_on(Timer::after(Duration::from_millis(300)));
+
+    let mut fg = Flowgraph::new();
+    let (mut tx, rx) = mpsc::channel(10);
+    let src = ChannelSource::<u32>::new(rx);
+    let tcp_snk = TcpSinkBuilder::<u32>::server("127.0.0.1:34802").build();
+    connect!(fg, src > tcp_snk);
+    let (server, _handle) = rt.start_sync(fg)?;
+
+    block_on(async move {
+        // wait for both clients to connect
+        Timer::after(Duration::from_secs(1)).await;
+        let data: Vec<u32> = (0..n_items as u32).collect();
+        tx.send(data.into_boxed_slice()).await?;
+        tx.close().await?;
+        for c in clients {
+            c.await?;
+        }
+        server.await?;
+        Ok::<_, anyhow::Error>(())
+    })?;
+
+    let expected: Vec<u32> = (0..n_items as u32).collect();
+    for snk in sinks {
+        assert_eq!(snk.get()?.items(), &expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn oversized_frame_header() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let tcp_src = TcpSourceBuilder::<f32>::server("127.0.0.1:34803")
+        .framing(TcpFraming::LengthPrefixed)
+        .build();
+    let snk = VectorSink::<f32>::new(1);
+    connect!(fg, tcp_src > snk);
+
+    let rt = Runtime::new();
+    let (task, _handle) = rt.start_sync(fg)?;
+
+    // announce 2 MiB of tags, which exceeds the receive buffer, and keep the connection open
+    let mut stream = TcpStream::connect("127.0.0.1:34803")?;
+    stream.write_all(&1u32.to_be_bytes())?;
+    stream.write_all(&(2u32 << 20).to_be_bytes())?;
+    stream.write_all(&vec![b' '; 1 << 16])?;
+
+    // the source drops the connection and finishes
+    block_on(task)?;
+    assert!(snk.get()?.items().is_empty());
+    drop(stream);
+    Ok(())
+}
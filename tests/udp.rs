@@ -0,0 +1,172 @@
+use anyhow::Result;
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::BlobToUdp;
+use futuresdr::blocks::Head;
+use futuresdr::blocks::MessagePipe;
+use futuresdr::blocks::UdpSink;
+use futuresdr::blocks::UdpSource;
+use futuresdr::blocks::UdpToBlob;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSource;
+use futuresdr::prelude::*;
+use futuresdr::runtime::PmtEncoding;
+use std::net::UdpSocket;
+
+#[test]
+fn round_trip_with_sequence_numbers() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let n_items = 4096;
+    let orig: Vec<Complex32> = (0..n_items)
+        .map(|i| Complex32::new(i as f32, -(i as f32)))
+        .collect();
+
+    let src = VectorSource::<Complex32>::new(orig.clone());
+    let udp_snk = UdpSink::<Complex32>::with_sequence_numbers("127.0.0.1:34781", 8192);
+    let udp_src = UdpSource::<Complex32>::with_sequence_numbers("127.0.0.1:34781", 8192);
+    let head = Head::<Complex32>::new(n_items as u64);
+    let snk = VectorSink::<Complex32>::new(n_items);
+
+    connect!(fg, src > udp_snk; udp_src > head > snk);
+
+    Runtime::new().run(fg)?;
+
+    assert_eq!(snk.get()?.items(), &orig);
+    assert_eq!(udp_src.get()?.lost(), 0);
+    assert_eq!(udp_src.get()?.reordered(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn loss_and_reorder_detection() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let udp_src = UdpSource::<u32>::with_sequence_numbers("127.0.0.1:34782", 1024);
+    let head = Head::<u32>::new(16);
+    let snk = VectorSink::<u32>::new(16);
+
+    connect!(fg, udp_src > head > snk);
+
+    let rt = Runtime::new();
+    let (task, _handle) = rt.start_sync(fg)?;
+
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+    for seq in [0u64, 1, 3, 2, 4] {
+        let mut datagram = seq.to_le_bytes().to_vec();
+        for i in 0..4u32 {
+            datagram.extend_from_slice(&(seq as u32 * 4 + i).to_ne_bytes());
+        }
+        socket.send_to(&datagram, "127.0.0.1:34782")?;
+    }
+
+    block_on(task)?;
+
+    let expected: Vec<u32> = [0, 1, 3, 4]
+        .iter()
+        .flat_map(|seq| (0..4).map(move |i| seq * 4 + i))
+        .collect();
+    assert_eq!(snk.get()?.items(), &expected);
+    assert_eq!(udp_src.get()?.lost(), 1);
+    assert_eq!(udp_src.get()?.reordered(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn sender_restart() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let udp_src = UdpSource::<u32>::with_sequence_numbers("127.0.0.1:34785", 1024);
+    let head = Head::<u32>::new(20);
+    let snk = VectorSink::<u32>::new(20);
+
+    connect!(fg, udp_src > head > snk);
+
+    let rt = Runtime::new();
+    let (task, _handle) = rt.start_sync(fg)?;
+
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+    for seq in [1000u64, 1001, 1002, 0, 1] {
+        let mut datagram = seq.to_le_bytes().to_vec();
+        for i in 0..4u32 {
+            datagram.extend_from_slice(&(seq as u32 * 4 + i).to_ne_bytes());
+        }
+        socket.send_to(&datagram, "127.0.0.1:34785")?;
+    }
+
+    block_on(task)?;
+
+    let expected: Vec<u32> = [1000, 1001, 1002, 0, 1]
+        .iter()
+        .flat_map(|seq| (0..4).map(move |i| seq * 4 + i))
+        .collect();
+    assert_eq!(snk.get()?.items(), &expected);
+    assert_eq!(udp_src.get()?.lost(), 0);
+    assert_eq!(udp_src.get()?.reordered(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn udp_to_blob() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let (tx, mut rx) = mpsc::channel(10);
+    let src = UdpToBlob::new("127.0.0.1:34783", 1500);
+    let pipe = MessagePipe::new(tx);
+
+    connect!(fg, src | pipe);
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start_sync(fg)?;
+
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+    for i in 0..3u8 {
+        socket.send_to(&[i; 10], "127.0.0.1:34783")?;
+    }
+
+    block_on(async move {
+        for i in 0..3u8 {
+            assert_eq!(rx.next().await, Some(Pmt::Blob(vec![i; 10])));
+        }
+        handle.terminate().await?;
+        task.await?;
+        Ok(())
+    })
+}
+
+#[test]
+fn encoded_pmts() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let (tx, mut rx) = mpsc::channel(10);
+    let src = UdpToBlob::with_encoding("127.0.0.1:34784", 1500, PmtEncoding::MessagePack);
+    let pipe = MessagePipe::new(tx);
+    connect!(fg, src | pipe);
+    let snk = fg.add_block(BlobToUdp::with_encoding(
+        "127.0.0.1:34784",
+        PmtEncoding::MessagePack,
+    ));
+    let snk_id: BlockId = snk.into();
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start_sync(fg)?;
+
+    block_on(async move {
+        let pmts = [
+            Pmt::F64(2.4e9),
+            Pmt::String("foo".to_string()),
+            Pmt::VecU64(vec![1, 2, 3]),
+        ];
+        for p in pmts.iter() {
+            handle.call(snk_id, "in", p.clone()).await?;
+        }
+        for p in pmts {
+            assert_eq!(rx.next().await, Some(p));
+        }
+        handle.terminate().await?;
+        task.await?;
+        Ok(())
+    })
+}
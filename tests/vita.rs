@@ -0,0 +1,218 @@
+use anyhow::Result;
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::Head;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSource;
+use futuresdr::blocks::vita::CONTEXT_TAG;
+use futuresdr::blocks::vita::Context;
+use futuresdr::blocks::vita::Format;
+use futuresdr::blocks::vita::STREAM_ID_TAG;
+use futuresdr::blocks::vita::TIME_TAG;
+use futuresdr::blocks::vita::Timestamp;
+use futuresdr::blocks::vita::Tsf;
+use futuresdr::blocks::vita::Tsi;
+use futuresdr::blocks::vita::VitaSink;
+use futuresdr::blocks::vita::VitaSource;
+use futuresdr::prelude::*;
+use std::net::UdpSocket;
+
+/// Collects `n_items` samples and all tags together with the absolute index of the tagged sample.
+#[derive(Block)]
+struct TagCollector {
+    #[input]
+    input: DefaultCpuReader<Complex32>,
+    n_items: usize,
+    items: Vec<Complex32>,
+    tags: Vec<(usize, Tag)>,
+    done: Option<oneshot::Sender<()>>,
+}
+
+impl TagCollector {
+    fn new(n_items: usize, done: oneshot::Sender<()>) -> Self {
+        Self {
+            input: Default::default(),
+            n_items,
+            items: Vec::new(),
+            tags: Vec::new(),
+            done: Some(done),
+        }
+    }
+}
+
+impl Kernel for TagCollector {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, tags) = self.input.slice_with_tags();
+        let n = std::cmp::min(i.len(), self.n_items - self.items.len());
+        for t in tags.iter().filter(|t| t.index < n) {
+            self.tags.push((self.items.len() + t.index, t.tag.clone()));
+        }
+        self.items.extend_from_slice(&i[..n]);
+        self.input.consume(n);
+        if self.items.len() == self.n_items {
+            if let Some(done) = self.done.take() {
+                let _ = done.send(());
+            }
+            io.finished = true;
+        }
+        Ok(())
+    }
+}
+
+fn words(w: &[u32]) -> Vec<u8> {
+    w.iter().flat_map(|w| w.to_be_bytes()).collect()
+}
+
+#[test]
+fn udp_round_trip() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let n_items = 10_000;
+    let orig: Vec<Complex32> = (0..n_items)
+        .map(|i| Complex32::new(i as f32, -(i as f32)))
+        .collect();
+
+    let src = VectorSource::<Complex32>::new(orig.clone());
+    let vita_snk = VitaSink::udp("127.0.0.1:34791", 1, Format::Cf32, 1000);
+    let vita_src: VitaSource = VitaSource::udp("127.0.0.1:34791", Format::Cf32);
+    let head = Head::<Complex32>::new(n_items as u64);
+    let snk = VectorSink::<Complex32>::new(n_items);
+
+    connect!(fg, src > vita_snk; vita_src > head > snk);
+
+    Runtime::new().run(fg)?;
+
+    assert_eq!(snk.get()?.items(), &orig);
+    Ok(())
+}
+
+#[test]
+fn tcp_round_trip() -> Result<()> {
+    let n_items = 10_000;
+    let orig: Vec<Complex32> = (0..n_items)
+        .map(|i| Complex32::new(i as f32 / n_items as f32, 0.5))
+        .collect();
+
+    let mut fg_tx = Flowgraph::new();
+    let src = VectorSource::<Complex32>::new(orig.clone());
+    let vita_snk = VitaSink::tcp("127.0.0.1:34792", 1, Format::Sc16, 360);
+    connect!(fg_tx, src > vita_snk);
+
+    let mut fg_rx = Flowgraph::new();
+    let vita_src: VitaSource = VitaSource::tcp("127.0.0.1:34792", Format::Sc16);
+    let snk = VectorSink::<Complex32>::new(n_items);
+    connect!(fg_rx, vita_src > snk);
+
+    let rt = Runtime::new();
+    let (tx, _handle) = rt.start_sync(fg_tx)?;
+    rt.run(fg_rx)?;
+    block_on(tx)?;
+
+    let items = snk.get()?.items().clone();
+    assert_eq!(items.len(), n_items);
+    for (a, b) in items.iter().zip(orig.iter()) {
+        assert!((a - b).norm() < 1.0 / 32768.0);
+    }
+    Ok(())
+}
+
+#[test]
+fn context_and_timestamps_map_to_tags() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let (done_tx, done_rx) = oneshot::channel();
+    let rx: VitaSource = VitaSource::udp("127.0.0.1:34793", Format::Cf32);
+    let tx = VitaSink::udp("127.0.0.1:34794", 9, Format::Sc16, 8);
+    let src: VitaSource = VitaSource::udp("127.0.0.1:34794", Format::Sc16);
+    let snk = TagCollector::new(16, done_tx);
+
+    connect!(fg, rx > tx; src > snk);
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start_sync(fg)?;
+
+    let rf = (2.4e9 * (1u64 << 20) as f64) as u64;
+    let rate = (1e6 * (1u64 << 20) as f64) as u64;
+    let context = words(&[
+        0x4040_0008,
+        7,
+        1000,
+        (1 << 31) | (1 << 27) | (1 << 21),
+        (rf >> 32) as u32,
+        rf as u32,
+        (rate >> 32) as u32,
+        rate as u32,
+    ]);
+
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+    socket.send_to(&context, "127.0.0.1:34793")?;
+    for (p, frac) in [0u32, 8].into_iter().enumerate() {
+        let mut data = words(&[0x1050_0015 | ((p as u32) << 16), 7, 1000, 0, frac]);
+        for i in 0..8 {
+            let v = (p * 8 + i) as f32 / 16.0;
+            data.extend_from_slice(&v.to_be_bytes());
+            data.extend_from_slice(&(-v).to_be_bytes());
+        }
+        socket.send_to(&data, "127.0.0.1:34793")?;
+    }
+
+    block_on(async move {
+        done_rx.await?;
+        handle.terminate().await?;
+        task.await
+    })?;
+
+    let snk = snk.get()?;
+    let expected: Vec<Complex32> = (0..16)
+        .map(|i| Complex32::new(i as f32 / 16.0, -(i as f32) / 16.0))
+        .collect();
+    assert_eq!(snk.items, expected);
+
+    let time = |index: usize| {
+        snk.tags.iter().find_map(|(i, t)| match t {
+            Tag::NamedAny(n, a) if *i == index && n == TIME_TAG => a.downcast_ref::<Timestamp>(),
+            _ => None,
+        })
+    };
+    for (index, frac) in [(0, 0), (8, 8)] {
+        assert_eq!(
+            time(index),
+            Some(&Timestamp {
+                tsi: Tsi::Utc,
+                integer: 1000,
+                tsf: Tsf::SampleCount,
+                fractional: frac,
+            })
+        );
+    }
+
+    assert!(
+        snk.tags
+            .iter()
+            .any(|t| *t == (0, Tag::NamedUsize(STREAM_ID_TAG.to_string(), 9)))
+    );
+
+    let contexts: Vec<&Context> = snk
+        .tags
+        .iter()
+        .filter_map(|(i, t)| match t {
+            Tag::NamedAny(n, a) if n == CONTEXT_TAG => {
+                assert_eq!(*i, 0);
+                a.downcast_ref::<Context>()
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(contexts.len(), 1);
+    assert_eq!(contexts[0].stream_id, 9);
+    assert_eq!(contexts[0].timestamp.integer, 1000);
+    assert_eq!(contexts[0].rf_reference_frequency, Some(2.4e9));
+    assert_eq!(contexts[0].sample_rate, Some(1e6));
+    assert_eq!(contexts[0].bandwidth, None);
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anyhow::Result;
+use async_tungstenite::tungstenite::Message;
+use futuresdr::async_io::Async;
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::Head;
+use futuresdr::blocks::MessagePipe;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::WebsocketPmtSource;
+use futuresdr::blocks::WebsocketSource;
+use futuresdr::prelude::*;
+use futuresdr::runtime::PmtEncoding;
+use std::net::TcpStream;
+
+async fn connect(port: u32) -> Result<async_tungstenite::WebSocketStream<Async<TcpStream>>> {
+    let stream = Async::<TcpStream>::connect(([127, 0, 0, 1], port as u16)).await?;
+    let (ws, _) = async_tungstenite::client_async(format!("ws://127.0.0.1:{port}"), stream).await?;
+    Ok(ws)
+}
+
+#[test]
+fn websocket_source() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let n_items = 1000;
+    let src = WebsocketSource::<u32>::new(34811);
+    let head = Head::<u32>::new(n_items as u64);
+    let snk = VectorSink::<u32>::new(n_items);
+    connect!(fg, src > head > snk);
+
+    let rt = Runtime::new();
+    let (task, _handle) = rt.start_sync(fg)?;
+
+    let orig: Vec<u32> = (0..n_items as u32).collect();
+    let bytes: Vec<u8> = orig.iter().flat_map(|v| v.to_ne_bytes()).collect();
+
+    block_on(async move {
+        // messages do not have to contain whole samples
+        let mut ws = connect(34811).await?;
+        for chunk in bytes.chunks(999) {
+            ws.send(Message::binary(chunk.to_vec())).await?;
+        }
+        task.await?;
+        Ok::<_, anyhow::Error>(())
+    })?;
+
+    assert_eq!(snk.get()?.items(), &orig);
+    Ok(())
+}
+
+#[test]
+fn websocket_pmt_source() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let (tx, mut rx) = mpsc::channel(10);
+    let src = WebsocketPmtSource::new(34812);
+    let pipe = MessagePipe::new(tx);
+    connect!(fg, src | pipe);
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start_sync(fg)?;
+
+    block_on(async move {
+        let mut ws = connect(34812).await?;
+        ws.send(Message::text(r#"{"U32": 5}"#)).await?;
+        ws.send(Message::text("not a pmt")).await?;
+        let cbor = PmtEncoding::Cbor.encode(&Pmt::String("foo".to_string()))?;
+        ws.send(Message::binary(cbor)).await?;
+        ws.close(None).await?;
+
+        // next client
+        let mut ws = connect(34812).await?;
+        ws.send(Message::text(r#""Null""#)).await?;
+
+        assert_eq!(rx.next().await, Some(Pmt::U32(5)));
+        assert_eq!(rx.next().await, Some(Pmt::String("foo".to_string())));
+        assert_eq!(rx.next().await, Some(Pmt::Null));
+
+        handle.terminate().await?;
+        task.await?;
+        Ok(())
+    })
+}
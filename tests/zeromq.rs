@@ -0,0 +1,302 @@
+use anyhow::Result;
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::Head;
+use futuresdr::blocks::MessagePipe;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSource;
+use futuresdr::blocks::zeromq::MessageSink;
+use futuresdr::blocks::zeromq::MessageSinkSocket;
+use futuresdr::blocks::zeromq::MessageSource;
+use futuresdr::blocks::zeromq::MessageSourceSocket;
+use futuresdr::blocks::zeromq::PubSinkBuilder;
+use futuresdr::blocks::zeromq::PullSourceBuilder;
+use futuresdr::blocks::zeromq::PushSinkBuilder;
+use futuresdr::blocks::zeromq::RepSinkBuilder;
+use futuresdr::blocks::zeromq::ReqSourceBuilder;
+use futuresdr::blocks::zeromq::SubSourceBuilder;
+use futuresdr::prelude::*;
+use futuresdr::runtime::PmtEncoding;
+use std::collections::HashMap;
+
+/// Outputs `n_items` samples and tags every `interval`-th sample.
+#[derive(Block)]
+struct TaggedSource {
+    #[output]
+    output: DefaultCpuWriter<f32>,
+    n_items: usize,
+    interval: usize,
+    produced: usize,
+}
+
+impl TaggedSource {
+    fn new(n_items: usize, interval: usize) -> Self {
+        Self {
+            output: Default::default(),
+            n_items,
+            interval,
+            produced: 0,
+        }
+    }
+}
+
+impl Kernel for TaggedSource {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (o, mut tags) = self.output.slice_with_tags();
+        let n = std::cmp::min(o.len(), self.n_items - self.produced);
+        for (k, v) in o[..n].iter_mut().enumerate() {
+            let index = self.produced + k;
+            *v = index as f32;
+            if index % self.interval == 0 {
+                tags.add_tag(k, Tag::Id(index as u64));
+                tags.add_tag(k, Tag::NamedF32("gain".to_string(), 0.5));
+            }
+        }
+        self.produced += n;
+        self.output.produce(n);
+        if self.produced == self.n_items {
+            io.finished = true;
+        }
+        Ok(())
+    }
+}
+
+/// Collects `n_items` samples and their tags.
+#[derive(Block)]
+struct TagCollector {
+    #[input]
+    input: DefaultCpuReader<f32>,
+    n_items: usize,
+    items: Vec<f32>,
+    tags: Vec<(usize, Tag)>,
+}
+
+impl TagCollector {
+    fn new(n_items: usize) -> Self {
+        Self {
+            input: Default::default(),
+            n_items,
+            items: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+}
+
+impl Kernel for TagCollector {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _mio: &mut MessageOutputs,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let (i, tags) = self.input.slice_with_tags();
+        let n = std::cmp::min(i.len(), self.n_items - self.items.len());
+        for t in tags.iter().filter(|t| t.index < n) {
+            self.tags.push((self.items.len() + t.index, t.tag.clone()));
+        }
+        self.items.extend_from_slice(&i[..n]);
+        self.input.consume(n);
+        if self.items.len() == self.n_items || self.input.finished() {
+            io.finished = true;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn push_pull_with_tags() -> Result<()> {
+    let n_items = 100_000;
+    let interval = 1000;
+
+    let mut fg_tx = Flowgraph::new();
+    let src = TaggedSource::new(n_items, interval);
+    let zmq_snk = PushSinkBuilder::<f32>::new()
+        .address("tcp://127.0.0.1:34821")
+        .pass_tags(true)
+        .build();
+    connect!(fg_tx, src > zmq_snk);
+
+    let mut fg_rx = Flowgraph::new();
+    let zmq_src = PullSourceBuilder::<f32>::new()
+        .address("tcp://127.0.0.1:34821")
+        .pass_tags(true)
+        .build();
+    let snk = TagCollector::new(n_items);
+    connect!(fg_rx, zmq_src > snk);
+
+    let rt = Runtime::new();
+    let (tx, _handle) = rt.start_sync(fg_tx)?;
+    rt.run(fg_rx)?;
+    block_on(tx)?;
+
+    let snk = snk.get()?;
+    let expected: Vec<f32> = (0..n_items).map(|i| i as f32).collect();
+    assert_eq!(snk.items, expected);
+    let expected: Vec<(usize, Tag)> = (0..n_items)
+        .step_by(interval)
+        .flat_map(|i| {
+            [
+                (i, Tag::Id(i as u64)),
+                (i, Tag::NamedF32("gain".to_string(), 0.5)),
+            ]
+        })
+        .collect();
+    assert_eq!(snk.tags, expected);
+    Ok(())
+}
+
+#[test]
+fn pub_sub_with_tags() -> Result<()> {
+    let n_items = 100_000;
+    let interval = 1000;
+
+    // publish until the subscriber is done, since messages before the subscription are dropped
+    let mut fg_tx = Flowgraph::new();
+    let src = TaggedSource::new(10_000_000, interval);
+    let zmq_snk = PubSinkBuilder::<f32>::new()
+        .address("tcp://127.0.0.1:34825")
+        .pass_tags(true)
+        .build();
+    connect!(fg_tx, src > zmq_snk);
+
+    let mut fg_rx = Flowgraph::new();
+    let zmq_src = SubSourceBuilder::<f32>::new()
+        .address("tcp://127.0.0.1:34825")
+        .pass_tags(true)
+        .build();
+    let snk = TagCollector::new(n_items);
+    connect!(fg_rx, zmq_src > snk);
+
+    let rt = Runtime::new();
+    let (tx, mut handle) = rt.start_sync(fg_tx)?;
+    rt.run(fg_rx)?;
+    block_on(async move {
+        handle.terminate().await?;
+        tx.await
+    })?;
+
+    let snk = snk.get()?;
+    assert_eq!(snk.items.len(), n_items);
+    let expected: Vec<(usize, Tag)> = snk
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| **v as usize % interval == 0)
+        .flat_map(|(i, v)| {
+            [
+                (i, Tag::Id(*v as u64)),
+                (i, Tag::NamedF32("gain".to_string(), 0.5)),
+            ]
+        })
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(snk.tags, expected);
+    Ok(())
+}
+
+#[test]
+fn req_rep() -> Result<()> {
+    let n_items = 100_000;
+    let orig: Vec<u32> = (0..n_items as u32).collect();
+
+    let mut fg_tx = Flowgraph::new();
+    let src = VectorSource::<u32>::new(orig.clone());
+    let zmq_snk = RepSinkBuilder::<u32>::new()
+        .address("tcp://127.0.0.1:34822")
+        .build();
+    connect!(fg_tx, src > zmq_snk);
+
+    let mut fg_rx = Flowgraph::new();
+    let zmq_src = ReqSourceBuilder::<u32>::new()
+        .address("tcp://127.0.0.1:34822")
+        .build();
+    let head = Head::<u32>::new(n_items as u64);
+    let snk = VectorSink::<u32>::new(n_items);
+    connect!(fg_rx, zmq_src > head > snk);
+
+    let rt = Runtime::new();
+    let (tx, _handle) = rt.start_sync(fg_tx)?;
+    rt.run(fg_rx)?;
+    block_on(tx)?;
+
+    assert_eq!(snk.get()?.items(), &orig);
+    Ok(())
+}
+
+#[test]
+fn messages() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let zmq_snk = MessageSink::new("tcp://127.0.0.1:34823", MessageSinkSocket::Push);
+    let zmq_src = MessageSource::new("tcp://127.0.0.1:34823", MessageSourceSocket::Pull);
+    let (tx, mut rx) = mpsc::channel(10);
+    let pipe = MessagePipe::new(tx);
+    connect!(fg, zmq_src | pipe);
+    let zmq_snk = fg.add_block(zmq_snk);
+    let snk_id: BlockId = zmq_snk.into();
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start_sync(fg)?;
+
+    block_on(async move {
+        let pmts = [
+            Pmt::String("foo".to_string()),
+            Pmt::U64(42),
+            Pmt::VecF32(vec![1.0, 2.0]),
+            Pmt::MapStrPmt(HashMap::from([("freq".to_string(), Pmt::F64(2.4e9))])),
+        ];
+        for p in pmts.iter() {
+            assert_eq!(handle.callback(snk_id, "in", p.clone()).await?, Pmt::Ok);
+        }
+        assert_eq!(handle.callback(snk_id, "in", Pmt::Finished).await?, Pmt::Ok);
+
+        for p in pmts {
+            assert_eq!(rx.next().await, Some(p));
+        }
+
+        handle.terminate().await?;
+        task.await?;
+        Ok(())
+    })
+}
+
+#[test]
+fn encoded_messages() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let zmq_snk = MessageSink::with_encoding(
+        "tcp://127.0.0.1:34824",
+        MessageSinkSocket::Rep,
+        PmtEncoding::Cbor,
+    );
+    let zmq_src = MessageSource::with_encoding(
+        "tcp://127.0.0.1:34824",
+        MessageSourceSocket::Req,
+        PmtEncoding::Cbor,
+    );
+    let (tx, mut rx) = mpsc::channel(10);
+    let pipe = MessagePipe::new(tx);
+    connect!(fg, zmq_src | pipe);
+    let zmq_snk = fg.add_block(zmq_snk);
+    let snk_id: BlockId = zmq_snk.into();
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start_sync(fg)?;
+
+    block_on(async move {
+        // not representable in GNU Radio's format
+        let pmts = [Pmt::Ok, Pmt::U32(7), Pmt::InvalidValue];
+        for p in pmts.iter() {
+            assert_eq!(handle.callback(snk_id, "in", p.clone()).await?, Pmt::Ok);
+        }
+        for p in pmts {
+            assert_eq!(rx.next().await, Some(p));
+        }
+
+        handle.terminate().await?;
+        task.await?;
+        Ok(())
+    })
+}